@@ -9,7 +9,8 @@ use crate::api::endpoints;
 use crate::api::response::ApiResponse;
 use crate::api::types::{
     ClaimRequest, ClaimResponse, EarningsResponse, LaunchRequest, LaunchResponse,
-    LeaderboardResponse, StatsResponse, WalletRequest,
+    LeaderboardResponse, PreparedTransaction, StatsResponse, WalletChallenge,
+    WalletChallengeRequest, WalletRequest,
 };
 use crate::models::Token;
 
@@ -59,6 +60,27 @@ impl ApiClient {
         api_resp.into_result()
     }
 
+    /// Ask the backend to assemble a launch transaction without submitting it.
+    pub async fn prepare_launch(&self, request: LaunchRequest) -> Result<PreparedTransaction> {
+        let url = endpoints::build_url(&self.base_url, endpoints::LAUNCH_PREPARE, &[]);
+        debug!("POST {}", url);
+
+        let resp = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send launch prepare request")?;
+
+        let api_resp: ApiResponse<PreparedTransaction> = resp
+            .json()
+            .await
+            .context("Failed to parse launch prepare response")?;
+
+        api_resp.into_result()
+    }
+
     /// Retrieve all tokens launched by the specified agent.
     pub async fn get_tokens(&self, agent_id: &str) -> Result<Vec<Token>> {
         let url = endpoints::build_url(
@@ -97,6 +119,16 @@ impl ApiClient {
         api_resp.into_result()
     }
 
+    /// Ask the backend to assemble a claim transaction without submitting it.
+    pub async fn prepare_claim(&self, request: ClaimRequest) -> Result<PreparedTransaction> {
+        let url = endpoints::build_url(&self.base_url, endpoints::CLAIM_PREPARE, &[]);
+        debug!("POST {}", url);
+
+        let resp = self.client.post(&url).json(&request).send().await.context("Failed to send claim prepare request")?;
+        let api_resp: ApiResponse<PreparedTransaction> = resp.json().await.context("Failed to parse claim prepare response")?;
+        api_resp.into_result()
+    }
+
     /// Associate a wallet address with an agent.
     pub async fn set_wallet(&self, request: WalletRequest) -> Result<()> {
         let url = endpoints::build_url(&self.base_url, endpoints::WALLET, &[]);
@@ -108,6 +140,19 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Request a single-use nonce for proving ownership of a wallet.
+    pub async fn wallet_challenge(
+        &self,
+        request: WalletChallengeRequest,
+    ) -> Result<WalletChallenge> {
+        let url = endpoints::build_url(&self.base_url, endpoints::WALLET_CHALLENGE, &[]);
+        debug!("POST {}", url);
+
+        let resp = self.client.post(&url).json(&request).send().await.context("Failed to send wallet challenge request")?;
+        let api_resp: ApiResponse<WalletChallenge> = resp.json().await.context("Failed to parse wallet challenge response")?;
+        api_resp.into_result()
+    }
+
     /// Fetch the leaderboard with the given filters.
     pub async fn get_leaderboard(
         &self,
@@ -4,10 +4,13 @@ pub const BASE_URL: &str = "https://api.frogpump.fun/v1";
 /// Endpoint paths (appended to base URL).
 pub const LAUNCH: &str = "/launch";
 pub const LAUNCH_SELF_FUNDED: &str = "/launch/self-funded";
+pub const LAUNCH_PREPARE: &str = "/launch/prepare";
 pub const TOKENS: &str = "/tokens";
 pub const EARNINGS: &str = "/earnings";
 pub const CLAIM: &str = "/claim";
+pub const CLAIM_PREPARE: &str = "/claim/prepare";
 pub const WALLET: &str = "/wallet";
+pub const WALLET_CHALLENGE: &str = "/wallet/challenge";
 pub const LEADERBOARD: &str = "/leaderboard";
 pub const STATS: &str = "/stats";
 pub const UPLOAD: &str = "/upload";
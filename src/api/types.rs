@@ -13,6 +13,18 @@ pub struct LaunchRequest {
     pub image_url: Option<String>,
     pub agent_id: String,
     pub self_funded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price: Option<u64>,
+    /// Lamports the agent spends buying its own token in the launch transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dev_buy_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dev_buy_slippage_bps: Option<u64>,
+    /// Mint whose keypair the CLI holds and signs with, instead of one the backend generates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_address: Option<String>,
 }
 
 /// Response returned after a successful token launch.
@@ -23,6 +35,20 @@ pub struct LaunchResponse {
     pub pump_fun_url: String,
 }
 
+/// A transaction assembled by the backend for the CLI to inspect, sign or simulate.
+///
+/// Gasless transactions arrive already signed by the platform fee payer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedTransaction {
+    /// Base64-encoded wire-format transaction.
+    pub transaction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_address: Option<String>,
+    /// Platform key paying the fees of a gasless transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<String>,
+}
+
 /// Response containing earnings data for an agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EarningsResponse {
@@ -52,8 +78,32 @@ pub struct ClaimResponse {
 pub struct WalletRequest {
     pub agent_id: String,
     pub wallet_address: String,
+    /// Base58 ed25519 signature over `message` wrapped in the Solana off-chain message format.
     pub signature: String,
+    /// The verification text that was signed; it repeats the fields below.
     pub message: String,
+    /// Cluster the wallet is being linked on, e.g. "mainnet-beta".
+    pub network: String,
+    /// When the challenge was issued (RFC 3339), as returned by the challenge endpoint.
+    pub issued_at: String,
+    /// Single-use nonce from the challenge endpoint.
+    pub nonce: String,
+}
+
+/// Request payload for a wallet verification challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletChallengeRequest {
+    pub agent_id: String,
+    pub wallet_address: String,
+}
+
+/// A single-use nonce the wallet must sign to prove ownership.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletChallenge {
+    pub nonce: String,
+    pub issued_at: String,
+    #[serde(default)]
+    pub expires_at: Option<String>,
 }
 
 /// A single entry on the leaderboard.
@@ -6,6 +6,10 @@ use clap::{Parser, Subcommand, Args};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Local wallet to sign with, by name (see `frogpump wallet list`)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub wallet: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -43,8 +47,51 @@ pub enum Commands {
         verbose: bool,
     },
 
+    /// Show on-chain bonding curve details for a token
+    Token {
+        /// Token mint address (base58)
+        mint: String,
+    },
+
+    /// Audit a token's on-chain state for common rug-pull risks
+    Audit {
+        /// Token mint address (base58)
+        mint: String,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Analyze how a token's supply is spread across holders
+    Holders(HoldersArgs),
+
+    /// Quote a buy or sell against a token's bonding curve
+    Quote(QuoteArgs),
+
+    /// Buy a token on its bonding curve with the local wallet
+    Buy(BuyArgs),
+
+    /// Sell a token back to its bonding curve with the local wallet
+    Sell(SellArgs),
+
     /// Show platform-wide statistics
     Stats,
+
+    /// Inspect Solana transactions
+    Tx(TxCommand),
+
+    /// Derive program addresses for debugging
+    Derive(DeriveCommand),
+
+    /// Generate a keypair whose address has a chosen prefix or suffix
+    Grind(GrindArgs),
+
+    /// Sign an exported transaction with the local wallet, e.g. on an offline machine
+    Sign(SignArgs),
+
+    /// Send a transaction signed with `frogpump sign`
+    Submit(SubmitArgs),
 }
 
 #[derive(Args, Debug)]
@@ -72,6 +119,42 @@ pub struct LaunchArgs {
     /// Use self-funded launch mode instead of gasless
     #[arg(long)]
     pub self_funded: bool,
+
+    /// Priority fee for self-funded launches: "auto" or micro-lamports per compute unit
+    #[arg(long, requires = "self_funded")]
+    pub priority_fee: Option<String>,
+
+    /// Compute unit limit for self-funded launches
+    #[arg(long, requires = "self_funded")]
+    pub compute_units: Option<u32>,
+
+    /// SOL to spend buying the new token in the launch transaction (e.g. 0.5)
+    #[arg(long, value_name = "SOL")]
+    pub dev_buy: Option<String>,
+
+    /// Most the dev buy price may move against the quote, in basis points
+    #[arg(long, value_name = "BPS", default_value_t = 100, requires = "dev_buy")]
+    pub dev_buy_slippage: u64,
+
+    /// Grind a mint address ending in these characters, ignoring case
+    #[arg(long, value_name = "SUFFIX", requires = "self_funded")]
+    pub vanity_suffix: Option<String>,
+
+    /// Use this keypair file as the mint, e.g. one made by `frogpump grind`
+    #[arg(long, value_name = "PATH", requires = "self_funded", conflicts_with = "vanity_suffix")]
+    pub mint_keypair: Option<std::path::PathBuf>,
+
+    /// Simulate the launch transaction and print the result without sending it
+    #[arg(long)]
+    pub simulate: bool,
+
+    /// Write the unsigned launch transaction to a file for `frogpump sign`
+    #[arg(long, value_name = "FILE", requires = "self_funded", conflicts_with = "simulate")]
+    pub export_unsigned: Option<std::path::PathBuf>,
+
+    /// Durable nonce account to use instead of a recent blockhash
+    #[arg(long, value_name = "ADDRESS", requires = "export_unsigned")]
+    pub nonce: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -87,6 +170,75 @@ pub struct ClaimArgs {
     /// Claim all available earnings
     #[arg(long)]
     pub all: bool,
+
+    /// Simulate the claim transaction and print the result without sending it
+    #[arg(long)]
+    pub simulate: bool,
+
+    /// Write the unsigned claim transaction to a file for `frogpump sign`
+    #[arg(long, value_name = "FILE", conflicts_with = "simulate")]
+    pub export_unsigned: Option<std::path::PathBuf>,
+
+    /// Durable nonce account to use instead of a recent blockhash
+    #[arg(long, value_name = "ADDRESS", requires = "export_unsigned")]
+    pub nonce: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct HoldersArgs {
+    /// Token mint address (base58); omit to summarize every token of the agent
+    pub mint: Option<String>,
+
+    /// Agent whose tokens to summarize when no mint is given
+    #[arg(long, short, conflicts_with = "mint")]
+    pub agent_id: Option<String>,
+
+    /// Write the full holder list to a CSV file
+    #[arg(long, value_name = "PATH", requires = "mint")]
+    pub csv: Option<std::path::PathBuf>,
+}
+
+#[derive(Args, Debug)]
+#[command(group(clap::ArgGroup::new("side").required(true).args(["buy", "sell"])))]
+pub struct QuoteArgs {
+    /// Token mint address (base58)
+    pub mint: String,
+
+    /// SOL to spend, fee included (e.g. 0.5)
+    #[arg(long, value_name = "SOL")]
+    pub buy: Option<String>,
+
+    /// Tokens to sell (e.g. 1000000)
+    #[arg(long, value_name = "TOKENS")]
+    pub sell: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct BuyArgs {
+    /// Token mint address (base58)
+    pub mint: String,
+
+    /// SOL to spend, fee included (e.g. 0.5)
+    #[arg(long)]
+    pub sol: String,
+
+    /// Most the price may move against the quote, in basis points
+    #[arg(long, default_value_t = 100)]
+    pub max_slippage: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct SellArgs {
+    /// Token mint address (base58)
+    pub mint: String,
+
+    /// Tokens to sell, or a percentage of the balance (e.g. 50%)
+    #[arg(long)]
+    pub tokens: String,
+
+    /// Most the price may move against the quote, in basis points
+    #[arg(long, default_value_t = 100)]
+    pub max_slippage: u64,
 }
 
 #[derive(Args, Debug)]
@@ -98,14 +250,13 @@ pub struct WalletCommand {
 #[derive(Subcommand, Debug)]
 pub enum WalletSubcommand {
     /// Associate a wallet address with your agent
-    Set {
-        /// Solana wallet address (base58)
-        #[arg(long, short)]
-        address: String,
+    Set(WalletSetArgs),
 
-        /// Signature proving wallet ownership
+    /// Fetch a verification challenge for signing with an external wallet
+    Challenge {
+        /// Wallet address to verify (base58); defaults to the local wallet
         #[arg(long, short)]
-        signature: String,
+        address: Option<String>,
     },
 
     /// Show the wallet associated with an agent
@@ -114,6 +265,167 @@ pub enum WalletSubcommand {
         #[arg(long, short)]
         agent_id: Option<String>,
     },
+
+    /// Generate a new local wallet keypair
+    New {
+        /// Replace an existing local wallet
+        #[arg(long)]
+        force: bool,
+
+        /// Generate a BIP39 recovery phrase and derive the wallet from it
+        #[arg(long)]
+        mnemonic: bool,
+
+        /// Number of words in the recovery phrase: 12 or 24
+        #[arg(long, default_value_t = 12, requires = "mnemonic")]
+        words: usize,
+
+        /// Protect the recovery phrase with an extra BIP39 passphrase, asked for at a prompt
+        #[arg(long, requires = "mnemonic")]
+        passphrase: bool,
+
+        /// Encrypt the new wallet with a passphrase, asked for at a prompt
+        #[arg(long)]
+        encrypt: bool,
+    },
+
+    /// Restore the local wallet from a BIP39 recovery phrase
+    Recover(WalletRecoverArgs),
+
+    /// Derive wallet addresses from a BIP39 recovery phrase, one per agent
+    Derive(WalletDeriveArgs),
+
+    /// Import an existing keypair as the local wallet
+    Import(WalletImportArgs),
+
+    /// Print the local wallet's secret key
+    Export {
+        /// Output format: json (Solana CLI keypair file) or base58
+        #[arg(long, default_value = "json", value_parser = ["json", "base58"])]
+        format: String,
+
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Print the local wallet's public address
+    Address,
+
+    /// Encrypt the local wallet with a passphrase
+    Encrypt,
+
+    /// Store the local wallet as a plaintext keypair file again
+    Decrypt {
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// List the local wallets
+    List,
+
+    /// Make a local wallet the one commands sign with
+    Use {
+        /// Wallet name
+        name: String,
+
+        /// Link the wallet to this agent instead of making it the default for all agents
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+
+    /// Describe the local wallet with a label, or clear it when omitted
+    Label {
+        /// Free-form description shown by `wallet list`
+        label: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct WalletSetArgs {
+    /// Solana wallet address (base58); defaults to the local wallet
+    #[arg(long, short)]
+    pub address: Option<String>,
+
+    /// Base58 signature over the challenge from `wallet challenge`; when omitted the local
+    /// wallet fetches and signs a fresh challenge
+    #[arg(long, short, requires_all = ["address", "nonce", "issued_at"])]
+    pub signature: Option<String>,
+
+    /// Nonce of the signed challenge
+    #[arg(long, requires = "signature")]
+    pub nonce: Option<String>,
+
+    /// Issue time of the signed challenge, exactly as printed by `wallet challenge`
+    #[arg(long, requires = "signature")]
+    pub issued_at: Option<String>,
+}
+
+#[derive(Args, Debug)]
+#[command(group(clap::ArgGroup::new("source").required(true).args(["keypair_file", "base58"])))]
+pub struct WalletImportArgs {
+    /// Solana CLI keypair file (JSON array of 64 bytes)
+    #[arg(long, value_name = "PATH")]
+    pub keypair_file: Option<std::path::PathBuf>,
+
+    /// Base58-encoded 64-byte secret key, as exported by most wallets
+    #[arg(long, value_name = "SECRET")]
+    pub base58: Option<String>,
+
+    /// Replace an existing local wallet
+    #[arg(long)]
+    pub force: bool,
+
+    /// Encrypt the imported wallet with a passphrase, asked for at a prompt
+    #[arg(long)]
+    pub encrypt: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct WalletRecoverArgs {
+    /// Account index in the derivation path m/44'/501'/<INDEX>'/0'
+    #[arg(long, default_value_t = 0)]
+    pub index: u32,
+
+    /// The phrase is protected by an extra BIP39 passphrase, asked for at a prompt
+    #[arg(long)]
+    pub passphrase: bool,
+
+    /// Replace an existing local wallet
+    #[arg(long)]
+    pub force: bool,
+
+    /// Encrypt the new wallet with a passphrase, asked for at a prompt
+    #[arg(long)]
+    pub encrypt: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct WalletDeriveArgs {
+    /// First account index in the derivation path m/44'/501'/<INDEX>'/0'
+    #[arg(long, default_value_t = 0)]
+    pub index: u32,
+
+    /// Number of consecutive accounts to list
+    #[arg(long, default_value_t = 1, conflicts_with = "save")]
+    pub count: u32,
+
+    /// The phrase is protected by an extra BIP39 passphrase, asked for at a prompt
+    #[arg(long)]
+    pub passphrase: bool,
+
+    /// Save the derived keypair as the local wallet
+    #[arg(long)]
+    pub save: bool,
+
+    /// Replace an existing local wallet when saving
+    #[arg(long, requires = "save")]
+    pub force: bool,
+
+    /// Encrypt the saved wallet with a passphrase, asked for at a prompt
+    #[arg(long, requires = "save")]
+    pub encrypt: bool,
 }
 
 #[derive(Args, Debug)]
@@ -141,7 +453,7 @@ pub struct ConfigCommand {
 pub enum ConfigSubcommand {
     /// Set a configuration value
     Set {
-        /// Config key (api_base_url, agent_id, network, rpc_url, verbose)
+        /// Config key (api_base_url, agent_id, network, rpc_url, verbose, commitment, gasless_fee_payer, lock_memory)
         key: String,
         /// Value to set
         value: String,
@@ -157,4 +469,105 @@ pub enum ConfigSubcommand {
     Show,
 }
 
+#[derive(Args, Debug)]
+pub struct TxCommand {
+    #[command(subcommand)]
+    pub command: TxSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TxSubcommand {
+    /// Decode a wire-format transaction and verify its signatures
+    Decode {
+        /// Base64 or base58 encoded transaction, or "-" to read it from stdin
+        transaction: String,
+    },
+}
+
+#[derive(Args, Debug)]
+#[command(group(clap::ArgGroup::new("pattern").required(true).multiple(true).args(["prefix", "suffix"])))]
+pub struct GrindArgs {
+    /// Characters the address must start with
+    #[arg(long)]
+    pub prefix: Option<String>,
+
+    /// Characters the address must end with
+    #[arg(long)]
+    pub suffix: Option<String>,
+
+    /// Match letter case exactly instead of ignoring it
+    #[arg(long)]
+    pub case_sensitive: bool,
+
+    /// Worker threads (default: one per CPU core)
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// Keypair file to write (default: <ADDRESS>.json in the current directory)
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<std::path::PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct SignArgs {
+    /// File written by `--export-unsigned`
+    pub file: std::path::PathBuf,
+
+    /// Where to write the signed transaction (default: <FILE>.signed.json)
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<std::path::PathBuf>,
+
+    /// Skip the confirmation prompt
+    #[arg(long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// File written by `frogpump sign`
+    pub file: std::path::PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct DeriveCommand {
+    #[command(subcommand)]
+    pub command: DeriveSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DeriveSubcommand {
+    /// Find a program-derived address and its bump seed
+    Pda {
+        /// Program that owns the address (base58)
+        #[arg(long)]
+        program: String,
+
+        /// Seeds in order: string:<text>, pubkey:<base58>, hex:<bytes>, u8:<n> or u64:<n>
+        seeds: Vec<String>,
+    },
+
+    /// Derive the associated token account of an owner for a mint
+    Ata {
+        /// Wallet that owns the token account (base58)
+        #[arg(long)]
+        owner: String,
+
+        /// Token mint address (base58)
+        #[arg(long)]
+        mint: String,
+    },
+
+    /// Derive the Metaplex Token Metadata account of a mint
+    Metadata {
+        /// Token mint address (base58)
+        mint: String,
+    },
+
+    /// Derive the pump.fun bonding curve of a mint and its token account
+    BondingCurve {
+        /// Token mint address (base58)
+        mint: String,
+    },
+}
+
 // iteration 101
@@ -4,8 +4,19 @@ use tabled::{Table, Tabled};
 use crate::api::types::{LeaderboardEntry, StatsResponse};
 use crate::models::earning::Earning;
 use crate::models::token::Token;
+use crate::config::network::Commitment;
+use crate::core::audit::{AuditReport, CheckStatus};
+use crate::core::holders::{HolderDistribution, HISTOGRAM_BOUNDS_BPS};
+use crate::solana::accounts::{Metadata, Mint};
+use crate::solana::connection::{ConfirmedTransaction, SimulationResult};
+use crate::solana::pumpfun::{self, BondingCurve, BuyQuote, SellQuote, LAMPORTS_PER_SOL};
+use crate::solana::transaction::DecodedTransaction;
+use crate::solana::wallet::WalletSummary;
 use crate::utils::display;
 
+/// Width of the longest bar in the holder histogram, in characters.
+const HISTOGRAM_WIDTH: usize = 40;
+
 #[derive(Tabled)]
 struct TokenRow {
     #[tabled(rename = "Name")]
@@ -18,6 +29,20 @@ struct TokenRow {
     verified: String,
 }
 
+#[derive(Tabled)]
+struct HolderSummaryRow {
+    #[tabled(rename = "Token")]
+    token: String,
+    #[tabled(rename = "Mint Address")]
+    mint_address: String,
+    #[tabled(rename = "Holders")]
+    holders: usize,
+    #[tabled(rename = "Gini")]
+    gini: String,
+    #[tabled(rename = "Top 10")]
+    top_ten: String,
+}
+
 #[derive(Tabled)]
 struct EarningRow {
     #[tabled(rename = "Token")]
@@ -42,6 +67,22 @@ struct LeaderboardRow {
     earnings: String,
 }
 
+#[derive(Tabled)]
+struct WalletRow {
+    #[tabled(rename = "")]
+    selected: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Address")]
+    address: String,
+    #[tabled(rename = "Encrypted")]
+    encrypted: String,
+    #[tabled(rename = "Agents")]
+    agents: String,
+    #[tabled(rename = "Label")]
+    label: String,
+}
+
 pub struct OutputFormatter;
 
 impl OutputFormatter {
@@ -120,6 +161,287 @@ impl OutputFormatter {
         display::print_key_value("Volume (24h)", &display::format_sol(stats.volume_24h));
     }
 
+    pub fn print_simulation(result: &SimulationResult) {
+        display::print_header("Simulation");
+        match result.err {
+            None => display::print_key_value("Result", &"Success".green().to_string()),
+            Some(ref err) => display::print_key_value("Result", &err.to_string().red().to_string()),
+        }
+        if let Some(units) = result.units_consumed {
+            display::print_key_value("Compute units", &units.to_string());
+        }
+        if !result.logs.is_empty() {
+            println!("\n  {}", "Program logs:".cyan());
+            for line in &result.logs {
+                println!("    {}", line.dimmed());
+            }
+        }
+    }
+
+    pub fn print_transaction(tx: &DecodedTransaction) {
+        display::print_header("Transaction");
+        display::print_key_value(
+            "Version",
+            &tx.version
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "legacy".to_string()),
+        );
+        display::print_key_value("Recent blockhash", &tx.recent_blockhash);
+        display::print_key_value(
+            "Required signatures",
+            &format!(
+                "{} ({} read-only)",
+                tx.header.num_required_signatures, tx.header.num_readonly_signed_accounts
+            ),
+        );
+        display::print_key_value(
+            "Read-only unsigned",
+            &tx.header.num_readonly_unsigned_accounts.to_string(),
+        );
+
+        println!("\n  {}", "Accounts:".cyan());
+        for (index, account) in tx.accounts.iter().enumerate() {
+            let mut flags = Vec::new();
+            if account.is_signer {
+                flags.push("signer");
+            }
+            if account.is_writable {
+                flags.push("writable");
+            }
+            if account.from_lookup_table {
+                flags.push("lookup table");
+            }
+            println!(
+                "    #{:<3} {} {}",
+                index,
+                account.address,
+                flags.join(", ").dimmed()
+            );
+        }
+
+        println!("\n  {}", "Signatures:".cyan());
+        for signature in &tx.signatures {
+            let status = match signature.verified {
+                Some(true) => "valid".green(),
+                Some(false) => "INVALID".red().bold(),
+                None => "missing".yellow(),
+            };
+            println!("    {:<44} {}", signature.signer, status);
+        }
+
+        println!("\n  {}", "Instructions:".cyan());
+        for (index, ix) in tx.instructions.iter().enumerate() {
+            let program = ix.program.unwrap_or(&ix.program_id);
+            match ix.name {
+                Some(name) => println!("    #{} {}: {}", index, program, name.bold()),
+                None => println!("    #{} {}", index, program),
+            }
+            if ix.name.is_some() {
+                for (label, value) in &ix.fields {
+                    println!("         {:<24} {}", label.dimmed(), value);
+                }
+                continue;
+            }
+            for account in &ix.accounts {
+                println!("         {:<24} {}", "account".dimmed(), account);
+            }
+            let data: String = ix.data.iter().map(|b| format!("{:02x}", b)).collect();
+            println!("         {:<24} {}", "data".dimmed(), data);
+        }
+    }
+
+    pub fn print_bonding_curve(curve: &BondingCurve) {
+        display::print_key_value("Price", &format!("{:.10} SOL", curve.price_sol()));
+        display::print_key_value("Market cap", &display::format_sol(curve.market_cap_sol()));
+        display::print_key_value(
+            "SOL in curve",
+            &display::format_sol(curve.real_sol_reserves as f64 / LAMPORTS_PER_SOL as f64),
+        );
+        let status = if curve.complete {
+            "Graduated".green().to_string()
+        } else {
+            format!("{:.1}% to graduation", curve.progress_percent())
+        };
+        display::print_key_value("Bonding curve", &status);
+    }
+
+    pub fn print_mint(mint: &Mint) {
+        display::print_key_value(
+            "Supply",
+            &display::format_token_amount(mint.supply, mint.decimals),
+        );
+        display::print_key_value("Decimals", &mint.decimals.to_string());
+        display::print_key_value("Mint authority", &format_authority(mint.mint_authority));
+        display::print_key_value("Freeze authority", &format_authority(mint.freeze_authority));
+    }
+
+    /// Print on-chain metadata, flagging fields that disagree with the API's record.
+    pub fn print_token_metadata(token: &Token, metadata: &Metadata) {
+        display::print_key_value("On-chain name", &compare_field(&metadata.name, &token.name));
+        display::print_key_value(
+            "On-chain symbol",
+            &compare_field(&metadata.symbol, &token.symbol),
+        );
+        display::print_key_value("Metadata URI", &metadata.uri);
+        display::print_key_value(
+            "Update authority",
+            &bs58::encode(metadata.update_authority).into_string(),
+        );
+        display::print_key_value("Mutable", if metadata.is_mutable { "yes" } else { "no" });
+        for creator in &metadata.creators {
+            let verified = if creator.verified { "verified" } else { "unverified" };
+            display::print_key_value(
+                "Creator",
+                &format!(
+                    "{} ({}%, {})",
+                    bs58::encode(creator.address).into_string(),
+                    creator.share,
+                    verified
+                ),
+            );
+        }
+    }
+
+    pub fn print_audit_report(report: &AuditReport) {
+        display::print_header("Token Audit");
+        display::print_key_value("Mint", &report.mint);
+        println!();
+        for check in &report.checks {
+            println!(
+                "  {} {:<18} {}",
+                format_check_status(check.status),
+                check.name,
+                check.detail
+            );
+        }
+        println!();
+        display::print_key_value("Result", &format_check_status(report.status));
+    }
+
+    pub fn print_holder_distribution(distribution: &HolderDistribution, decimals: u8) {
+        display::print_key_value("Holders", &distribution.holders.len().to_string());
+        display::print_key_value(
+            "Circulating",
+            &display::format_token_amount(distribution.circulating, decimals),
+        );
+        display::print_key_value("Gini", &format!("{:.3}", distribution.gini()));
+        display::print_key_value("Top 10 share", &display::format_bps(distribution.top_ten_bps()));
+
+        let buckets = distribution.histogram();
+        let widest = buckets.iter().copied().max().unwrap_or(0).max(1);
+        println!("\n  {}", "Share of circulating supply".bold());
+        for (i, count) in buckets.iter().enumerate() {
+            let label = match i {
+                0 => format!("< {}", display::format_bps(HISTOGRAM_BOUNDS_BPS[0])),
+                i if i == HISTOGRAM_BOUNDS_BPS.len() => {
+                    format!(">= {}", display::format_bps(HISTOGRAM_BOUNDS_BPS[i - 1]))
+                }
+                i => format!(
+                    "{} - {}",
+                    display::format_bps(HISTOGRAM_BOUNDS_BPS[i - 1]),
+                    display::format_bps(HISTOGRAM_BOUNDS_BPS[i])
+                ),
+            };
+            let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(widest));
+            println!(
+                "  {:>16} | {} {}",
+                label,
+                format!("{:<width$}", bar, width = HISTOGRAM_WIDTH).green(),
+                count
+            );
+        }
+
+        if !distribution.holders.is_empty() {
+            println!("\n  {}", "Largest holders".bold());
+            for holder in distribution.holders.iter().take(10) {
+                println!(
+                    "  {:<44} {:>24} {:>7}",
+                    holder.owner,
+                    display::format_token_amount(holder.amount, decimals),
+                    display::format_bps(distribution.share_bps(holder.amount))
+                );
+            }
+        }
+    }
+
+    pub fn print_holder_summaries(summaries: &[(&Token, HolderDistribution)]) {
+        if summaries.is_empty() {
+            println!("{}", "No tokens found.".yellow());
+            return;
+        }
+        let rows: Vec<HolderSummaryRow> = summaries
+            .iter()
+            .map(|(token, distribution)| HolderSummaryRow {
+                token: format!("{} ({})", token.name, token.symbol),
+                mint_address: display::short_address(&token.mint_address),
+                holders: distribution.holders.len(),
+                gini: format!("{:.3}", distribution.gini()),
+                top_ten: display::format_bps(distribution.top_ten_bps()),
+            })
+            .collect();
+        println!("{}", Table::new(rows));
+    }
+
+    pub fn print_wallet_list(wallets: &[WalletSummary]) {
+        if wallets.is_empty() {
+            println!("{}", "No local wallets. Create one with: frogpump wallet new".yellow());
+            return;
+        }
+        let rows: Vec<WalletRow> = wallets
+            .iter()
+            .map(|w| WalletRow {
+                selected: if w.selected { "*".green().to_string() } else { String::new() },
+                name: w.name.clone(),
+                address: w.address.clone(),
+                encrypted: if w.encrypted { "Yes".to_string() } else { "No".to_string() },
+                agents: w.agents.join(", "),
+                label: w.label.clone().unwrap_or_default(),
+            })
+            .collect();
+        println!("{}", Table::new(rows));
+    }
+
+    pub fn print_buy_quote(quote: &BuyQuote) {
+        display::print_header("Buy Quote");
+        display::print_key_value("SOL in", &display::format_lamports(quote.sol_in));
+        display::print_key_value(
+            "Tokens out",
+            &display::format_token_amount(quote.tokens_out, pumpfun::TOKEN_DECIMALS),
+        );
+        display::print_key_value("Fee", &display::format_lamports(quote.fee));
+        display::print_key_value("Price impact", &display::format_bps(quote.price_impact_bps));
+    }
+
+    pub fn print_sell_quote(quote: &SellQuote) {
+        display::print_header("Sell Quote");
+        display::print_key_value(
+            "Tokens in",
+            &display::format_token_amount(quote.tokens_in, pumpfun::TOKEN_DECIMALS),
+        );
+        display::print_key_value("SOL out", &display::format_lamports(quote.sol_out));
+        display::print_key_value("Fee", &display::format_lamports(quote.fee));
+        display::print_key_value("Price impact", &display::format_bps(quote.price_impact_bps));
+    }
+
+    pub fn print_confirmation(confirmation: &ConfirmedTransaction) {
+        let status = match confirmation.err {
+            None => {
+                let level = match confirmation.commitment {
+                    Commitment::Processed => "Processed",
+                    Commitment::Confirmed => "Confirmed",
+                    Commitment::Finalized => "Finalized",
+                };
+                format!("{} in slot {}", level, confirmation.slot)
+                    .green()
+                    .to_string()
+            }
+            Some(ref err) => format!("Failed in slot {}: {}", confirmation.slot, err)
+                .red()
+                .to_string(),
+        };
+        display::print_key_value("Status", &status);
+    }
+
     pub fn print_success(message: &str) {
         println!("{} {}", "[OK]".green().bold(), message);
     }
@@ -129,4 +451,28 @@ impl OutputFormatter {
     }
 }
 
+fn format_check_status(status: CheckStatus) -> String {
+    match status {
+        CheckStatus::Pass => "[PASS]".green().bold().to_string(),
+        CheckStatus::Warn => "[WARN]".yellow().bold().to_string(),
+        CheckStatus::Fail => "[FAIL]".red().bold().to_string(),
+    }
+}
+
+fn format_authority(authority: Option<[u8; 32]>) -> String {
+    match authority {
+        Some(key) => bs58::encode(key).into_string(),
+        None => "none".green().to_string(),
+    }
+}
+
+/// Show an on-chain value, marking it when it differs from what the API reports.
+fn compare_field(on_chain: &str, api: &str) -> String {
+    if on_chain == api {
+        on_chain.to_string()
+    } else {
+        format!("{} {}", on_chain.yellow(), format!("(API: {})", api).yellow())
+    }
+}
+
 // iteration 63
@@ -1,3 +1,4 @@
+use crate::core::engine::PriorityFee;
 use crate::utils::crypto::is_valid_base58;
 use crate::utils::error::FrogError;
 
@@ -63,4 +64,182 @@ pub fn parse_period(period: &str) -> Result<String, FrogError> {
     }
 }
 
+/// Parse a priority fee: "auto" or a whole number of micro-lamports per compute unit.
+pub fn parse_priority_fee(value: &str) -> Result<PriorityFee, FrogError> {
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("auto") {
+        return Ok(PriorityFee::Auto);
+    }
+    trimmed
+        .parse::<u64>()
+        .map(PriorityFee::MicroLamports)
+        .map_err(|_| {
+            FrogError::Validation(format!(
+                "Invalid priority fee '{}'. Use 'auto' or micro-lamports per compute unit",
+                value
+            ))
+        })
+}
+
+/// Parse a decimal amount such as "0.5" into integer base units with the given decimals.
+pub fn parse_amount(value: &str, decimals: u8) -> Result<u64, FrogError> {
+    let invalid = || FrogError::Validation(format!("Invalid amount '{}'", value));
+    let trimmed = value.trim();
+    let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > decimals as usize {
+        return Err(FrogError::Validation(format!(
+            "Amount '{}' has more than {} decimal places",
+            value, decimals
+        )));
+    }
+
+    let scale = 10u64.pow(decimals as u32);
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid())?
+    };
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().map_err(|_| invalid())?
+            * 10u64.pow((decimals as usize - fraction.len()) as u32)
+    };
+    whole
+        .checked_mul(scale)
+        .and_then(|units| units.checked_add(fraction))
+        .ok_or_else(|| FrogError::Validation(format!("Amount '{}' is too large", value)))
+}
+
+/// A token amount given either exactly or as a share of the current balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenAmount {
+    /// Integer base units.
+    Units(u64),
+    /// Share of the balance in basis points.
+    Percent(u64),
+}
+
+impl TokenAmount {
+    /// Resolve against the current balance in base units.
+    pub fn resolve(self, balance: u64) -> u64 {
+        match self {
+            TokenAmount::Units(units) => units,
+            TokenAmount::Percent(bps) => (balance as u128 * bps as u128 / 10_000) as u64,
+        }
+    }
+}
+
+/// Parse a token amount such as "1500.5" or a percentage of the balance such as "50%".
+pub fn parse_token_amount(value: &str, decimals: u8) -> Result<TokenAmount, FrogError> {
+    let trimmed = value.trim();
+    let Some(percent) = trimmed.strip_suffix('%') else {
+        return parse_amount(trimmed, decimals).map(TokenAmount::Units);
+    };
+    // Two decimal places of a percentage are exactly basis points.
+    let bps = parse_amount(percent, 2)?;
+    if bps == 0 || bps > 10_000 {
+        return Err(FrogError::Validation(format!(
+            "Percentage '{}' must be above 0% and at most 100%",
+            value
+        )));
+    }
+    Ok(TokenAmount::Percent(bps))
+}
+
+/// Parse a PDA seed written as `string:<text>`, `pubkey:<base58>`, `hex:<bytes>`,
+/// `u8:<n>` or `u64:<n>` (little-endian). Text without a known prefix is taken as a string.
+pub fn parse_seed(value: &str) -> Result<Vec<u8>, FrogError> {
+    let invalid = |kind: &str| FrogError::Validation(format!("Invalid {} seed '{}'", kind, value));
+    let Some((kind, body)) = value.split_once(':') else {
+        return Ok(value.as_bytes().to_vec());
+    };
+    match kind {
+        "string" => Ok(body.as_bytes().to_vec()),
+        "pubkey" => {
+            let address = validate_address(body)?;
+            let bytes = bs58::decode(&address).into_vec().map_err(|_| invalid(kind))?;
+            if bytes.len() != 32 {
+                return Err(invalid(kind));
+            }
+            Ok(bytes)
+        }
+        "hex" => {
+            if body.len() % 2 != 0 {
+                return Err(invalid(kind));
+            }
+            (0..body.len())
+                .step_by(2)
+                .map(|i| {
+                    body.get(i..i + 2)
+                        .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                        .ok_or_else(|| invalid(kind))
+                })
+                .collect()
+        }
+        "u8" => body.parse::<u8>().map(|n| vec![n]).map_err(|_| invalid(kind)),
+        "u64" => body
+            .parse::<u64>()
+            .map(|n| n.to_le_bytes().to_vec())
+            .map_err(|_| invalid(kind)),
+        _ => Ok(value.as_bytes().to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("0.5", 9).unwrap(), 500_000_000);
+        assert_eq!(parse_amount("12", 6).unwrap(), 12_000_000);
+        assert_eq!(parse_amount(".000001", 6).unwrap(), 1);
+        assert_eq!(parse_amount("3.", 6).unwrap(), 3_000_000);
+        assert!(parse_amount("0.0000001", 6).is_err());
+        assert!(parse_amount("-1", 9).is_err());
+        assert!(parse_amount(".", 9).is_err());
+        assert!(parse_amount("1e9", 9).is_err());
+        assert!(parse_amount("99999999999", 9).is_err());
+    }
+
+    #[test]
+    fn test_parse_token_amount() {
+        assert_eq!(parse_token_amount("2.5", 6).unwrap(), TokenAmount::Units(2_500_000));
+        assert_eq!(parse_token_amount("50%", 6).unwrap(), TokenAmount::Percent(5_000));
+        assert_eq!(parse_token_amount("12.34%", 6).unwrap().resolve(1_000_000), 123_400);
+        assert_eq!(parse_token_amount("100%", 6).unwrap().resolve(987), 987);
+        assert!(parse_token_amount("0%", 6).is_err());
+        assert!(parse_token_amount("100.01%", 6).is_err());
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("metadata").unwrap(), b"metadata");
+        assert_eq!(parse_seed("string:u8:7").unwrap(), b"u8:7");
+        assert_eq!(parse_seed("https://x").unwrap(), b"https://x");
+        assert_eq!(
+            parse_seed("pubkey:11111111111111111111111111111111").unwrap(),
+            vec![0; 32]
+        );
+        assert_eq!(parse_seed("hex:00ff1A").unwrap(), vec![0x00, 0xff, 0x1a]);
+        assert_eq!(parse_seed("u8:7").unwrap(), vec![7]);
+        assert_eq!(parse_seed("u64:258").unwrap(), vec![2, 1, 0, 0, 0, 0, 0, 0]);
+        assert!(parse_seed("hex:abc").is_err());
+        assert!(parse_seed("hex:zz").is_err());
+        assert!(parse_seed("u8:256").is_err());
+        assert!(parse_seed("pubkey:not-base58").is_err());
+    }
+}
+
 // iteration 102
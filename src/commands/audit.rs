@@ -0,0 +1,26 @@
+use anyhow::{Context, Result};
+
+use crate::cli::output::OutputFormatter;
+use crate::cli::parser::validate_address;
+use crate::config::settings::Settings;
+use crate::core::audit;
+use crate::solana::connection::SolanaConnection;
+
+pub async fn execute(mint: String, json: bool, config: &Settings) -> Result<()> {
+    let mint = validate_address(&mint).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let connection = SolanaConnection::new(&config.rpc_url);
+    let state = audit::fetch_token_state(&connection, &mint).await?;
+    let report = audit::audit(&mint, &state);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to serialize audit report")?
+        );
+    } else {
+        OutputFormatter::print_audit_report(&report);
+    }
+
+    Ok(())
+}
@@ -5,8 +5,12 @@ use crate::api::client::ApiClient;
 use crate::cli::app::ClaimArgs;
 use crate::cli::output::OutputFormatter;
 use crate::cli::parser::validate_agent_id;
+use crate::commands::offline::print_exported;
 use crate::config::settings::Settings;
 use crate::core::fee_collector::FeeCollector;
+use crate::core::offline::{self, OfflineKind, OfflineTransaction};
+use crate::solana::connection::{SolanaConnection, DEFAULT_CONFIRMATION_TIMEOUT};
+use crate::solana::transaction::VersionedTransaction;
 use crate::utils::display;
 
 pub async fn execute(args: ClaimArgs, config: &Settings) -> Result<()> {
@@ -18,7 +22,6 @@ pub async fn execute(args: ClaimArgs, config: &Settings) -> Result<()> {
         None => anyhow::bail!("Agent ID required. Pass --agent-id or set it in config."),
     };
 
-    let api = ApiClient::new(&config.api_base_url);
     let collector = FeeCollector::new(ApiClient::new(&config.api_base_url));
 
     // Fetch current unclaimed earnings
@@ -39,24 +42,62 @@ pub async fn execute(args: ClaimArgs, config: &Settings) -> Result<()> {
         anyhow::bail!("Specify --token <mint> or --all to claim earnings.");
     }
 
-    if let Some(ref token) = args.token {
+    let claim = if let Some(ref token) = args.token {
         let matching: Vec<_> = unclaimed.iter().filter(|e| e.token_id == *token).collect();
         if matching.is_empty() {
             anyhow::bail!("No unclaimed earnings found for token {}.", token);
         }
         let token_total: f64 = matching.iter().map(|e| e.amount).sum();
-        println!(
-            "  About to claim {} for token {}",
+        format!(
+            "{} for token {}",
             display::format_sol(token_total),
             display::short_address(token)
-        );
+        )
     } else {
-        println!(
-            "  About to claim {} across {} earning(s) for agent {}",
+        format!(
+            "{} across {} earning(s) for agent {}",
             display::format_sol(total),
             unclaimed.len(),
             agent_id
-        );
+        )
+    };
+    println!("  About to claim {}", claim);
+
+    if args.simulate {
+        let prepared = collector
+            .prepare_claim(&agent_id, args.token.as_deref())
+            .await?;
+        let tx = VersionedTransaction::from_base64(&prepared.transaction)
+            .context("Backend returned an invalid claim transaction")?;
+        let result = SolanaConnection::new(&config.rpc_url)
+            .simulate_transaction(&tx)
+            .await
+            .context("Claim simulation failed")?;
+
+        OutputFormatter::print_simulation(&result);
+        if !result.is_success() {
+            anyhow::bail!("Claim would fail; nothing was sent");
+        }
+        return Ok(());
+    }
+
+    if let Some(ref path) = args.export_unsigned {
+        let prepared = collector
+            .prepare_claim(&agent_id, args.token.as_deref())
+            .await?;
+        let mut tx = VersionedTransaction::from_base64(&prepared.transaction)
+            .context("Backend returned an invalid claim transaction")?;
+        if let Some(ref nonce) = args.nonce {
+            tx = offline::bind_durable_nonce(&SolanaConnection::new(&config.rpc_url), &tx, nonce)
+                .await
+                .context("Failed to bind the claim to the durable nonce")?;
+        }
+
+        let summary = format!("Claim {}", claim);
+        let exported =
+            OfflineTransaction::new(OfflineKind::Claim, summary, config.network, agent_id, &tx);
+        exported.save(path)?;
+        return print_exported(path, &exported);
     }
 
     print!("  Proceed? [y/N] ");
@@ -74,12 +115,30 @@ pub async fn execute(args: ClaimArgs, config: &Settings) -> Result<()> {
         collector.claim_all(&agent_id).await?
     };
 
+    let confirmation = SolanaConnection::new(&config.rpc_url)
+        .wait_for_confirmation(
+            &response.tx_signature,
+            config.commitment,
+            DEFAULT_CONFIRMATION_TIMEOUT,
+        )
+        .await
+        .context("Failed to confirm claim transaction")?;
+    if let Some(ref err) = confirmation.err {
+        anyhow::bail!(
+            "Claim transaction {} failed in slot {}: {}",
+            response.tx_signature,
+            confirmation.slot,
+            err
+        );
+    }
+
     OutputFormatter::print_success(&format!(
         "Claimed {} for agent {}",
         display::format_sol(response.amount),
         agent_id,
     ));
     display::print_key_value("Transaction", &display::short_address(&response.tx_signature));
+    OutputFormatter::print_confirmation(&confirmation);
 
     Ok(())
 }
@@ -27,7 +27,7 @@ pub fn execute_get(key: &str) -> Result<()> {
         }
         None => {
             anyhow::bail!(
-                "Unknown config key '{}'. Valid keys: api_base_url, agent_id, wallet_address, network, rpc_url, verbose",
+                "Unknown config key '{}'. Valid keys: api_base_url, agent_id, wallet_address, network, rpc_url, verbose, commitment, gasless_fee_payer, lock_memory",
                 key
             );
         }
@@ -55,6 +55,15 @@ pub fn execute_show() -> Result<()> {
     display::print_key_value("network", &format!("{:?}", settings.network));
     display::print_key_value("rpc_url", &settings.rpc_url);
     display::print_key_value("verbose", &settings.verbose.to_string());
+    display::print_key_value("commitment", settings.commitment.as_str());
+    display::print_key_value(
+        "gasless_fee_payer",
+        settings
+            .gasless_fee_payer
+            .as_deref()
+            .unwrap_or("(not set)"),
+    );
+    display::print_key_value("lock_memory", &settings.lock_memory.to_string());
 
     Ok(())
 }
@@ -0,0 +1,59 @@
+use anyhow::Result;
+
+use crate::cli::parser::{parse_seed, validate_address};
+use crate::solana::keypair;
+use crate::solana::pda;
+use crate::solana::pumpfun;
+use crate::utils::display;
+
+fn decode_address(address: &str) -> Result<[u8; 32]> {
+    let address = validate_address(address).map_err(|e| anyhow::anyhow!("{}", e))?;
+    keypair::decode_pubkey(&address)
+}
+
+pub fn execute_pda(program: &str, seeds: &[String]) -> Result<()> {
+    let program_id = decode_address(program)?;
+    let seeds = seeds
+        .iter()
+        .map(|seed| parse_seed(seed).map_err(|e| anyhow::anyhow!("{}", e)))
+        .collect::<Result<Vec<_>>>()?;
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let (address, bump) = pda::find_program_address(&seed_refs, &program_id)?;
+
+    display::print_header("Program Derived Address");
+    display::print_key_value("Program", program);
+    display::print_key_value("Address", &keypair::pubkey_from_bytes(&address)?);
+    display::print_key_value("Bump", &bump.to_string());
+    Ok(())
+}
+
+pub fn execute_ata(owner: &str, mint: &str) -> Result<()> {
+    let address = pda::associated_token_address(&decode_address(owner)?, &decode_address(mint)?);
+
+    display::print_header("Associated Token Account");
+    display::print_key_value("Owner", owner);
+    display::print_key_value("Mint", mint);
+    display::print_key_value("Address", &keypair::pubkey_from_bytes(&address)?);
+    Ok(())
+}
+
+pub fn execute_metadata(mint: &str) -> Result<()> {
+    let address = pda::metadata_address(&decode_address(mint)?);
+
+    display::print_header("Token Metadata Account");
+    display::print_key_value("Mint", mint);
+    display::print_key_value("Address", &keypair::pubkey_from_bytes(&address)?);
+    Ok(())
+}
+
+pub fn execute_bonding_curve(mint: &str) -> Result<()> {
+    let mint_key = decode_address(mint)?;
+    let curve = pumpfun::bonding_curve_address(&mint_key);
+    let curve_tokens = pda::associated_token_address(&curve, &mint_key);
+
+    display::print_header("Bonding Curve");
+    display::print_key_value("Mint", mint);
+    display::print_key_value("Curve account", &keypair::pubkey_from_bytes(&curve)?);
+    display::print_key_value("Curve tokens", &keypair::pubkey_from_bytes(&curve_tokens)?);
+    Ok(())
+}
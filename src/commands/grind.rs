@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::cli::app::GrindArgs;
+use crate::cli::output::OutputFormatter;
+use crate::core::grind::{self, VanityPattern};
+use crate::solana::keypair::{self, SecretKeypair};
+use crate::utils::display;
+
+pub async fn execute(args: GrindArgs) -> Result<()> {
+    let pattern = VanityPattern::new(
+        args.prefix.as_deref(),
+        args.suffix.as_deref(),
+        args.case_sensitive,
+    )?;
+    if let Some(ref output) = args.output {
+        if output.exists() {
+            anyhow::bail!("{} already exists", output.display());
+        }
+    }
+
+    let kp = grind_with_progress(pattern, args.threads).await?;
+    let address = kp.address();
+    let path = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.json", address)));
+    keypair::write_keypair_file(&path, &kp)?;
+
+    OutputFormatter::print_success(&format!("Found {}", address));
+    display::print_key_value("Saved to", &path.display().to_string());
+    println!(
+        "\n  Launch with it as the mint: frogpump launch --self-funded --mint-keypair {} ...",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Grind a keypair matching `pattern` on every core, showing progress until
+/// one is found. Ctrl-C gives up.
+pub async fn grind_with_progress(
+    pattern: VanityPattern,
+    threads: Option<usize>,
+) -> Result<SecretKeypair> {
+    let threads = threads
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1);
+    let expected = pattern.expected_attempts();
+    let attempts = Arc::new(AtomicU64::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let mut worker = tokio::task::spawn_blocking({
+        let attempts = attempts.clone();
+        let stop = stop.clone();
+        move || grind::grind(&pattern, threads, &attempts, &stop)
+    });
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .expect("valid template"),
+    );
+    let started = Instant::now();
+    let mut ticker = tokio::time::interval(Duration::from_millis(250));
+    let mut interrupted = false;
+    let found = loop {
+        tokio::select! {
+            result = &mut worker => break result.context("Grinding workers failed")?,
+            _ = ticker.tick() => {
+                let tried = attempts.load(Ordering::Relaxed);
+                let rate = tried as f64 / started.elapsed().as_secs_f64().max(0.001);
+                spinner.set_message(format!(
+                    "Grinding on {} threads: {} keys tried ({:.0}/s), ~{:.0} expected",
+                    threads, tried, rate, expected
+                ));
+                spinner.tick();
+            }
+            _ = tokio::signal::ctrl_c(), if !interrupted => {
+                interrupted = true;
+                stop.store(true, Ordering::Relaxed);
+            }
+        }
+    };
+    spinner.finish_and_clear();
+
+    found.context(format!(
+        "Interrupted after {} keys",
+        attempts.load(Ordering::Relaxed)
+    ))
+}
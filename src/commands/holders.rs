@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+
+use crate::api::client::ApiClient;
+use crate::cli::app::HoldersArgs;
+use crate::cli::output::OutputFormatter;
+use crate::cli::parser::{validate_address, validate_agent_id};
+use crate::config::settings::Settings;
+use crate::core::holders;
+use crate::solana::connection::SolanaConnection;
+use crate::solana::pumpfun;
+use crate::utils::display;
+
+pub async fn execute(args: HoldersArgs, config: &Settings) -> Result<()> {
+    let connection = SolanaConnection::new(&config.rpc_url);
+
+    let Some(mint) = args.mint else {
+        return summarize_agent(args.agent_id, &connection, config).await;
+    };
+    let mint = validate_address(&mint).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let distribution = holders::fetch_distribution(&connection, &mint).await?;
+    let decimals = connection
+        .get_mint(&mint)
+        .await
+        .context("Failed to fetch mint account")?
+        .map_or(pumpfun::TOKEN_DECIMALS, |mint| mint.decimals);
+
+    display::print_header("Holder Distribution");
+    display::print_key_value("Mint", &mint);
+    OutputFormatter::print_holder_distribution(&distribution, decimals);
+
+    if let Some(path) = args.csv {
+        std::fs::write(&path, distribution.to_csv())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!(
+            "\n  Wrote {} holder(s) to {}",
+            distribution.holders.len(),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show the headline distribution figures for every token the agent launched.
+async fn summarize_agent(
+    agent_id: Option<String>,
+    connection: &SolanaConnection,
+    config: &Settings,
+) -> Result<()> {
+    let agent_id = match agent_id.or_else(|| config.agent_id.clone()) {
+        Some(id) => {
+            validate_agent_id(&id).map_err(|e| anyhow::anyhow!("{}", e))?;
+            id
+        }
+        None => anyhow::bail!("Pass a mint address, or an agent ID with --agent-id or in config."),
+    };
+
+    let tokens = ApiClient::new(&config.api_base_url)
+        .get_tokens(&agent_id)
+        .await
+        .context("Failed to fetch tokens")?;
+
+    let mut summaries = Vec::with_capacity(tokens.len());
+    for token in &tokens {
+        let distribution = holders::fetch_distribution(connection, &token.mint_address)
+            .await
+            .with_context(|| format!("Failed to analyze holders of {}", token.symbol))?;
+        summaries.push((token, distribution));
+    }
+
+    display::print_header(&format!("Holder distribution for agent: {}", agent_id));
+    OutputFormatter::print_holder_summaries(&summaries);
+
+    Ok(())
+}
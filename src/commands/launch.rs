@@ -1,14 +1,20 @@
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use log::info;
+use std::path::PathBuf;
 use std::time::Duration;
+use zeroize::Zeroizing;
 
 use crate::api::client::ApiClient;
 use crate::cli::app::LaunchArgs;
 use crate::cli::output::OutputFormatter;
-use crate::cli::parser::validate_agent_id;
+use crate::cli::parser::{parse_amount, parse_priority_fee, validate_agent_id};
+use crate::commands::grind;
+use crate::commands::offline::print_exported;
 use crate::config::settings::Settings;
 use crate::core::engine::LaunchEngine;
+use crate::core::grind::VanityPattern;
+use crate::solana::keypair::{self, SecretKeypair};
 use crate::utils::display;
 
 pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
@@ -20,6 +26,39 @@ pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
         None => anyhow::bail!("Agent ID required. Pass --agent-id or set it in config."),
     };
 
+    let priority_fee = args
+        .priority_fee
+        .as_deref()
+        .map(parse_priority_fee)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let dev_buy = args
+        .dev_buy
+        .as_deref()
+        .map(|sol| parse_amount(sol, 9))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let mint_keypair = match (&args.vanity_suffix, &args.mint_keypair) {
+        (Some(suffix), _) => {
+            let pattern = VanityPattern::new(None, Some(suffix), false)?;
+            let mint = grind::grind_with_progress(pattern, None).await?;
+            // Save the mint before launching so it is not lost if the launch fails.
+            let path = PathBuf::from(format!("{}.json", mint.address()));
+            keypair::write_keypair_file(&path, &mint)?;
+            display::print_key_value("Vanity mint", &mint.address());
+            display::print_key_value("Saved to", &path.display().to_string());
+            Some(mint)
+        }
+        (None, Some(path)) => {
+            let data = std::fs::read_to_string(path)
+                .map(Zeroizing::new)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Some(SecretKeypair::from_json(&data)?)
+        }
+        (None, None) => None,
+    };
+
     info!(
         "Launching token {} ({}) for agent {}",
         args.name, args.symbol, agent_id
@@ -35,9 +74,58 @@ pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
     spinner.enable_steady_tick(Duration::from_millis(100));
 
     let api = ApiClient::new(&config.api_base_url);
-    let engine = LaunchEngine::new(api, config.clone());
+    let engine = LaunchEngine::new(api, config.clone())
+        .with_priority_fee(priority_fee)
+        .with_compute_unit_limit(args.compute_units)
+        .with_dev_buy(dev_buy, args.dev_buy_slippage)
+        .with_mint_keypair(mint_keypair);
+
+    if args.simulate {
+        spinner.set_message(format!(
+            "Simulating launch of {} ({})...",
+            args.name, args.symbol
+        ));
+        let result = engine
+            .simulate_launch(
+                args.name.clone(),
+                args.symbol.clone(),
+                args.description,
+                args.image,
+                Some(agent_id),
+                args.self_funded,
+            )
+            .await?;
+        spinner.finish_and_clear();
 
-    let response = engine
+        OutputFormatter::print_simulation(&result);
+        if !result.is_success() {
+            anyhow::bail!("Launch would fail; nothing was sent");
+        }
+        return Ok(());
+    }
+
+    if let Some(ref path) = args.export_unsigned {
+        spinner.set_message(format!(
+            "Preparing launch of {} ({}) for offline signing...",
+            args.name, args.symbol
+        ));
+        let exported = engine
+            .export_launch(
+                args.name.clone(),
+                args.symbol.clone(),
+                args.description,
+                args.image,
+                Some(agent_id),
+                args.nonce.as_deref(),
+            )
+            .await?;
+        spinner.finish_and_clear();
+
+        exported.save(path)?;
+        return print_exported(path, &exported);
+    }
+
+    let outcome = engine
         .execute_launch(
             args.name.clone(),
             args.symbol.clone(),
@@ -48,6 +136,7 @@ pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
         )
         .await
         .context("Token launch failed")?;
+    let response = &outcome.response;
 
     spinner.finish_and_clear();
 
@@ -56,7 +145,11 @@ pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
         args.name, args.symbol
     ));
     display::print_key_value("Mint address", &response.mint_address);
-    display::print_key_value("Transaction", &display::short_address(&response.tx_signature));
+    display::print_key_value(
+        "Transaction",
+        &display::short_address(&response.tx_signature),
+    );
+    OutputFormatter::print_confirmation(&outcome.confirmation);
     display::print_key_value("Agent", &agent_id);
     display::print_key_value(
         "Launch type",
@@ -66,6 +159,9 @@ pub async fn execute(args: LaunchArgs, config: &Settings) -> Result<()> {
             "Gasless"
         },
     );
+    if let Some(lamports) = dev_buy {
+        display::print_key_value("Dev buy", &display::format_lamports(lamports));
+    }
     display::print_key_value("View on pump.fun", &response.pump_fun_url);
 
     Ok(())
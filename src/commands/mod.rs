@@ -5,7 +5,16 @@ pub mod wallet;
 pub mod leaderboard;
 pub mod config_cmd;
 pub mod tokens;
+pub mod token;
+pub mod audit;
+pub mod holders;
+pub mod quote;
+pub mod trade;
 pub mod stats;
+pub mod tx;
+pub mod derive;
+pub mod grind;
+pub mod offline;
 
 pub use launch::execute as launch_token;
 pub use status::execute as check_status;
@@ -17,4 +26,15 @@ pub use config_cmd::execute_set as config_set;
 pub use config_cmd::execute_get as config_get;
 pub use config_cmd::execute_show as config_show;
 pub use tokens::execute as list_tokens;
+pub use token::execute as show_token;
+pub use audit::execute as audit_token;
+pub use holders::execute as show_holders;
+pub use quote::execute as quote_trade;
+pub use trade::execute_buy as buy_token;
+pub use trade::execute_sell as sell_token;
 pub use stats::execute as show_stats;
+pub use tx::execute_decode as decode_transaction;
+pub use derive::execute_pda as derive_pda;
+pub use grind::execute as grind_keypair;
+pub use offline::execute_sign as sign_transaction;
+pub use offline::execute_submit as submit_transaction;
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::cli::app::{SignArgs, SubmitArgs};
+use crate::cli::output::OutputFormatter;
+use crate::config::settings::Settings;
+use crate::core::offline::{self, OfflineKind, OfflineTransaction};
+use crate::solana::connection::SolanaConnection;
+use crate::solana::wallet::WalletManager;
+use crate::utils::display;
+
+/// Report an export written by `--export-unsigned` and what to do with it.
+pub fn print_exported(path: &Path, exported: &OfflineTransaction) -> Result<()> {
+    let tx = exported.transaction()?;
+    OutputFormatter::print_success(&format!("Exported: {}", exported.summary));
+    display::print_key_value("Saved to", &path.display().to_string());
+    display::print_key_value("Needs signature", &offline::missing_signers(&tx).join(", "));
+    match exported.nonce_account {
+        Some(ref nonce) => display::print_key_value("Nonce account", nonce),
+        None => println!(
+            "\n  {}",
+            "Without --nonce the transaction expires about a minute after export.".yellow()
+        ),
+    }
+    println!(
+        "\n  Sign it on the wallet's machine: frogpump sign {}",
+        path.display()
+    );
+    Ok(())
+}
+
+pub fn execute_sign(args: SignArgs, config: &Settings) -> Result<()> {
+    let mut exported = OfflineTransaction::load(&args.file)?;
+    let mut tx = exported.transaction()?;
+
+    print_context(&exported);
+    println!();
+    OutputFormatter::print_transaction(&tx.decode());
+    println!();
+
+    // Check the wallet is a signer before asking for a passphrase.
+    let wallet = WalletManager::select(config, Some(&exported.agent_id))?;
+    let address = wallet.get_public_key()?;
+    let is_signer = tx
+        .message
+        .signer_keys()
+        .iter()
+        .any(|key| bs58::encode(key).into_string() == address);
+    if !is_signer {
+        anyhow::bail!(
+            "Wallet '{}' ({}) is not a signer of this transaction",
+            wallet.name(),
+            address
+        );
+    }
+    if !args.yes
+        && !display::confirm(&format!(
+            "Sign with wallet '{}' ({})?",
+            wallet.name(),
+            address
+        ))
+        .context("Failed to read confirmation")?
+    {
+        anyhow::bail!("Signing cancelled");
+    }
+
+    tx.sign(&wallet.load_keypair()?)?;
+    exported.set_transaction(&tx);
+    let output = args.output.unwrap_or_else(|| signed_path(&args.file));
+    exported.save(&output)?;
+
+    OutputFormatter::print_success(&format!("Signed with wallet '{}'", wallet.name()));
+    display::print_key_value("Saved to", &output.display().to_string());
+    let missing = offline::missing_signers(&tx);
+    if missing.is_empty() {
+        println!(
+            "\n  Send it from an online machine: frogpump submit {}",
+            output.display()
+        );
+    } else {
+        display::print_key_value("Still unsigned", &missing.join(", "));
+    }
+    Ok(())
+}
+
+pub async fn execute_submit(args: SubmitArgs, config: &Settings) -> Result<()> {
+    let exported = OfflineTransaction::load(&args.file)?;
+    if exported.network != config.network {
+        anyhow::bail!(
+            "{} was exported for {:?} but the CLI is configured for {:?}",
+            args.file.display(),
+            exported.network,
+            config.network
+        );
+    }
+    let tx = exported.transaction()?;
+    let missing = offline::missing_signers(&tx);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Transaction is missing signatures from {}; run `frogpump sign` first",
+            missing.join(", ")
+        );
+    }
+    if tx.verify_signatures().contains(&Some(false)) {
+        anyhow::bail!("Transaction carries invalid signatures");
+    }
+
+    let confirmation = SolanaConnection::new(&config.rpc_url)
+        .send_and_confirm(&tx, config.commitment)
+        .await
+        .context("Failed to submit transaction")?;
+    if let Some(ref err) = confirmation.err {
+        anyhow::bail!(
+            "Transaction {} failed in slot {}: {}",
+            confirmation.signature,
+            confirmation.slot,
+            err
+        );
+    }
+
+    OutputFormatter::print_success(&format!("Submitted: {}", exported.summary));
+    display::print_key_value(
+        "Transaction",
+        &display::short_address(&confirmation.signature),
+    );
+    OutputFormatter::print_confirmation(&confirmation);
+    if let Some(ref mint) = exported.mint_address {
+        display::print_key_value("Mint address", mint);
+        if exported.kind == OfflineKind::Launch {
+            display::print_key_value(
+                "View on pump.fun",
+                &format!("https://pump.fun/coin/{}", mint),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_context(exported: &OfflineTransaction) {
+    display::print_header("Offline Transaction");
+    display::print_key_value("Action", &exported.summary);
+    display::print_key_value("Agent", &exported.agent_id);
+    display::print_key_value("Network", &format!("{:?}", exported.network));
+    if let Some(ref mint) = exported.mint_address {
+        display::print_key_value("Mint address", mint);
+    }
+    match exported.nonce_account {
+        Some(ref nonce) => display::print_key_value("Nonce account", nonce),
+        None => display::print_key_value("Nonce account", "none (blockhash expires quickly)"),
+    }
+    display::print_key_value("Exported", &exported.created_at.to_rfc3339());
+}
+
+/// `out.json` becomes `out.signed.json`, next to the input.
+fn signed_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "transaction".to_string());
+    path.with_file_name(format!("{}.signed.json", stem))
+}
@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+
+use crate::cli::app::QuoteArgs;
+use crate::cli::output::OutputFormatter;
+use crate::cli::parser::{parse_amount, validate_address};
+use crate::config::settings::Settings;
+use crate::solana::connection::SolanaConnection;
+use crate::solana::pumpfun;
+
+pub async fn execute(args: QuoteArgs, config: &Settings) -> Result<()> {
+    let mint = validate_address(&args.mint).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let connection = SolanaConnection::new(&config.rpc_url);
+    let curve = connection
+        .get_bonding_curve(&mint)
+        .await
+        .context("Failed to fetch bonding curve")?
+        .with_context(|| format!("{} has no pump.fun bonding curve", mint))?;
+    let global = connection
+        .get_pump_fun_global()
+        .await
+        .context("Failed to fetch pump.fun fee configuration")?;
+
+    if let Some(ref sol) = args.buy {
+        let lamports = parse_amount(sol, 9).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let quote = curve.quote_buy(lamports, global.fee_basis_points)?;
+        OutputFormatter::print_buy_quote(&quote);
+    } else if let Some(ref tokens) = args.sell {
+        let units =
+            parse_amount(tokens, pumpfun::TOKEN_DECIMALS).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let quote = curve.quote_sell(units, global.fee_basis_points)?;
+        OutputFormatter::print_sell_quote(&quote);
+    }
+    println!();
+
+    Ok(())
+}
@@ -4,7 +4,6 @@ use crate::api::client::ApiClient;
 use crate::cli::output::OutputFormatter;
 use crate::cli::parser::validate_agent_id;
 use crate::config::settings::Settings;
-use crate::core::fee_collector::FeeCollector;
 use crate::utils::display;
 
 pub async fn execute(agent_id: Option<String>, config: &Settings) -> Result<()> {
@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+
+use crate::cli::output::OutputFormatter;
+use crate::cli::parser::validate_address;
+use crate::config::settings::Settings;
+use crate::solana::connection::SolanaConnection;
+use crate::solana::keypair;
+use crate::solana::pumpfun;
+use crate::utils::display;
+
+pub async fn execute(mint: String, config: &Settings) -> Result<()> {
+    let mint = validate_address(&mint).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let curve_address = keypair::pubkey_from_bytes(&pumpfun::bonding_curve_for_mint(&mint)?)?;
+
+    let curve = SolanaConnection::new(&config.rpc_url)
+        .get_bonding_curve(&mint)
+        .await
+        .context("Failed to fetch bonding curve")?
+        .with_context(|| format!("{} has no pump.fun bonding curve", mint))?;
+
+    display::print_header("Token");
+    display::print_key_value("Mint", &mint);
+    display::print_key_value("Curve account", &curve_address);
+    OutputFormatter::print_bonding_curve(&curve);
+    display::print_key_value("Pump.fun", &format!("https://pump.fun/coin/{}", mint));
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use crate::api::client::ApiClient;
 use crate::cli::output::OutputFormatter;
 use crate::cli::parser::validate_agent_id;
 use crate::config::settings::Settings;
+use crate::solana::connection::SolanaConnection;
 use crate::utils::display;
 
 pub async fn execute(agent_id: Option<String>, verbose: bool, config: &Settings) -> Result<()> {
@@ -26,6 +27,7 @@ pub async fn execute(agent_id: Option<String>, verbose: bool, config: &Settings)
     OutputFormatter::print_token_table(&tokens);
 
     if verbose {
+        let connection = SolanaConnection::new(&config.rpc_url);
         for token in &tokens {
             println!();
             display::print_divider();
@@ -39,6 +41,21 @@ pub async fn execute(agent_id: Option<String>, verbose: bool, config: &Settings)
                 display::print_key_value("Image", img);
             }
             display::print_key_value("Created", &display::format_timestamp(&token.created_at));
+            match connection.get_bonding_curve(&token.mint_address).await {
+                Ok(Some(curve)) => OutputFormatter::print_bonding_curve(&curve),
+                Ok(None) => display::print_key_value("Bonding curve", "not found"),
+                Err(e) => display::print_key_value("Bonding curve", &format!("unavailable ({})", e)),
+            }
+            match connection.get_mint(&token.mint_address).await {
+                Ok(Some(mint)) => OutputFormatter::print_mint(&mint),
+                Ok(None) => display::print_key_value("Mint account", "not found"),
+                Err(e) => display::print_key_value("Mint account", &format!("unavailable ({})", e)),
+            }
+            match connection.get_token_metadata(&token.mint_address).await {
+                Ok(Some(metadata)) => OutputFormatter::print_token_metadata(token, &metadata),
+                Ok(None) => display::print_key_value("Metadata", "not found"),
+                Err(e) => display::print_key_value("Metadata", &format!("unavailable ({})", e)),
+            }
         }
     }
 
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::cli::app::{BuyArgs, SellArgs};
+use crate::cli::output::OutputFormatter;
+use crate::cli::parser::{parse_amount, parse_token_amount, validate_address};
+use crate::config::settings::Settings;
+use crate::core::validator;
+use crate::solana::connection::{ConfirmedTransaction, SolanaConnection};
+use crate::solana::keypair::{self, SecretKeypair};
+use crate::solana::pda;
+use crate::solana::pumpfun::{self, BondingCurve, Global, TradeAccounts};
+use crate::solana::transaction::{
+    create_associated_token_account_idempotent, Instruction, TransactionBuilder,
+};
+use crate::solana::wallet::WalletManager;
+use crate::utils::display;
+
+pub async fn execute_buy(args: BuyArgs, config: &Settings) -> Result<()> {
+    validator::validate_slippage_bps(args.max_slippage)?;
+    let lamports = parse_amount(&args.sol, 9).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let market = Market::load(&args.mint, config).await?;
+
+    let quote = market
+        .curve
+        .quote_buy(lamports, market.global.fee_basis_points)?;
+    let max_sol_cost = quote.max_sol_cost(args.max_slippage);
+    OutputFormatter::print_buy_quote(&quote);
+    display::print_key_value("Max SOL cost", &display::format_lamports(max_sol_cost));
+
+    let instructions = vec![
+        create_associated_token_account_idempotent(
+            &market.accounts.user,
+            &market.accounts.user,
+            &market.accounts.mint,
+        ),
+        pumpfun::buy_instruction(&market.accounts, quote.tokens_out, max_sol_cost),
+    ];
+    let confirmation = market
+        .submit(instructions, config)
+        .await
+        .context("Buy failed")?;
+
+    OutputFormatter::print_success(&format!(
+        "Bought {} tokens",
+        display::format_token_amount(quote.tokens_out, pumpfun::TOKEN_DECIMALS)
+    ));
+    display::print_key_value("Transaction", &confirmation.signature);
+    OutputFormatter::print_confirmation(&confirmation);
+
+    Ok(())
+}
+
+pub async fn execute_sell(args: SellArgs, config: &Settings) -> Result<()> {
+    validator::validate_slippage_bps(args.max_slippage)?;
+    let amount = parse_token_amount(&args.tokens, pumpfun::TOKEN_DECIMALS)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let market = Market::load(&args.mint, config).await?;
+
+    let token_account = keypair::pubkey_from_bytes(&pda::associated_token_address(
+        &market.accounts.user,
+        &market.accounts.mint,
+    ))?;
+    let balance = market
+        .connection
+        .get_token_account_balance(&token_account)
+        .await
+        .context("Failed to fetch token balance")?
+        .unwrap_or(0);
+    if balance == 0 {
+        anyhow::bail!("The wallet holds no {} tokens", args.mint);
+    }
+    let tokens = amount.resolve(balance);
+    if tokens > balance {
+        anyhow::bail!(
+            "Cannot sell {} tokens; the wallet holds {}",
+            display::format_token_amount(tokens, pumpfun::TOKEN_DECIMALS),
+            display::format_token_amount(balance, pumpfun::TOKEN_DECIMALS)
+        );
+    }
+
+    let quote = market
+        .curve
+        .quote_sell(tokens, market.global.fee_basis_points)?;
+    let min_sol_output = quote.min_sol_output(args.max_slippage);
+    OutputFormatter::print_sell_quote(&quote);
+    display::print_key_value("Min SOL output", &display::format_lamports(min_sol_output));
+
+    let instructions = vec![pumpfun::sell_instruction(
+        &market.accounts,
+        tokens,
+        min_sol_output,
+    )];
+    let confirmation = market
+        .submit(instructions, config)
+        .await
+        .context("Sell failed")?;
+
+    OutputFormatter::print_success(&format!(
+        "Sold {} tokens",
+        display::format_token_amount(tokens, pumpfun::TOKEN_DECIMALS)
+    ));
+    display::print_key_value("Transaction", &confirmation.signature);
+    OutputFormatter::print_confirmation(&confirmation);
+
+    Ok(())
+}
+
+/// Everything needed to trade one mint with the local wallet.
+struct Market {
+    connection: SolanaConnection,
+    keypair: SecretKeypair,
+    global: Global,
+    curve: BondingCurve,
+    accounts: TradeAccounts,
+}
+
+impl Market {
+    async fn load(mint: &str, config: &Settings) -> Result<Self> {
+        let mint = validate_address(mint).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let keypair = WalletManager::select(config, None)?
+            .load_keypair()
+            .context("Trades are signed by the local wallet")?;
+        let user = keypair.pubkey();
+
+        let connection = SolanaConnection::new(&config.rpc_url);
+        let curve = connection
+            .get_bonding_curve(&mint)
+            .await
+            .context("Failed to fetch bonding curve")?
+            .with_context(|| format!("{} has no pump.fun bonding curve", mint))?;
+        let global = connection
+            .get_pump_fun_global()
+            .await
+            .context("Failed to fetch pump.fun fee configuration")?;
+        let accounts = TradeAccounts::new(&global, &curve, keypair::decode_pubkey(&mint)?, user)?;
+
+        Ok(Self {
+            connection,
+            keypair,
+            global,
+            curve,
+            accounts,
+        })
+    }
+
+    /// Sign the instructions with the local wallet as fee payer and send them.
+    async fn submit(
+        &self,
+        instructions: Vec<Instruction>,
+        config: &Settings,
+    ) -> Result<ConfirmedTransaction> {
+        let blockhash = self.connection.get_recent_blockhash().await?;
+        let mut builder = TransactionBuilder::new();
+        for instruction in instructions {
+            builder.add_instruction(instruction);
+        }
+        let tx = builder
+            .set_fee_payer(self.accounts.user)
+            .set_recent_blockhash(blockhash)
+            .sign(&self.keypair)?
+            .build_versioned_transaction()?;
+
+        info!(
+            "Submitting trade from {}",
+            keypair::pubkey_from_bytes(&self.accounts.user)?
+        );
+        let confirmation = self
+            .connection
+            .send_and_confirm(&tx, config.commitment)
+            .await?;
+        if let Some(ref err) = confirmation.err {
+            anyhow::bail!(
+                "Transaction {} failed in slot {}: {}",
+                confirmation.signature,
+                confirmation.slot,
+                err
+            );
+        }
+        Ok(confirmation)
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+use std::io::{self, Read};
+
+use crate::cli::output::OutputFormatter;
+use crate::solana::transaction::VersionedTransaction;
+
+pub fn execute_decode(input: &str) -> Result<()> {
+    let encoded = if input == "-" {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .context("Failed to read transaction from stdin")?;
+        buffer
+    } else {
+        input.to_string()
+    };
+
+    let tx = parse_transaction(encoded.trim())?;
+    let decoded = tx.decode();
+
+    println!();
+    OutputFormatter::print_transaction(&decoded);
+    println!();
+
+    if decoded.signatures.iter().any(|s| s.verified == Some(false)) {
+        anyhow::bail!("Transaction carries invalid signatures");
+    }
+    Ok(())
+}
+
+/// Accept either encoding; base58 strings are usually also valid base64, so
+/// base64 is tried first and only a successful parse counts.
+fn parse_transaction(encoded: &str) -> Result<VersionedTransaction> {
+    VersionedTransaction::from_base64(encoded)
+        .or_else(|_| VersionedTransaction::from_base58(encoded))
+        .context("Input is not a base64 or base58 encoded transaction")
+}
@@ -1,31 +1,57 @@
 use anyhow::{Context, Result};
+use colored::Colorize;
+use zeroize::Zeroizing;
 
 use crate::api::client::ApiClient;
-use crate::api::types::WalletRequest;
+use crate::api::types::{WalletChallengeRequest, WalletRequest};
+use crate::cli::app::{WalletDeriveArgs, WalletImportArgs, WalletRecoverArgs, WalletSetArgs};
 use crate::cli::output::OutputFormatter;
 use crate::cli::parser::{validate_address, validate_agent_id};
 use crate::config::settings::Settings;
+use crate::solana::keypair::{self, SecretKeypair};
+use crate::solana::keystore;
+use crate::solana::mnemonic::{self, Mnemonic};
+use crate::solana::wallet::{OwnershipMessage, WalletManager, WalletSummary};
 use crate::utils::display;
 
-pub async fn execute_set(address: String, signature: String, config: &Settings) -> Result<()> {
-    let address = validate_address(&address).map_err(|e| anyhow::anyhow!("{}", e))?;
+pub async fn execute_set(args: WalletSetArgs, config: &Settings) -> Result<()> {
+    let address = args
+        .address
+        .map(|a| validate_address(&a).map_err(|e| anyhow::anyhow!("{}", e)))
+        .transpose()?;
+    let agent_id = require_agent_id(config)?;
+    let api = ApiClient::new(&config.api_base_url);
 
-    let agent_id = match config.agent_id.as_ref() {
-        Some(id) => {
-            validate_agent_id(id).map_err(|e| anyhow::anyhow!("{}", e))?;
-            id.clone()
+    let (message, signature) = match (address, args.signature, args.nonce, args.issued_at) {
+        (Some(address), Some(signature), Some(nonce), Some(issued_at)) => {
+            let message = OwnershipMessage {
+                agent_id: agent_id.clone(),
+                wallet_address: address,
+                network: network_name(config),
+                issued_at,
+                nonce,
+            };
+            verify_ownership(&message, &signature)?;
+            (message, signature)
+        }
+        (address, _, _, _) => {
+            let wallet = local_wallet(config, address.as_deref())?;
+            let message =
+                fetch_challenge(&api, &agent_id, &wallet.get_public_key()?, config).await?;
+            let signature = sign_ownership(&wallet, &message)?;
+            (message, signature)
         }
-        None => anyhow::bail!(
-            "Agent ID required. Set it in config first: frogpump config set agent_id <id>"
-        ),
     };
 
-    let api = ApiClient::new(&config.api_base_url);
+    let address = message.wallet_address.clone();
     let request = WalletRequest {
         agent_id: agent_id.clone(),
         wallet_address: address.clone(),
         signature,
-        message: format!("frogpump:verify:{}", agent_id),
+        message: message.text(),
+        network: message.network,
+        issued_at: message.issued_at,
+        nonce: message.nonce,
     };
 
     api.set_wallet(request)
@@ -41,6 +67,32 @@ pub async fn execute_set(address: String, signature: String, config: &Settings)
     Ok(())
 }
 
+/// Fetch a challenge and print the message an external wallet must sign.
+pub async fn execute_challenge(address: Option<String>, config: &Settings) -> Result<()> {
+    let address = match address {
+        Some(address) => validate_address(&address).map_err(|e| anyhow::anyhow!("{}", e))?,
+        None => local_wallet(config, None)?.get_public_key()?,
+    };
+    let agent_id = require_agent_id(config)?;
+    let api = ApiClient::new(&config.api_base_url);
+    let message = fetch_challenge(&api, &agent_id, &address, config).await?;
+
+    display::print_header("Wallet Verification Challenge");
+    display::print_key_value("Wallet", &message.wallet_address);
+    display::print_key_value("Nonce", &message.nonce);
+    display::print_key_value("Issued at", &message.issued_at);
+    println!("\n  Sign this text as a Solana off-chain message (e.g. `solana sign-offchain-message`):\n");
+    for line in message.text().lines() {
+        println!("    {}", line);
+    }
+    println!(
+        "\n  Then run: frogpump wallet set --address {} --nonce {} --issued-at {} --signature <SIGNATURE>",
+        message.wallet_address, message.nonce, message.issued_at
+    );
+
+    Ok(())
+}
+
 pub async fn execute_show(agent_id: Option<String>, config: &Settings) -> Result<()> {
     let agent_id = match agent_id.or_else(|| config.agent_id.clone()) {
         Some(id) => {
@@ -62,4 +114,410 @@ pub async fn execute_show(agent_id: Option<String>, config: &Settings) -> Result
     Ok(())
 }
 
+pub fn execute_new(force: bool, encrypt: bool, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    ensure_replaceable(&wallet, force)?;
+
+    save_wallet(&wallet, &SecretKeypair::generate(), encrypt)?;
+
+    print_saved(&wallet, "Generated a new wallet")
+}
+
+pub fn execute_new_mnemonic(
+    words: usize,
+    passphrase: bool,
+    force: bool,
+    encrypt: bool,
+    config: &Settings,
+) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    ensure_replaceable(&wallet, force)?;
+
+    let phrase = Mnemonic::generate(words)?;
+    let passphrase = if passphrase {
+        read_new_mnemonic_passphrase()?
+    } else {
+        Zeroizing::default()
+    };
+    let seed = phrase.to_seed(&passphrase)?;
+    save_wallet(&wallet, &mnemonic::derive_keypair(&seed, 0)?, encrypt)?;
+
+    display::print_header("Recovery Phrase");
+    println!("  {}", phrase.phrase());
+    println!(
+        "\n  Write these {} words down and keep them offline. Anyone who has them controls every wallet derived from them.",
+        phrase.word_count()
+    );
+    println!();
+    display::print_key_value("Derivation path", &mnemonic::format_path(&mnemonic::solana_path(0)));
+    print_saved(&wallet, "Generated a new wallet")
+}
+
+pub fn execute_recover(args: WalletRecoverArgs, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    ensure_replaceable(&wallet, args.force)?;
+
+    let seed = read_seed(args.passphrase)?;
+    save_wallet(&wallet, &mnemonic::derive_keypair(&seed, args.index)?, args.encrypt)?;
+
+    display::print_key_value(
+        "Derivation path",
+        &mnemonic::format_path(&mnemonic::solana_path(args.index)),
+    );
+    print_saved(&wallet, "Recovered wallet")
+}
+
+pub fn execute_derive(args: WalletDeriveArgs, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    if args.save {
+        ensure_replaceable(&wallet, args.force)?;
+    }
+    let seed = read_seed(args.passphrase)?;
+
+    if args.save {
+        save_wallet(&wallet, &mnemonic::derive_keypair(&seed, args.index)?, args.encrypt)?;
+        display::print_key_value(
+            "Derivation path",
+            &mnemonic::format_path(&mnemonic::solana_path(args.index)),
+        );
+        return print_saved(&wallet, "Saved wallet");
+    }
+
+    let last = args
+        .index
+        .checked_add(args.count.max(1) - 1)
+        .context("Account index out of range")?;
+    display::print_header("Derived Wallets");
+    for index in args.index..=last {
+        let kp = mnemonic::derive_keypair(&seed, index)?;
+        display::print_key_value(
+            &mnemonic::format_path(&mnemonic::solana_path(index)),
+            &kp.address(),
+        );
+    }
+    Ok(())
+}
+
+pub fn execute_import(args: WalletImportArgs, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    ensure_replaceable(&wallet, args.force)?;
+
+    let kp = match (args.keypair_file, args.base58) {
+        (Some(path), _) => {
+            let data = std::fs::read_to_string(&path)
+                .map(Zeroizing::new)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            SecretKeypair::from_json(&data)?
+        }
+        (None, Some(encoded)) => SecretKeypair::from_base58(Zeroizing::new(encoded).trim())?,
+        (None, None) => anyhow::bail!("Pass --keypair-file or --base58"),
+    };
+    save_wallet(&wallet, &kp, args.encrypt)?;
+
+    print_saved(&wallet, "Imported wallet")
+}
+
+pub fn execute_export(format: &str, yes: bool, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    if !yes
+        && !display::confirm("This prints your secret key. Anyone who sees it controls the wallet. Continue?")
+            .context("Failed to read confirmation")?
+    {
+        anyhow::bail!("Export cancelled");
+    }
+
+    // Only decrypt the key once the user has agreed to see it.
+    let kp = wallet.load_keypair()?;
+
+    match format {
+        "base58" => println!("{}", *kp.to_base58()),
+        _ => println!("{}", *kp.to_json()),
+    }
+
+    Ok(())
+}
+
+pub fn execute_address(config: &Settings) -> Result<()> {
+    println!("{}", WalletManager::select(config, None)?.get_public_key()?);
+    Ok(())
+}
+
+pub fn execute_encrypt(config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    if wallet.is_encrypted()? {
+        anyhow::bail!("The wallet at {} is already encrypted", wallet.wallet_path().display());
+    }
+    let kp = wallet.load_keypair()?;
+    let passphrase = keystore::read_new_passphrase()?;
+    wallet.save_encrypted(&kp, &passphrase)?;
+
+    OutputFormatter::print_success(&format!("Encrypted wallet {}", wallet.get_public_key()?));
+    display::print_key_value("Saved to", &wallet.wallet_path().display().to_string());
+    println!(
+        "\n  Unattended agents can supply the passphrase via {} or {}",
+        keystore::PASSPHRASE_ENV,
+        keystore::PASSPHRASE_FD_ENV
+    );
+    Ok(())
+}
+
+pub fn execute_decrypt(yes: bool, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    if !wallet.is_encrypted()? {
+        anyhow::bail!("The wallet at {} is not encrypted", wallet.wallet_path().display());
+    }
+    if !yes
+        && !display::confirm("This stores the wallet's secret key unencrypted on disk. Continue?")
+            .context("Failed to read confirmation")?
+    {
+        anyhow::bail!("Decryption cancelled");
+    }
+    let kp = wallet.load_keypair()?;
+    wallet.save_keypair(&kp)?;
+
+    OutputFormatter::print_success(&format!("Decrypted wallet {}", wallet.get_public_key()?));
+    display::print_key_value("Saved to", &wallet.wallet_path().display().to_string());
+    Ok(())
+}
+
+pub fn execute_list(config: &Settings) -> Result<()> {
+    let selected = WalletManager::select(config, None)?;
+    let registry = selected.registry()?;
+
+    let mut wallets = Vec::new();
+    for name in selected.list()? {
+        let wallet = selected.named(&name)?;
+        wallets.push(WalletSummary {
+            address: wallet.get_public_key()?,
+            encrypted: wallet.is_encrypted()?,
+            label: registry.labels.get(&name).cloned(),
+            agents: config
+                .agent_wallets
+                .iter()
+                .filter(|(_, wallet)| **wallet == name)
+                .map(|(agent, _)| agent.clone())
+                .collect(),
+            selected: name == selected.name(),
+            name,
+        });
+    }
+
+    OutputFormatter::print_wallet_list(&wallets);
+    Ok(())
+}
+
+pub fn execute_use(name: &str, agent: Option<String>, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::new().named(name)?;
+    if !wallet.exists() {
+        anyhow::bail!(
+            "No wallet named '{}'. Create one with `frogpump wallet new --wallet {}`.",
+            name,
+            name
+        );
+    }
+
+    match agent {
+        Some(agent) => {
+            let agent = validate_agent_id(&agent).map_err(|e| anyhow::anyhow!("{}", e))?;
+            let mut settings = Settings::load().map_err(|e| anyhow::anyhow!("{}", e))?;
+            settings
+                .agent_wallets
+                .insert(agent.clone(), name.to_string());
+            settings.save().map_err(|e| anyhow::anyhow!("{}", e))?;
+            OutputFormatter::print_success(&format!(
+                "Agent {} now signs with wallet '{}'",
+                agent, name
+            ));
+        }
+        None => {
+            let mut registry = wallet.registry()?;
+            registry.active = Some(name.to_string());
+            wallet.save_registry(&registry)?;
+            OutputFormatter::print_success(&format!("Now using wallet '{}'", name));
+
+            let agent_wallet = config
+                .agent_id
+                .as_ref()
+                .and_then(|agent| Some((agent, config.agent_wallets.get(agent)?)));
+            if let Some((agent, linked)) = agent_wallet.filter(|(_, linked)| *linked != name) {
+                println!(
+                    "\n  {}",
+                    format!(
+                        "Agent {} is linked to wallet '{}', which takes precedence.",
+                        agent, linked
+                    )
+                    .yellow()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_label(label: Option<String>, config: &Settings) -> Result<()> {
+    let wallet = WalletManager::select(config, None)?;
+    if !wallet.exists() {
+        anyhow::bail!("No wallet found at {}", wallet.wallet_path().display());
+    }
+
+    let mut registry = wallet.registry()?;
+    match label.as_deref().map(str::trim).filter(|label| !label.is_empty()) {
+        Some(label) => {
+            registry
+                .labels
+                .insert(wallet.name().to_string(), label.to_string());
+            OutputFormatter::print_success(&format!(
+                "Labelled wallet '{}': {}",
+                wallet.name(),
+                label
+            ));
+        }
+        None => {
+            registry.labels.remove(wallet.name());
+            OutputFormatter::print_success(&format!(
+                "Cleared the label of wallet '{}'",
+                wallet.name()
+            ));
+        }
+    }
+    wallet.save_registry(&registry)
+}
+
+fn require_agent_id(config: &Settings) -> Result<String> {
+    match config.agent_id.as_ref() {
+        Some(id) => validate_agent_id(id).map_err(|e| anyhow::anyhow!("{}", e)),
+        None => anyhow::bail!(
+            "Agent ID required. Set it in config first: frogpump config set agent_id <id>"
+        ),
+    }
+}
+
+fn network_name(config: &Settings) -> String {
+    config.network.config().name
+}
+
+async fn fetch_challenge(
+    api: &ApiClient,
+    agent_id: &str,
+    address: &str,
+    config: &Settings,
+) -> Result<OwnershipMessage> {
+    let challenge = api
+        .wallet_challenge(WalletChallengeRequest {
+            agent_id: agent_id.to_string(),
+            wallet_address: address.to_string(),
+        })
+        .await
+        .context("Failed to fetch wallet verification challenge")?;
+
+    Ok(OwnershipMessage {
+        agent_id: agent_id.to_string(),
+        wallet_address: address.to_string(),
+        network: network_name(config),
+        issued_at: challenge.issued_at,
+        nonce: challenge.nonce,
+    })
+}
+
+/// The local wallet, checked against `address` when one is given.
+fn local_wallet(config: &Settings, address: Option<&str>) -> Result<WalletManager> {
+    let wallet = WalletManager::select(config, None)?;
+    if !wallet.exists() {
+        anyhow::bail!(
+            "No local wallet to sign with at {}. Pass --signature, or create one with `frogpump wallet new`.",
+            wallet.wallet_path().display()
+        );
+    }
+    let local = wallet.get_public_key()?;
+    if let Some(address) = address {
+        if address != local {
+            anyhow::bail!(
+                "{} is not the local wallet ({}). Use `frogpump wallet challenge --address {}` to link a different wallet.",
+                address,
+                local,
+                address
+            );
+        }
+    }
+    Ok(wallet)
+}
+
+/// Sign the ownership message with the local wallet and check the result, returning it in base58.
+fn sign_ownership(wallet: &WalletManager, message: &OwnershipMessage) -> Result<String> {
+    let bytes = message.signing_bytes()?;
+    let signature = wallet.sign_message(&bytes)?;
+    let pubkey = keypair::bytes_from_pubkey(&message.wallet_address)?;
+    if !WalletManager::verify_signature(&pubkey, &bytes, &signature)? {
+        anyhow::bail!("Local wallet produced a signature that does not verify");
+    }
+    Ok(bs58::encode(signature).into_string())
+}
+
+/// Check an externally produced `signature` before it is sent to the API.
+fn verify_ownership(message: &OwnershipMessage, signature: &str) -> Result<()> {
+    let signature = bs58::decode(signature)
+        .into_vec()
+        .context("Signature is not valid base58")?;
+    let pubkey = keypair::bytes_from_pubkey(&message.wallet_address)?;
+    if !WalletManager::verify_signature(&pubkey, &message.signing_bytes()?, &signature)? {
+        anyhow::bail!(
+            "Signature does not verify for wallet {}. Sign the exact text from `wallet challenge` \
+             and pass its --nonce and --issued-at unchanged",
+            message.wallet_address
+        );
+    }
+    Ok(())
+}
+
+/// Read a recovery phrase and optional BIP39 passphrase, and stretch them into a seed.
+fn read_seed(passphrase: bool) -> Result<Zeroizing<[u8; 64]>> {
+    let phrase = display::prompt_secret("Recovery phrase: ").context("Failed to read recovery phrase")?;
+    let phrase = Mnemonic::parse(&phrase)?;
+    let passphrase = if passphrase {
+        display::prompt_secret("Mnemonic passphrase: ").context("Failed to read passphrase")?
+    } else {
+        Zeroizing::default()
+    };
+    phrase.to_seed(&passphrase)
+}
+
+fn read_new_mnemonic_passphrase() -> Result<Zeroizing<String>> {
+    let first = display::prompt_secret("Mnemonic passphrase: ").context("Failed to read passphrase")?;
+    let second = display::prompt_secret("Repeat passphrase: ").context("Failed to read passphrase")?;
+    if first != second {
+        anyhow::bail!("Passphrases do not match");
+    }
+    Ok(first)
+}
+
+fn ensure_replaceable(wallet: &WalletManager, force: bool) -> Result<()> {
+    if wallet.exists() && !force {
+        anyhow::bail!(
+            "A wallet already exists at {}. Pass --force to replace it; its key will be lost unless exported first.",
+            wallet.wallet_path().display()
+        );
+    }
+    Ok(())
+}
+
+/// Save `kp` as the local wallet, encrypted when asked to or when it replaces an
+/// encrypted wallet, so replacing a key never silently drops its protection.
+fn save_wallet(wallet: &WalletManager, kp: &SecretKeypair, encrypt: bool) -> Result<()> {
+    if encrypt || (wallet.exists() && wallet.is_encrypted()?) {
+        let passphrase = keystore::read_new_passphrase()?;
+        wallet.save_encrypted(kp, &passphrase)
+    } else {
+        wallet.save_keypair(kp)
+    }
+}
+
+fn print_saved(wallet: &WalletManager, action: &str) -> Result<()> {
+    let address = wallet.get_public_key()?;
+    OutputFormatter::print_success(&format!("{} {}", action, address));
+    display::print_key_value("Wallet", wallet.name());
+    display::print_key_value("Saved to", &wallet.wallet_path().display().to_string());
+    println!("\n  Link it to your agent with: frogpump wallet set");
+    Ok(())
+}
+
 // iteration 96
@@ -1,5 +1,5 @@
 pub mod network;
 pub mod settings;
 
-pub use network::{Network, NetworkConfig};
+pub use network::{Commitment, Network, NetworkConfig};
 pub use settings::Settings;
@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use crate::config::network::Network;
+use crate::config::network::{Commitment, Network};
+use crate::utils::crypto::is_valid_solana_address;
 use crate::utils::error::{FrogError, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,20 @@ pub struct Settings {
     pub network: Network,
     pub rpc_url: String,
     pub verbose: bool,
+    #[serde(default)]
+    pub commitment: Commitment,
+    /// Platform key expected to pay for gasless launches.
+    #[serde(default)]
+    pub gasless_fee_payer: Option<String>,
+    /// Named local wallet each agent signs with, by agent ID.
+    #[serde(default)]
+    pub agent_wallets: BTreeMap<String, String>,
+    /// Lock decrypted keys into RAM so they are never swapped out or written to core dumps.
+    #[serde(default)]
+    pub lock_memory: bool,
+    /// Wallet picked with `--wallet` for this invocation; never saved.
+    #[serde(skip)]
+    pub wallet_override: Option<String>,
 }
 
 impl Default for Settings {
@@ -25,6 +41,11 @@ impl Default for Settings {
             network: Network::Mainnet,
             rpc_url: Network::Mainnet.default_rpc().to_string(),
             verbose: false,
+            commitment: Commitment::default(),
+            gasless_fee_payer: None,
+            agent_wallets: BTreeMap::new(),
+            lock_memory: false,
+            wallet_override: None,
         }
     }
 }
@@ -62,6 +83,16 @@ impl Settings {
         Ok(())
     }
 
+    /// Name of the local wallet to sign with for `agent_id`, or the configured
+    /// agent: the `--wallet` flag first, then the agent's linked wallet.
+    pub fn wallet_for(&self, agent_id: Option<&str>) -> Option<String> {
+        self.wallet_override.clone().or_else(|| {
+            agent_id
+                .or(self.agent_id.as_deref())
+                .and_then(|id| self.agent_wallets.get(id).cloned())
+        })
+    }
+
     pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
             "api_base_url" => self.api_base_url = value.to_string(),
@@ -82,6 +113,27 @@ impl Settings {
                     FrogError::Config("verbose must be true or false".to_string())
                 })?;
             }
+            "commitment" => {
+                self.commitment = value.parse().map_err(|_| {
+                    FrogError::Config(
+                        "commitment must be processed, confirmed or finalized".to_string(),
+                    )
+                })?;
+            }
+            "lock_memory" => {
+                self.lock_memory = value.parse().map_err(|_| {
+                    FrogError::Config("lock_memory must be true or false".to_string())
+                })?;
+            }
+            "gasless_fee_payer" => {
+                if !is_valid_solana_address(value) {
+                    return Err(FrogError::Config(format!(
+                        "gasless_fee_payer is not a valid Solana address: {}",
+                        value
+                    )));
+                }
+                self.gasless_fee_payer = Some(value.to_string());
+            }
             _ => return Err(FrogError::Config(format!("Unknown setting: {}", key))),
         }
         Ok(())
@@ -95,6 +147,9 @@ impl Settings {
             "network" => Some(format!("{:?}", self.network)),
             "rpc_url" => Some(self.rpc_url.clone()),
             "verbose" => Some(self.verbose.to_string()),
+            "commitment" => Some(self.commitment.as_str().to_string()),
+            "gasless_fee_payer" => self.gasless_fee_payer.clone(),
+            "lock_memory" => Some(self.lock_memory.to_string()),
             _ => None,
         }
     }
@@ -0,0 +1,334 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::solana::accounts::{Metadata, Mint};
+use crate::solana::connection::{LargestTokenAccount, SolanaConnection};
+use crate::solana::keypair;
+use crate::solana::pda;
+use crate::solana::pumpfun::{self, BondingCurve};
+use crate::utils::display;
+
+/// Share of supply held by a single wallet that triggers a warning, in basis points.
+const TOP_HOLDER_WARN_BPS: u64 = 500;
+const TOP_HOLDER_FAIL_BPS: u64 = 1_000;
+
+/// Combined share of the ten largest holders that triggers a warning, in basis points.
+const TOP_TEN_WARN_BPS: u64 = 2_500;
+const TOP_TEN_FAIL_BPS: u64 = 5_000;
+
+/// Share of supply held by the creator that triggers a warning, in basis points.
+const CREATOR_WARN_BPS: u64 = 500;
+const CREATOR_FAIL_BPS: u64 = 2_000;
+
+/// Outcome of a single audit check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl AuditCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Result of auditing a token's on-chain state.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub mint: String,
+    /// The worst status among the checks.
+    pub status: CheckStatus,
+    pub checks: Vec<AuditCheck>,
+}
+
+/// On-chain state an audit is judged on.
+#[derive(Debug, Clone)]
+pub struct TokenState {
+    pub mint: Mint,
+    pub metadata: Option<Metadata>,
+    pub curve: Option<BondingCurve>,
+    /// The bonding curve's token account, which holds unsold supply rather than a holder's.
+    pub curve_token_account: String,
+    pub largest_accounts: Vec<LargestTokenAccount>,
+    pub creator: Option<String>,
+    /// Tokens in the creator's associated token account.
+    pub creator_balance: u64,
+}
+
+/// Fetch everything an audit needs for `mint`.
+pub async fn fetch_token_state(connection: &SolanaConnection, mint: &str) -> Result<TokenState> {
+    let mint_account = connection
+        .get_mint(mint)
+        .await
+        .context("Failed to fetch mint account")?
+        .with_context(|| format!("Mint account {} not found", mint))?;
+    let metadata = connection
+        .get_token_metadata(mint)
+        .await
+        .context("Failed to fetch token metadata")?;
+    let curve = connection
+        .get_bonding_curve(mint)
+        .await
+        .context("Failed to fetch bonding curve")?;
+    let largest_accounts = connection
+        .get_token_largest_accounts(mint)
+        .await
+        .context("Failed to fetch largest token accounts")?;
+
+    let mint_key = keypair::decode_pubkey(mint)?;
+    let curve_key = pumpfun::bonding_curve_address(&mint_key);
+    let curve_token_account =
+        keypair::pubkey_from_bytes(&pda::associated_token_address(&curve_key, &mint_key))?;
+
+    // pump.fun records the creator on the curve; otherwise fall back to the
+    // first creator who verified themselves in the metadata.
+    let creator = curve.as_ref().and_then(|curve| curve.creator).or_else(|| {
+        metadata
+            .as_ref()
+            .and_then(|metadata| metadata.creators.iter().find(|c| c.verified))
+            .map(|creator| creator.address)
+    });
+    let creator_balance = match creator {
+        Some(creator) => {
+            let account =
+                keypair::pubkey_from_bytes(&pda::associated_token_address(&creator, &mint_key))?;
+            connection
+                .get_token_account_balance(&account)
+                .await
+                .context("Failed to fetch creator balance")?
+                .unwrap_or(0)
+        }
+        None => 0,
+    };
+
+    Ok(TokenState {
+        mint: mint_account,
+        metadata,
+        curve,
+        curve_token_account,
+        largest_accounts,
+        creator: creator
+            .map(|creator| keypair::pubkey_from_bytes(&creator))
+            .transpose()?,
+        creator_balance,
+    })
+}
+
+/// Judge a token's on-chain state.
+pub fn audit(mint: &str, state: &TokenState) -> AuditReport {
+    let supply = state.mint.supply;
+    let mut checks = vec![
+        match state.mint.mint_authority {
+            None => AuditCheck::new("Mint authority", CheckStatus::Pass, "revoked"),
+            Some(key) => AuditCheck::new(
+                "Mint authority",
+                CheckStatus::Fail,
+                format!("{} can mint more supply", bs58::encode(key).into_string()),
+            ),
+        },
+        match state.mint.freeze_authority {
+            None => AuditCheck::new("Freeze authority", CheckStatus::Pass, "revoked"),
+            Some(key) => AuditCheck::new(
+                "Freeze authority",
+                CheckStatus::Fail,
+                format!(
+                    "{} can freeze holder accounts",
+                    bs58::encode(key).into_string()
+                ),
+            ),
+        },
+        match &state.metadata {
+            None => AuditCheck::new("Metadata", CheckStatus::Warn, "no Metaplex metadata"),
+            Some(metadata) if metadata.is_mutable => AuditCheck::new(
+                "Metadata",
+                CheckStatus::Warn,
+                format!(
+                    "mutable by {}",
+                    bs58::encode(metadata.update_authority).into_string()
+                ),
+            ),
+            Some(_) => AuditCheck::new("Metadata", CheckStatus::Pass, "immutable"),
+        },
+    ];
+
+    let holders: Vec<&LargestTokenAccount> = state
+        .largest_accounts
+        .iter()
+        .filter(|account| account.address != state.curve_token_account && account.amount > 0)
+        .collect();
+    let top_holder = holders.first().map_or(0, |account| account.amount);
+    let top_ten: u64 = holders.iter().take(10).map(|account| account.amount).sum();
+    checks.push(share_check(
+        "Top holder",
+        share_bps(top_holder, supply),
+        TOP_HOLDER_WARN_BPS,
+        TOP_HOLDER_FAIL_BPS,
+    ));
+    checks.push(share_check(
+        "Top 10 holders",
+        share_bps(top_ten, supply),
+        TOP_TEN_WARN_BPS,
+        TOP_TEN_FAIL_BPS,
+    ));
+
+    checks.push(match &state.creator {
+        Some(creator) => {
+            let mut check = share_check(
+                "Creator share",
+                share_bps(state.creator_balance, supply),
+                CREATOR_WARN_BPS,
+                CREATOR_FAIL_BPS,
+            );
+            check.detail = format!("{} held by {}", check.detail, creator);
+            check
+        }
+        None => AuditCheck::new("Creator share", CheckStatus::Warn, "creator unknown"),
+    });
+
+    checks.push(match &state.curve {
+        Some(curve) if curve.complete => AuditCheck::new(
+            "Graduation",
+            CheckStatus::Pass,
+            "graduated off the bonding curve",
+        ),
+        Some(curve) => AuditCheck::new(
+            "Graduation",
+            CheckStatus::Warn,
+            format!(
+                "on the bonding curve, {:.1}% to graduation",
+                curve.progress_percent()
+            ),
+        ),
+        None => AuditCheck::new("Graduation", CheckStatus::Warn, "no pump.fun bonding curve"),
+    });
+
+    AuditReport {
+        mint: mint.to_string(),
+        status: checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(CheckStatus::Pass),
+        checks,
+    }
+}
+
+fn share_bps(amount: u64, supply: u64) -> u64 {
+    if supply == 0 {
+        return 0;
+    }
+    (amount as u128 * 10_000 / supply as u128) as u64
+}
+
+fn share_check(name: &str, bps: u64, warn_bps: u64, fail_bps: u64) -> AuditCheck {
+    let status = if bps >= fail_bps {
+        CheckStatus::Fail
+    } else if bps >= warn_bps {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    AuditCheck::new(
+        name,
+        status,
+        format!("{} of supply", display::format_bps(bps)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPLY: u64 = 1_000_000_000_000_000;
+
+    fn holder(address: &str, amount: u64) -> LargestTokenAccount {
+        LargestTokenAccount {
+            address: address.to_string(),
+            amount,
+        }
+    }
+
+    fn state() -> TokenState {
+        TokenState {
+            mint: Mint {
+                mint_authority: None,
+                supply: SUPPLY,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: None,
+            },
+            metadata: None,
+            curve: None,
+            curve_token_account: "curve".to_string(),
+            largest_accounts: vec![
+                holder("curve", SUPPLY / 2),
+                holder("a", SUPPLY / 50),
+                holder("b", SUPPLY / 100),
+            ],
+            creator: Some("creator".to_string()),
+            creator_balance: SUPPLY / 100,
+        }
+    }
+
+    fn status_of(report: &AuditReport, name: &str) -> CheckStatus {
+        report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_audit_checks() {
+        let mut state = state();
+        let report = audit("mint", &state);
+        assert_eq!(status_of(&report, "Mint authority"), CheckStatus::Pass);
+        assert_eq!(status_of(&report, "Freeze authority"), CheckStatus::Pass);
+        // The curve's own token account is not counted as a holder.
+        assert_eq!(status_of(&report, "Top holder"), CheckStatus::Pass);
+        assert_eq!(status_of(&report, "Top 10 holders"), CheckStatus::Pass);
+        assert_eq!(status_of(&report, "Creator share"), CheckStatus::Pass);
+        assert_eq!(status_of(&report, "Metadata"), CheckStatus::Warn);
+        assert_eq!(report.status, CheckStatus::Warn);
+
+        state.mint.freeze_authority = Some([0x22; 32]);
+        state.largest_accounts[1].amount = SUPPLY / 8;
+        state.creator_balance = SUPPLY / 4;
+        let report = audit("mint", &state);
+        assert_eq!(status_of(&report, "Freeze authority"), CheckStatus::Fail);
+        assert_eq!(status_of(&report, "Top holder"), CheckStatus::Fail);
+        assert_eq!(status_of(&report, "Creator share"), CheckStatus::Fail);
+        assert_eq!(report.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_share_check_thresholds() {
+        assert_eq!(share_bps(SUPPLY / 20, SUPPLY), 500);
+        assert_eq!(share_bps(1, 0), 0);
+        assert_eq!(share_check("x", 499, 500, 1_000).status, CheckStatus::Pass);
+        assert_eq!(share_check("x", 500, 500, 1_000).status, CheckStatus::Warn);
+        assert_eq!(
+            share_check("x", 1_000, 500, 1_000).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            share_check("x", 1_234, 500, 1_000).detail,
+            "12.34% of supply"
+        );
+    }
+}
@@ -0,0 +1,791 @@
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::solana::pda;
+use crate::solana::programs;
+use crate::solana::pumpfun;
+use crate::solana::transaction::{CompiledInstruction, VersionedTransaction};
+
+/// System program `AdvanceNonceAccount`, the only System instruction the agent may sign.
+const SYSTEM_ADVANCE_NONCE_ACCOUNT: u32 = 4;
+
+/// SPL Token `CloseAccount`, allowed when the rent goes back to the agent.
+const TOKEN_CLOSE_ACCOUNT: u8 = 9;
+
+/// Offsets of the `global`, `fee_recipient`, `mint` and `associated_user`
+/// accounts in pump.fun `buy` and `sell`.
+const PUMP_FUN_TRADE_GLOBAL: usize = 0;
+const PUMP_FUN_TRADE_FEE_RECIPIENT: usize = 1;
+const PUMP_FUN_TRADE_MINT: usize = 2;
+const PUMP_FUN_TRADE_ASSOCIATED_USER: usize = 5;
+
+/// Offset of the `user` account in pump.fun `buy` and `sell`.
+const PUMP_FUN_TRADE_USER: usize = 6;
+
+/// Offset of the `user` account, the rent payer, in pump.fun `create`.
+const PUMP_FUN_CREATE_USER: usize = 7;
+
+/// Programs a co-signed transaction may invoke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AllowedProgram {
+    System,
+    Token,
+    AssociatedToken,
+    Metadata,
+    PumpFun,
+    ComputeBudget,
+}
+
+impl AllowedProgram {
+    const ALL: [AllowedProgram; 6] = [
+        AllowedProgram::System,
+        AllowedProgram::Token,
+        AllowedProgram::AssociatedToken,
+        AllowedProgram::Metadata,
+        AllowedProgram::PumpFun,
+        AllowedProgram::ComputeBudget,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            AllowedProgram::System => programs::SYSTEM_PROGRAM_ID,
+            AllowedProgram::Token => programs::TOKEN_PROGRAM_ID,
+            AllowedProgram::AssociatedToken => programs::ASSOCIATED_TOKEN_PROGRAM_ID,
+            AllowedProgram::Metadata => programs::METADATA_PROGRAM_ID,
+            AllowedProgram::PumpFun => programs::PUMP_FUN_PROGRAM_ID,
+            AllowedProgram::ComputeBudget => programs::COMPUTE_BUDGET_PROGRAM_ID,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            AllowedProgram::System => "System",
+            AllowedProgram::Token => "SPL Token",
+            AllowedProgram::AssociatedToken => "Associated Token",
+            AllowedProgram::Metadata => "Metaplex Token Metadata",
+            AllowedProgram::PumpFun => "pump.fun",
+            AllowedProgram::ComputeBudget => "ComputeBudget",
+        }
+    }
+
+    fn from_key(key: &[u8; 32]) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|program| programs::program_id(program.id()) == *key)
+    }
+}
+
+/// A reason to refuse co-signing a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    UnexpectedFeePayer {
+        expected: String,
+        found: String,
+    },
+    AgentPaysFees,
+    AgentNotSigner,
+    MissingFeePayerSignature,
+    InvalidSignature {
+        signer: String,
+    },
+    ProgramNotAllowed {
+        instruction: usize,
+        program: String,
+    },
+    AgentOutflow {
+        instruction: usize,
+        program: &'static str,
+    },
+    DevBuyOverLimit {
+        instruction: usize,
+        max_sol_cost: u64,
+        allowed: u64,
+    },
+    DevBuyMissing,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::UnexpectedFeePayer { expected, found } => {
+                write!(
+                    f,
+                    "fee payer is {} but the platform fee payer is {}",
+                    found, expected
+                )
+            }
+            Violation::AgentPaysFees => write!(f, "the agent wallet is the fee payer"),
+            Violation::AgentNotSigner => {
+                write!(f, "the transaction does not require the agent's signature")
+            }
+            Violation::MissingFeePayerSignature => {
+                write!(f, "the fee payer has not signed the transaction")
+            }
+            Violation::InvalidSignature { signer } => {
+                write!(f, "signature from {} does not match the message", signer)
+            }
+            Violation::ProgramNotAllowed {
+                instruction,
+                program,
+            } => write!(
+                f,
+                "instruction {} invokes program {} which is not on the allowlist",
+                instruction, program
+            ),
+            Violation::AgentOutflow {
+                instruction,
+                program,
+            } => write!(
+                f,
+                "instruction {} ({}) could move SOL or tokens out of the agent wallet",
+                instruction, program
+            ),
+            Violation::DevBuyOverLimit {
+                instruction,
+                max_sol_cost,
+                allowed,
+            } => write!(
+                f,
+                "instruction {} buys for up to {} lamports but the dev buy allows {}",
+                instruction, max_sol_cost, allowed
+            ),
+            Violation::DevBuyMissing => {
+                write!(f, "the transaction does not include the requested dev buy")
+            }
+        }
+    }
+}
+
+/// Checks a partially signed transaction from the platform before the agent
+/// adds its signature.
+///
+/// The platform must pay the fees and have signed already, every instruction
+/// must target an allowlisted program, and no instruction may use the agent's
+/// signature in a way that lets SOL or tokens leave the agent wallet. The
+/// one exception is a dev buy the agent asked for, see [`allow_dev_buy`].
+///
+/// [`self_funded`] checks a transaction the agent pays for itself the same
+/// way, except that the agent is the fee payer and funds its own `create`.
+///
+/// [`allow_dev_buy`]: CosignPolicy::allow_dev_buy
+/// [`self_funded`]: CosignPolicy::self_funded
+pub struct CosignPolicy {
+    fee_payer: [u8; 32],
+    agent: [u8; 32],
+    dev_buy: Option<DevBuyAllowance>,
+}
+
+/// A purchase of the launched token the agent pays for itself.
+#[derive(Debug, Clone, Copy)]
+struct DevBuyAllowance {
+    mint: [u8; 32],
+    fee_recipient: [u8; 32],
+    max_sol_cost: u64,
+}
+
+impl CosignPolicy {
+    pub fn new(fee_payer: [u8; 32], agent: [u8; 32]) -> Self {
+        Self {
+            fee_payer,
+            agent,
+            dev_buy: None,
+        }
+    }
+
+    /// Check a self-funded transaction: the agent pays the fees and signs
+    /// first, so no fee payer signature is expected yet.
+    pub fn self_funded(agent: [u8; 32]) -> Self {
+        Self::new(agent, agent)
+    }
+
+    /// Require a pump.fun buy of `mint` by the agent, spending at most
+    /// `max_sol_cost` lamports, and permit creating the agent's token account
+    /// for it. `fee_recipient` is the one recorded in pump.fun's `Global` account.
+    pub fn allow_dev_buy(
+        mut self,
+        mint: [u8; 32],
+        fee_recipient: [u8; 32],
+        max_sol_cost: u64,
+    ) -> Self {
+        self.dev_buy = Some(DevBuyAllowance {
+            mint,
+            fee_recipient,
+            max_sol_cost,
+        });
+        self
+    }
+
+    /// Fail with every violation found, or succeed if the transaction is safe to sign.
+    pub fn verify(&self, tx: &VersionedTransaction) -> Result<()> {
+        let violations = self.check(tx);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = violations.iter().map(|v| format!("  - {}", v)).collect();
+        bail!("Transaction failed verification:\n{}", details.join("\n"))
+    }
+
+    /// List every way the transaction breaks the policy.
+    pub fn check(&self, tx: &VersionedTransaction) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let keys = tx.message.static_account_keys();
+
+        match keys.first() {
+            Some(payer) if *payer == self.agent && !self.agent_pays_fees() => {
+                violations.push(Violation::AgentPaysFees)
+            }
+            Some(payer) if *payer != self.fee_payer => {
+                violations.push(Violation::UnexpectedFeePayer {
+                    expected: bs58::encode(self.fee_payer).into_string(),
+                    found: bs58::encode(payer).into_string(),
+                })
+            }
+            _ => {}
+        }
+
+        let agent_index = tx
+            .message
+            .signer_keys()
+            .iter()
+            .position(|key| *key == self.agent);
+        if agent_index.is_none() {
+            violations.push(Violation::AgentNotSigner);
+        }
+
+        for (slot, verified) in tx.verify_signatures().into_iter().enumerate() {
+            match verified {
+                None if slot == 0 && !self.agent_pays_fees() => {
+                    violations.push(Violation::MissingFeePayerSignature)
+                }
+                Some(false) => violations.push(Violation::InvalidSignature {
+                    signer: bs58::encode(keys[slot]).into_string(),
+                }),
+                _ => {}
+            }
+        }
+
+        let mut dev_buy_budget = self.dev_buy.map(|allowance| allowance.max_sol_cost);
+        let mut dev_buy_found = false;
+        for (instruction, ix) in tx.message.instructions().iter().enumerate() {
+            let program_key = keys.get(ix.program_id_index as usize);
+            let Some(program) = program_key.and_then(AllowedProgram::from_key) else {
+                violations.push(Violation::ProgramNotAllowed {
+                    instruction,
+                    program: program_key
+                        .map(|key| bs58::encode(key).into_string())
+                        .unwrap_or_else(|| "<unresolved>".to_string()),
+                });
+                continue;
+            };
+
+            let Some(agent_index) = agent_index else {
+                continue;
+            };
+            let agent_index = agent_index as u8;
+            if let Some(max_sol_cost) = self.dev_buy_cost(tx, program, ix, agent_index) {
+                dev_buy_found = true;
+                let allowed = dev_buy_budget.unwrap_or(0);
+                match allowed.checked_sub(max_sol_cost) {
+                    Some(remaining) => dev_buy_budget = Some(remaining),
+                    None => violations.push(Violation::DevBuyOverLimit {
+                        instruction,
+                        max_sol_cost,
+                        allowed,
+                    }),
+                }
+                continue;
+            }
+            if self.creates_dev_buy_account(tx, program, ix, agent_index) {
+                continue;
+            }
+            if self.may_debit_agent(tx, program, ix, agent_index) {
+                violations.push(Violation::AgentOutflow {
+                    instruction,
+                    program: program.name(),
+                });
+            }
+        }
+        if self.dev_buy.is_some() && !dev_buy_found {
+            violations.push(Violation::DevBuyMissing);
+        }
+
+        violations
+    }
+
+    fn agent_pays_fees(&self) -> bool {
+        self.fee_payer == self.agent
+    }
+
+    /// The SOL limit of a pump.fun buy of the dev-buy mint paid by the agent.
+    ///
+    /// The tokens must go to the agent's own token account and the fee to the
+    /// protocol's fee recipient; a buy routed anywhere else is not the dev buy.
+    fn dev_buy_cost(
+        &self,
+        tx: &VersionedTransaction,
+        program: AllowedProgram,
+        ix: &CompiledInstruction,
+        agent_index: u8,
+    ) -> Option<u64> {
+        let allowance = self.dev_buy?;
+        if program != AllowedProgram::PumpFun
+            || !ix.data.starts_with(&pumpfun::BUY_DISCRIMINATOR)
+            || ix.accounts.get(PUMP_FUN_TRADE_USER) != Some(&agent_index)
+            || account_key(tx, ix, PUMP_FUN_TRADE_MINT) != Some(allowance.mint)
+            || account_key(tx, ix, PUMP_FUN_TRADE_GLOBAL) != Some(pumpfun::global_address())
+            || account_key(tx, ix, PUMP_FUN_TRADE_FEE_RECIPIENT) != Some(allowance.fee_recipient)
+            || account_key(tx, ix, PUMP_FUN_TRADE_ASSOCIATED_USER)
+                != Some(pda::associated_token_address(&self.agent, &allowance.mint))
+        {
+            return None;
+        }
+        let max_sol_cost = ix.data.get(16..24)?;
+        Some(u64::from_le_bytes(max_sol_cost.try_into().unwrap()))
+    }
+
+    /// Whether an instruction creates the agent's own token account for the dev-buy mint.
+    fn creates_dev_buy_account(
+        &self,
+        tx: &VersionedTransaction,
+        program: AllowedProgram,
+        ix: &CompiledInstruction,
+        agent_index: u8,
+    ) -> bool {
+        let Some(allowance) = self.dev_buy else {
+            return false;
+        };
+        // `Create` (empty data or 0) and `CreateIdempotent` (1), paid for and owned by the agent.
+        program == AllowedProgram::AssociatedToken
+            && matches!(ix.data[..], [] | [0] | [1])
+            && ix.accounts.first() == Some(&agent_index)
+            && ix.accounts.get(2) == Some(&agent_index)
+            && account_key(tx, ix, 3) == Some(allowance.mint)
+    }
+
+    /// Whether an instruction could use the agent's signature to move funds out.
+    fn may_debit_agent(
+        &self,
+        tx: &VersionedTransaction,
+        program: AllowedProgram,
+        ix: &CompiledInstruction,
+        agent_index: u8,
+    ) -> bool {
+        if !ix.accounts.contains(&agent_index) {
+            return false;
+        }
+        let agent_writable = tx.message.is_writable(agent_index as usize);
+
+        match program {
+            // Any signed System instruction can transfer, fund, assign or
+            // allocate the agent account; only advancing a nonce is harmless.
+            AllowedProgram::System => {
+                let tag = ix
+                    .data
+                    .get(..4)
+                    .map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+                tag != Some(SYSTEM_ADVANCE_NONCE_ACCOUNT)
+            }
+            // The agent's signature is token-account authority: it can
+            // transfer, approve, burn or hand over its tokens. Closing an
+            // account is fine as long as the rent comes back to the agent.
+            AllowedProgram::Token => {
+                let closes_to_agent = ix.data.first() == Some(&TOKEN_CLOSE_ACCOUNT)
+                    && ix.accounts.get(1) == Some(&agent_index);
+                !closes_to_agent
+            }
+            // A self-funded agent pays the rent of its own launch.
+            AllowedProgram::PumpFun
+                if self.agent_pays_fees()
+                    && ix.data.starts_with(&pumpfun::CREATE_DISCRIMINATOR) =>
+            {
+                false
+            }
+            // A dev buy makes the agent writable across the whole message, so
+            // pump.fun's `create` is judged by whether the agent pays its rent.
+            AllowedProgram::PumpFun
+                if self.dev_buy.is_some()
+                    && ix.data.starts_with(&pumpfun::CREATE_DISCRIMINATOR) =>
+            {
+                ix.accounts.get(PUMP_FUN_CREATE_USER) == Some(&agent_index)
+            }
+            // These programs only debit a signer that is also writable, e.g.
+            // as the rent payer or the trader of a pump.fun buy or sell.
+            AllowedProgram::AssociatedToken
+            | AllowedProgram::Metadata
+            | AllowedProgram::PumpFun => agent_writable,
+            AllowedProgram::ComputeBudget => false,
+        }
+    }
+}
+
+/// Static account key at `position` in an instruction's account list.
+fn account_key(
+    tx: &VersionedTransaction,
+    ix: &CompiledInstruction,
+    position: usize,
+) -> Option<[u8; 32]> {
+    let index = *ix.accounts.get(position)?;
+    tx.message
+        .static_account_keys()
+        .get(index as usize)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::keypair::SecretKeypair;
+    use crate::solana::programs::program_id;
+    use crate::solana::transaction::{
+        create_associated_token_account_idempotent, set_compute_unit_limit, AccountMeta,
+        Instruction, TransactionBuilder,
+    };
+
+    const BLOCKHASH: &str = "LbUiWL3xVV8hTFYBVdbTNrpDo41NKS6o3LHHuDzjfcY";
+    const FEE_RECIPIENT: [u8; 32] = [0x57; 32];
+
+    fn keypair_from_seed(seed: u8) -> SecretKeypair {
+        SecretKeypair::from_secret(&[seed; 32])
+    }
+
+    fn pubkey(keypair: &SecretKeypair) -> [u8; 32] {
+        keypair.pubkey()
+    }
+
+    fn system_transfer(from: [u8; 32], to: [u8; 32]) -> Instruction {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        Instruction {
+            program_id: program_id(programs::SYSTEM_PROGRAM_ID),
+            accounts: vec![AccountMeta::new(from, true), AccountMeta::new(to, false)],
+            data,
+        }
+    }
+
+    /// A pump.fun instruction naming the agent as a read-only signer, e.g. the creator.
+    fn pump_create(backend: [u8; 32], agent: [u8; 32]) -> Instruction {
+        Instruction {
+            program_id: program_id(programs::PUMP_FUN_PROGRAM_ID),
+            accounts: vec![
+                AccountMeta::new([0x44; 32], false),
+                AccountMeta::new(backend, true),
+                AccountMeta::new_readonly(agent, true),
+            ],
+            data: vec![0x18, 0x1e, 0xc8, 0x28, 0x05, 0x1c, 0x07, 0x77],
+        }
+    }
+
+    /// Build a transaction paid and signed by the backend, leaving the agent's slot empty.
+    fn build(backend: &SecretKeypair, instructions: Vec<Instruction>) -> VersionedTransaction {
+        let mut builder = TransactionBuilder::new();
+        builder.add_instruction(set_compute_unit_limit(200_000));
+        for ix in instructions {
+            builder.add_instruction(ix);
+        }
+        builder
+            .set_fee_payer(pubkey(backend))
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .sign(backend)
+            .unwrap();
+        builder.build_transaction().unwrap().into()
+    }
+
+    #[test]
+    fn test_accepts_sponsored_launch() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let tx = build(
+            &backend,
+            vec![
+                pump_create(pubkey(&backend), pubkey(&agent)),
+                system_transfer(pubkey(&backend), [0x55; 32]),
+            ],
+        );
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent));
+        assert_eq!(policy.check(&tx), vec![]);
+        assert!(policy.verify(&tx).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_sol_outflow_from_agent() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let tx = build(
+            &backend,
+            vec![
+                pump_create(pubkey(&backend), pubkey(&agent)),
+                system_transfer(pubkey(&agent), [0x55; 32]),
+            ],
+        );
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent));
+        // The transfer makes the agent writable for the whole message, so the
+        // pump.fun instruction could now debit it as well.
+        assert_eq!(
+            policy.check(&tx),
+            vec![
+                Violation::AgentOutflow {
+                    instruction: 1,
+                    program: "pump.fun",
+                },
+                Violation::AgentOutflow {
+                    instruction: 2,
+                    program: "System",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_authority_rules() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent));
+        let token_ix = |data: Vec<u8>, destination: [u8; 32]| Instruction {
+            program_id: program_id(programs::TOKEN_PROGRAM_ID),
+            accounts: vec![
+                AccountMeta::new([0x66; 32], false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(pubkey(&agent), true),
+            ],
+            data,
+        };
+
+        let transfer = build(
+            &backend,
+            vec![token_ix(vec![3, 1, 0, 0, 0, 0, 0, 0, 0], [0x77; 32])],
+        );
+        assert_eq!(policy.check(&transfer).len(), 1);
+
+        let close = build(
+            &backend,
+            vec![token_ix(vec![TOKEN_CLOSE_ACCOUNT], pubkey(&agent))],
+        );
+        assert_eq!(policy.check(&close), vec![]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_program_and_fee_payer() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let unknown = Instruction {
+            program_id: [0xEE; 32],
+            accounts: vec![],
+            data: vec![],
+        };
+        let tx = build(
+            &backend,
+            vec![pump_create(pubkey(&backend), pubkey(&agent)), unknown],
+        );
+
+        let policy = CosignPolicy::new([0x99; 32], pubkey(&agent));
+        let violations = policy.check(&tx);
+        assert!(matches!(
+            violations[0],
+            Violation::UnexpectedFeePayer { .. }
+        ));
+        assert!(matches!(
+            violations[1],
+            Violation::ProgramNotAllowed { instruction: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_self_funded_launch() {
+        let (backend, agent, mint) = (
+            keypair_from_seed(1),
+            keypair_from_seed(2),
+            keypair_from_seed(3),
+        );
+        let mut accounts: Vec<AccountMeta> = (0x40..0x47)
+            .map(|seed| AccountMeta::new([seed; 32], false))
+            .collect();
+        accounts[0] = AccountMeta::new(pubkey(&mint), true);
+        accounts.push(AccountMeta::new(pubkey(&agent), true));
+        let create = Instruction {
+            program_id: program_id(programs::PUMP_FUN_PROGRAM_ID),
+            accounts,
+            data: pumpfun::CREATE_DISCRIMINATOR.to_vec(),
+        };
+        // Paid by the agent, which has not signed yet; the mint has.
+        let prepare = |payer: [u8; 32], instructions: Vec<Instruction>| {
+            let mut builder = TransactionBuilder::new();
+            builder.add_instruction(set_compute_unit_limit(200_000));
+            for ix in instructions {
+                builder.add_instruction(ix);
+            }
+            builder
+                .set_fee_payer(payer)
+                .set_recent_blockhash(BLOCKHASH.to_string())
+                .sign(&mint)
+                .unwrap();
+            builder.build_versioned_transaction().unwrap()
+        };
+        let policy = CosignPolicy::self_funded(pubkey(&agent));
+        assert_eq!(
+            policy.check(&prepare(pubkey(&agent), vec![create.clone()])),
+            vec![]
+        );
+
+        // A transfer slipped in by the backend is still refused.
+        let injected = prepare(
+            pubkey(&agent),
+            vec![create.clone(), system_transfer(pubkey(&agent), [0x55; 32])],
+        );
+        assert_eq!(
+            policy.check(&injected),
+            vec![Violation::AgentOutflow {
+                instruction: 2,
+                program: "System",
+            }]
+        );
+        let token_transfer = Instruction {
+            program_id: program_id(programs::TOKEN_PROGRAM_ID),
+            accounts: vec![
+                AccountMeta::new([0x66; 32], false),
+                AccountMeta::new([0x77; 32], false),
+                AccountMeta::new_readonly(pubkey(&agent), true),
+            ],
+            data: vec![3, 1, 0, 0, 0, 0, 0, 0, 0],
+        };
+        let injected = prepare(pubkey(&agent), vec![create.clone(), token_transfer]);
+        assert_eq!(
+            policy.check(&injected),
+            vec![Violation::AgentOutflow {
+                instruction: 2,
+                program: "SPL Token",
+            }]
+        );
+
+        // So is a launch someone else pays for.
+        assert!(matches!(
+            policy.check(&prepare(pubkey(&backend), vec![create]))[..],
+            [Violation::UnexpectedFeePayer { .. }, ..]
+        ));
+    }
+
+    #[test]
+    fn test_requires_valid_fee_payer_signature() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent));
+
+        let mut tx = build(
+            &backend,
+            vec![pump_create(pubkey(&backend), pubkey(&agent))],
+        );
+        tx.signatures[0] = [0u8; 64];
+        assert_eq!(policy.check(&tx), vec![Violation::MissingFeePayerSignature]);
+
+        tx.signatures[0] = [1u8; 64];
+        assert!(matches!(
+            policy.check(&tx)[..],
+            [Violation::InvalidSignature { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_dev_buy_allowance() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let mint = [0x44; 32];
+        let trade = pumpfun::TradeAccounts {
+            mint,
+            creator: pubkey(&agent),
+            user: pubkey(&agent),
+            fee_recipient: FEE_RECIPIENT,
+        };
+        let tx = build(
+            &backend,
+            vec![
+                pump_create(pubkey(&backend), pubkey(&agent)),
+                create_associated_token_account_idempotent(&pubkey(&agent), &pubkey(&agent), &mint),
+                pumpfun::buy_instruction(&trade, 1_000, 500_000_000),
+            ],
+        );
+
+        // Without the allowance the agent's writable account taints every instruction naming it.
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent));
+        assert_eq!(
+            policy.check(&tx),
+            vec![
+                Violation::AgentOutflow {
+                    instruction: 1,
+                    program: "pump.fun",
+                },
+                Violation::AgentOutflow {
+                    instruction: 2,
+                    program: "Associated Token",
+                },
+                Violation::AgentOutflow {
+                    instruction: 3,
+                    program: "pump.fun",
+                },
+            ]
+        );
+
+        let policy = policy.allow_dev_buy(mint, FEE_RECIPIENT, 500_000_000);
+        assert_eq!(policy.check(&tx), vec![]);
+
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent)).allow_dev_buy(
+            mint,
+            FEE_RECIPIENT,
+            1,
+        );
+        assert_eq!(
+            policy.check(&tx),
+            vec![Violation::DevBuyOverLimit {
+                instruction: 3,
+                max_sol_cost: 500_000_000,
+                allowed: 1,
+            }]
+        );
+
+        // A buy of some other mint is not the dev buy.
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent)).allow_dev_buy(
+            [0x45; 32],
+            FEE_RECIPIENT,
+            u64::MAX,
+        );
+        let violations = policy.check(&tx);
+        assert!(violations.contains(&Violation::DevBuyMissing));
+        assert!(violations.contains(&Violation::AgentOutflow {
+            instruction: 3,
+            program: "pump.fun",
+        }));
+    }
+
+    #[test]
+    fn test_dev_buy_accounts_must_be_genuine() {
+        let (backend, agent) = (keypair_from_seed(1), keypair_from_seed(2));
+        let mint = [0x44; 32];
+        let trade = pumpfun::TradeAccounts {
+            mint,
+            creator: pubkey(&agent),
+            user: pubkey(&agent),
+            fee_recipient: FEE_RECIPIENT,
+        };
+        let policy = CosignPolicy::new(pubkey(&backend), pubkey(&agent)).allow_dev_buy(
+            mint,
+            FEE_RECIPIENT,
+            u64::MAX,
+        );
+        let tampered = |position: usize, key: [u8; 32]| {
+            let mut buy = pumpfun::buy_instruction(&trade, 1_000, 500_000_000);
+            buy.accounts[position].pubkey = key;
+            build(
+                &backend,
+                vec![pump_create(pubkey(&backend), pubkey(&agent)), buy],
+            )
+        };
+
+        // The backend's own token account would receive the agent's tokens.
+        let backend_ata = pda::associated_token_address(&pubkey(&backend), &mint);
+        for (position, key) in [
+            (PUMP_FUN_TRADE_ASSOCIATED_USER, backend_ata),
+            (PUMP_FUN_TRADE_FEE_RECIPIENT, [0x55; 32]),
+            (PUMP_FUN_TRADE_GLOBAL, [0x56; 32]),
+        ] {
+            let violations = policy.check(&tampered(position, key));
+            assert!(violations.contains(&Violation::DevBuyMissing));
+            assert!(violations.contains(&Violation::AgentOutflow {
+                instruction: 2,
+                program: "pump.fun",
+            }));
+        }
+    }
+}
@@ -2,28 +2,96 @@ use anyhow::{Context, Result};
 use log::info;
 
 use crate::api::client::ApiClient;
-use crate::api::types::{LaunchRequest, LaunchResponse};
+use crate::api::types::{LaunchRequest, LaunchResponse, PreparedTransaction};
 use crate::config::settings::Settings;
+use crate::core::cosign::CosignPolicy;
+use crate::core::offline::{self, OfflineKind, OfflineTransaction};
 use crate::core::token::TokenBuilder;
 use crate::core::validator;
 use crate::models::LaunchType;
+use crate::solana::connection::{ConfirmedTransaction, SimulationResult, SolanaConnection};
+use crate::solana::keypair::{self, SecretKeypair};
+use crate::solana::programs;
+use crate::solana::pumpfun::{self, BondingCurve, TradeAccounts};
+use crate::solana::transaction::{
+    create_associated_token_account_idempotent, set_compute_unit_limit, set_compute_unit_price,
+    Instruction, TransactionBuilder, VersionedMessage, VersionedTransaction,
+    SET_COMPUTE_UNIT_LIMIT, SET_COMPUTE_UNIT_PRICE,
+};
+use crate::solana::wallet::WalletManager;
+use crate::utils::display;
+
+/// Priority fee policy for self-funded launches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// Estimate from recent prioritization fees on the configured RPC node.
+    Auto,
+    /// A fixed price in micro-lamports per compute unit.
+    MicroLamports(u64),
+}
+
+/// A launch that has landed on-chain at the configured commitment.
+#[derive(Debug, Clone)]
+pub struct LaunchOutcome {
+    pub response: LaunchResponse,
+    pub confirmation: ConfirmedTransaction,
+}
 
 /// Core engine that orchestrates token launches through the FrogPump platform.
 pub struct LaunchEngine {
     api: ApiClient,
     config: Settings,
+    priority_fee: Option<PriorityFee>,
+    compute_unit_limit: Option<u32>,
+    dev_buy_lamports: Option<u64>,
+    dev_buy_slippage_bps: u64,
+    mint_keypair: Option<SecretKeypair>,
 }
 
 impl LaunchEngine {
     /// Create a new LaunchEngine with the given API client and settings.
     pub fn new(api: ApiClient, config: Settings) -> Self {
-        Self { api, config }
+        Self {
+            api,
+            config,
+            priority_fee: None,
+            compute_unit_limit: None,
+            dev_buy_lamports: None,
+            dev_buy_slippage_bps: 0,
+            mint_keypair: None,
+        }
+    }
+
+    /// Set the priority fee policy applied to self-funded launches.
+    pub fn with_priority_fee(mut self, priority_fee: Option<PriorityFee>) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// Set the compute unit limit requested by self-funded launches.
+    pub fn with_compute_unit_limit(mut self, units: Option<u32>) -> Self {
+        self.compute_unit_limit = units;
+        self
+    }
+
+    /// Buy the new token for `lamports` in the same transaction that launches it.
+    pub fn with_dev_buy(mut self, lamports: Option<u64>, slippage_bps: u64) -> Self {
+        self.dev_buy_lamports = lamports;
+        self.dev_buy_slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Use `keypair` as the new token's mint in self-funded launches, e.g. a vanity address.
+    pub fn with_mint_keypair(mut self, keypair: Option<SecretKeypair>) -> Self {
+        self.mint_keypair = keypair;
+        self
     }
 
     /// Execute a token launch with the provided parameters.
     ///
     /// Validates all inputs, builds the launch request, and dispatches
-    /// to either gasless or self-funded execution path.
+    /// to either gasless or self-funded execution path. Returns once the
+    /// launch transaction has reached the configured commitment.
     pub async fn execute_launch(
         &self,
         name: String,
@@ -32,7 +100,133 @@ impl LaunchEngine {
         image_url: Option<String>,
         agent_id: Option<String>,
         self_funded: bool,
-    ) -> Result<LaunchResponse> {
+    ) -> Result<LaunchOutcome> {
+        let request =
+            self.build_request(name, symbol, description, image_url, agent_id, self_funded)?;
+
+        info!(
+            "Launching token {} ({}) for agent {}",
+            request.name, request.symbol, request.agent_id
+        );
+
+        let outcome = if self_funded {
+            self.execute_self_funded(request).await?
+        } else {
+            self.execute_gasless(request).await?
+        };
+
+        if let Some(ref err) = outcome.confirmation.err {
+            anyhow::bail!(
+                "Launch transaction {} failed in slot {}: {}",
+                outcome.confirmation.signature,
+                outcome.confirmation.slot,
+                err
+            );
+        }
+        Ok(outcome)
+    }
+
+    /// Simulate a launch without submitting anything.
+    ///
+    /// The backend assembles the launch transaction, which is decoded locally
+    /// and run through `simulateTransaction` on the configured RPC node.
+    pub async fn simulate_launch(
+        &self,
+        name: String,
+        symbol: String,
+        description: Option<String>,
+        image_url: Option<String>,
+        agent_id: Option<String>,
+        self_funded: bool,
+    ) -> Result<SimulationResult> {
+        let request =
+            self.build_request(name, symbol, description, image_url, agent_id, self_funded)?;
+
+        info!("Simulating launch of {} ({})", request.name, request.symbol);
+
+        let tx = if self_funded {
+            let agent = self.self_funded_payer(&request.agent_id)?;
+            self.prepare_self_funded(request, agent, false).await?.1
+        } else {
+            let prepared = self
+                .api
+                .prepare_launch(request)
+                .await
+                .context("Failed to prepare launch transaction")?;
+            VersionedTransaction::from_base64(&prepared.transaction)
+                .context("Backend returned an invalid launch transaction")?
+        };
+
+        SolanaConnection::new(&self.config.rpc_url)
+            .simulate_transaction(&tx)
+            .await
+            .context("Launch simulation failed")
+    }
+
+    /// Prepare a self-funded launch to be signed by the wallet on another machine.
+    ///
+    /// With `nonce_account` the transaction uses that durable nonce instead of
+    /// a recent blockhash, so it stays valid while it is carried offline. The
+    /// mint keypair, if one is made locally, signs here; only the wallet's
+    /// signature is left to add.
+    pub async fn export_launch(
+        &self,
+        name: String,
+        symbol: String,
+        description: Option<String>,
+        image_url: Option<String>,
+        agent_id: Option<String>,
+        nonce_account: Option<&str>,
+    ) -> Result<OfflineTransaction> {
+        let mut request =
+            self.build_request(name, symbol, description, image_url, agent_id, true)?;
+        request.self_funded = true;
+
+        info!("Exporting launch of {} ({})", request.name, request.symbol);
+
+        let mut summary = format!("Launch {} ({})", request.name, request.symbol);
+        if let Some(lamports) = request.dev_buy_lamports {
+            summary.push_str(&format!(
+                " with a dev buy of {}",
+                display::format_lamports(lamports)
+            ));
+        }
+        let agent_id = request.agent_id.clone();
+        let agent = self.self_funded_payer(&agent_id)?;
+        let (prepared, mut tx, mint_keypair) = self
+            .prepare_self_funded(request, agent, nonce_account.is_some())
+            .await?;
+        if let Some(account) = nonce_account {
+            let connection = SolanaConnection::new(&self.config.rpc_url);
+            tx = offline::bind_durable_nonce(&connection, &tx, account)
+                .await
+                .context("Failed to bind the launch to the durable nonce")?;
+        }
+        if let Some(ref mint_keypair) = mint_keypair {
+            tx.sign(mint_keypair)?;
+        }
+
+        let mut exported = OfflineTransaction::new(
+            OfflineKind::Launch,
+            summary,
+            self.config.network,
+            agent_id,
+            &tx,
+        );
+        exported.mint_address = prepared.mint_address;
+        Ok(exported)
+    }
+
+    /// Validate the launch parameters and assemble the request.
+    fn build_request(
+        &self,
+        name: String,
+        symbol: String,
+        description: Option<String>,
+        image_url: Option<String>,
+        agent_id: Option<String>,
+        self_funded: bool,
+    ) -> Result<LaunchRequest> {
         let agent_id = agent_id
             .or_else(|| self.config.agent_id.clone())
             .context("Agent ID is required. Set it via --agent-id or in config.")?;
@@ -40,6 +234,9 @@ impl LaunchEngine {
         validator::validate_token_name(&name)?;
         validator::validate_symbol(&symbol)?;
         validator::validate_agent_id(&agent_id)?;
+        if let Some(units) = self.compute_unit_limit {
+            validator::validate_compute_unit_limit(units)?;
+        }
 
         let launch_type = if self_funded {
             LaunchType::SelfFunded
@@ -47,45 +244,370 @@ impl LaunchEngine {
             LaunchType::Gasless
         };
 
-        let request = TokenBuilder::new(name, symbol)
+        TokenBuilder::new(name, symbol)
             .description(description)
             .image_url(image_url)
-            .agent_id(agent_id.clone())
+            .agent_id(agent_id)
             .launch_type(launch_type)
-            .build()?;
-
-        info!("Launching token {} ({}) for agent {}", request.name, request.symbol, agent_id);
-
-        if self_funded {
-            self.execute_self_funded(request).await
-        } else {
-            self.execute_gasless(request).await
-        }
+            .dev_buy(self.dev_buy_lamports, self.dev_buy_slippage_bps)
+            .build()
     }
 
     /// Execute a gasless launch through the platform's sponsored transaction flow.
-    async fn execute_gasless(&self, request: LaunchRequest) -> Result<LaunchResponse> {
+    ///
+    /// The platform returns a transaction it has already signed as fee payer.
+    /// It is checked against the co-signing policy before the local wallet
+    /// adds its signature and the engine submits it.
+    async fn execute_gasless(&self, request: LaunchRequest) -> Result<LaunchOutcome> {
         info!("Using gasless launch mode");
-        self.api
-            .launch(request)
+        let dev_buy_limit = dev_buy_limit(&request);
+        let wallet = WalletManager::select(&self.config, Some(&request.agent_id))?;
+        let prepared = self
+            .api
+            .prepare_launch(request)
+            .await
+            .context("Failed to prepare gasless launch")?;
+        let tx = VersionedTransaction::from_base64(&prepared.transaction)
+            .context("Backend returned an invalid launch transaction")?;
+
+        let keypair = wallet
+            .load_keypair()
+            .context("Gasless launches are countersigned by the local wallet")?;
+        let agent = keypair.pubkey();
+        let fee_payer = self.expected_fee_payer(&prepared)?;
+        let mut policy = CosignPolicy::new(fee_payer, agent);
+        if let Some(max_sol_cost) = dev_buy_limit {
+            // The backend includes the dev buy; the agent pays for it, within its limit.
+            let mint = prepared
+                .mint_address
+                .as_deref()
+                .context("Backend did not return a mint address for the launch")?;
+            let global = SolanaConnection::new(&self.config.rpc_url)
+                .get_pump_fun_global()
+                .await
+                .context("Failed to fetch pump.fun fee configuration")?;
+            policy = policy.allow_dev_buy(
+                keypair::decode_pubkey(mint)?,
+                global.fee_recipient,
+                max_sol_cost,
+            );
+        }
+        policy
+            .verify(&tx)
+            .context("Refusing to sign the gasless launch transaction")?;
+
+        self.sign_and_submit(prepared, tx, &[keypair])
             .await
             .context("Gasless launch failed")
     }
 
     /// Execute a self-funded launch where the agent pays transaction fees.
-    async fn execute_self_funded(&self, mut request: LaunchRequest) -> Result<LaunchResponse> {
+    ///
+    /// The backend prepares the transaction, which is checked before the local
+    /// wallet signs it as fee payer and the engine submits it directly to the
+    /// configured RPC node.
+    async fn execute_self_funded(&self, mut request: LaunchRequest) -> Result<LaunchOutcome> {
         info!("Using self-funded launch mode");
         request.self_funded = true;
-        self.api
-            .launch(request)
+
+        let keypair = WalletManager::select(&self.config, Some(&request.agent_id))?
+            .load_keypair()
+            .context("Self-funded launches are signed by the local wallet")?;
+        let (prepared, tx, mint_keypair) = self
+            .prepare_self_funded(request, keypair.pubkey(), false)
+            .await?;
+
+        let mut signers = vec![keypair];
+        signers.extend(mint_keypair);
+        self.sign_and_submit(prepared, tx, &signers)
             .await
             .context("Self-funded launch failed")
     }
+
+    /// Have the backend prepare a self-funded launch paid by `agent`, then set
+    /// its compute budget and add the dev buy locally.
+    ///
+    /// The prepared transaction is held to the co-signing policy with the agent
+    /// as fee payer before anything is added to it, so the backend cannot slip
+    /// in a transfer out of the wallet that pays. The compute unit limit and
+    /// priority fee it carries are set here instead of being trusted from the
+    /// backend, and the dev buy is appended here. Rebuilding the message would
+    /// void any signature the backend made, so a rebuilt launch uses a mint
+    /// keypair generated here; `local_mint` asks for one even when nothing else
+    /// needs the rebuild, for transactions that are rebuilt again afterwards.
+    /// A mint keypair set with `with_mint_keypair` is used instead when given.
+    /// Either is returned for signing alongside the wallet.
+    async fn prepare_self_funded(
+        &self,
+        mut request: LaunchRequest,
+        agent: [u8; 32],
+        local_mint: bool,
+    ) -> Result<(
+        PreparedTransaction,
+        VersionedTransaction,
+        Option<SecretKeypair>,
+    )> {
+        let dev_buy = request
+            .dev_buy_lamports
+            .map(|lamports| (lamports, request.dev_buy_slippage_bps.unwrap_or(0)));
+        let rebuild = dev_buy.is_some()
+            || local_mint
+            || self.compute_unit_limit.is_some()
+            || self.priority_fee.is_some();
+        let mint_keypair = self
+            .mint_keypair
+            .clone()
+            .or_else(|| rebuild.then(SecretKeypair::generate));
+        if let Some(ref mint_keypair) = mint_keypair {
+            request.mint_address = Some(mint_keypair.address());
+        }
+        let requested_mint = request.mint_address.clone();
+        request.compute_unit_limit = self.compute_unit_limit;
+        request.compute_unit_price = match self.priority_fee {
+            Some(PriorityFee::MicroLamports(price)) => Some(price),
+            _ => None,
+        };
+
+        let prepared = self
+            .api
+            .prepare_launch(request)
+            .await
+            .context("Failed to prepare launch transaction")?;
+        let tx = VersionedTransaction::from_base64(&prepared.transaction)
+            .context("Backend returned an invalid launch transaction")?;
+        CosignPolicy::self_funded(agent)
+            .verify(&tx)
+            .context("Refusing to sign the self-funded launch transaction")?;
+
+        if mint_keypair.is_some() && prepared.mint_address != requested_mint {
+            anyhow::bail!("Backend prepared the launch for a different mint than requested");
+        }
+        let Some(mint) = mint_keypair.as_ref().filter(|_| rebuild) else {
+            if let Some(price) = compute_unit_price(&tx.message).filter(|price| *price > 0) {
+                anyhow::bail!(
+                    "Backend added a priority fee of {} micro-lamports per compute unit that was not requested",
+                    price
+                );
+            }
+            return Ok((prepared, tx, mint_keypair));
+        };
+        let tx = self
+            .rebuild_self_funded(&tx, mint.pubkey(), dev_buy)
+            .await?;
+        Ok((prepared, tx, mint_keypair))
+    }
+
+    /// Rebuild a prepared launch with the requested compute budget and, if
+    /// asked, the fee payer buying `lamports` of the new token.
+    async fn rebuild_self_funded(
+        &self,
+        tx: &VersionedTransaction,
+        mint: [u8; 32],
+        dev_buy: Option<(u64, u64)>,
+    ) -> Result<VersionedTransaction> {
+        let connection = SolanaConnection::new(&self.config.rpc_url);
+        let tables = connection.get_lookup_tables(&tx.message).await?;
+        let mut instructions = tx
+            .message
+            .decompile(&tx.message.resolve_addresses(&tables)?)?;
+        let buyer = tx.message.static_account_keys()[0];
+
+        if let Some((lamports, slippage_bps)) = dev_buy {
+            let creator = instructions
+                .iter()
+                .find_map(pumpfun::create_instruction_creator)
+                .context("Prepared launch has no pump.fun create instruction")?;
+            let global = connection
+                .get_pump_fun_global()
+                .await
+                .context("Failed to fetch pump.fun fee configuration")?;
+            let quote = BondingCurve::initial(&global, creator)
+                .quote_buy(lamports, global.fee_basis_points)
+                .context("Invalid dev buy")?;
+            info!(
+                "Dev buy of {} tokens for up to {} lamports",
+                quote.tokens_out,
+                quote.max_sol_cost(slippage_bps)
+            );
+
+            let accounts = TradeAccounts {
+                mint,
+                creator,
+                user: buyer,
+                fee_recipient: global.fee_recipient,
+            };
+            instructions.push(create_associated_token_account_idempotent(
+                &buyer, &buyer, &mint,
+            ));
+            instructions.push(pumpfun::buy_instruction(
+                &accounts,
+                quote.tokens_out,
+                quote.max_sol_cost(slippage_bps),
+            ));
+        }
+
+        let price = self
+            .resolve_priority_fee(&writable_accounts(&instructions))
+            .await?;
+        let mut builder = TransactionBuilder::new();
+        for instruction in with_compute_budget(instructions, self.compute_unit_limit, price) {
+            builder.add_instruction(instruction);
+        }
+        // Keep the prepared lookups so the rebuilt transaction stays within the packet limit.
+        for table in tables {
+            builder.add_lookup_table(table);
+        }
+        builder
+            .set_fee_payer(buyer)
+            .set_recent_blockhash(bs58::encode(tx.message.recent_blockhash()).into_string());
+        builder.build_versioned_transaction()
+    }
+
+    /// Add the local signatures to a prepared launch and submit it.
+    async fn sign_and_submit(
+        &self,
+        prepared: PreparedTransaction,
+        mut tx: VersionedTransaction,
+        signers: &[SecretKeypair],
+    ) -> Result<LaunchOutcome> {
+        let mint_address = prepared
+            .mint_address
+            .context("Backend did not return a mint address for the launch")?;
+        for keypair in signers {
+            tx.sign(keypair)?;
+        }
+
+        let confirmation = SolanaConnection::new(&self.config.rpc_url)
+            .send_and_confirm(&tx, self.config.commitment)
+            .await?;
+
+        Ok(LaunchOutcome {
+            response: LaunchResponse {
+                pump_fun_url: format!("https://pump.fun/coin/{}", mint_address),
+                mint_address,
+                tx_signature: confirmation.signature.clone(),
+            },
+            confirmation,
+        })
+    }
+
+    /// The wallet that pays for a self-funded launch by `agent_id`.
+    ///
+    /// When the wallet is kept on another machine for offline signing, the
+    /// `wallet_address` setting names it instead.
+    fn self_funded_payer(&self, agent_id: &str) -> Result<[u8; 32]> {
+        let wallet = WalletManager::select(&self.config, Some(agent_id))?;
+        let address = if wallet.exists() {
+            wallet.get_public_key()?
+        } else {
+            self.config.wallet_address.clone().context(
+                "No local wallet pays for the launch; set wallet_address in config to the wallet that will sign it",
+            )?
+        };
+        keypair::decode_pubkey(&address).context("Invalid wallet address")
+    }
+
+    /// The platform fee payer a gasless transaction must use.
+    ///
+    /// A key pinned in the config takes precedence over the one the backend
+    /// advertises alongside the transaction.
+    fn expected_fee_payer(&self, prepared: &PreparedTransaction) -> Result<[u8; 32]> {
+        let address = self
+            .config
+            .gasless_fee_payer
+            .as_deref()
+            .or(prepared.fee_payer.as_deref())
+            .context(
+                "Backend did not identify the gasless fee payer; set gasless_fee_payer in config",
+            )?;
+        keypair::decode_pubkey(address).context("Invalid gasless fee payer address")
+    }
+
+    /// Turn the configured priority fee policy into a price in micro-lamports
+    /// per compute unit, estimating from fees recently paid to write `accounts`.
+    async fn resolve_priority_fee(&self, accounts: &[String]) -> Result<Option<u64>> {
+        let micro_lamports = match self.priority_fee {
+            None => return Ok(None),
+            Some(PriorityFee::MicroLamports(price)) => price,
+            Some(PriorityFee::Auto) => SolanaConnection::new(&self.config.rpc_url)
+                .estimate_priority_fee(accounts)
+                .await
+                .context("Failed to estimate priority fee")?,
+        };
+        info!(
+            "Using priority fee of {} micro-lamports per compute unit",
+            micro_lamports
+        );
+        Ok(Some(micro_lamports))
+    }
+}
+
+/// Replace the compute budget of `instructions` with the requested limit and price.
+///
+/// A limit that was not requested is left as the backend prepared it, but an
+/// unrequested priority fee is dropped: the agent only pays one it asked for.
+fn with_compute_budget(
+    instructions: Vec<Instruction>,
+    limit: Option<u32>,
+    price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut budget: Vec<Instruction> = limit
+        .map(set_compute_unit_limit)
+        .into_iter()
+        .chain(price.map(set_compute_unit_price))
+        .collect();
+    budget.extend(instructions.into_iter().filter(|ix| {
+        !(is_compute_budget(ix, SET_COMPUTE_UNIT_PRICE)
+            || limit.is_some() && is_compute_budget(ix, SET_COMPUTE_UNIT_LIMIT))
+    }));
+    budget
+}
+
+fn is_compute_budget(ix: &Instruction, tag: u8) -> bool {
+    ix.program_id == programs::program_id(programs::COMPUTE_BUDGET_PROGRAM_ID)
+        && ix.data.first() == Some(&tag)
+}
+
+/// Price set by a `SetComputeUnitPrice` instruction in `message`, if any.
+fn compute_unit_price(message: &VersionedMessage) -> Option<u64> {
+    let program = programs::program_id(programs::COMPUTE_BUDGET_PROGRAM_ID);
+    message.instructions().iter().find_map(|ix| {
+        let key = message
+            .static_account_keys()
+            .get(ix.program_id_index as usize)?;
+        match ix.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_PRICE, price)) if *key == program => {
+                price.try_into().ok().map(u64::from_le_bytes)
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Accounts `instructions` write to, the ones whose recent fees predict this launch's.
+fn writable_accounts(instructions: &[Instruction]) -> Vec<String> {
+    let mut accounts: Vec<String> = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_writable)
+        .map(|meta| bs58::encode(meta.pubkey).into_string())
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+    accounts
+}
+
+/// Most the agent may pay for the dev buy in a launch request, slippage included.
+fn dev_buy_limit(request: &LaunchRequest) -> Option<u64> {
+    let lamports = request.dev_buy_lamports?;
+    let slippage_bps = request.dev_buy_slippage_bps.unwrap_or(0);
+    Some((lamports as u128 * (10_000 + slippage_bps) as u128 / 10_000) as u64)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solana::transaction::{AccountMeta, LoadedAddresses};
 
     #[test]
     fn test_engine_creation() {
@@ -93,6 +615,103 @@ mod tests {
         let config = Settings::default();
         let engine = LaunchEngine::new(api, config);
         assert!(engine.config.agent_id.is_none());
+        assert!(engine.priority_fee.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fixed_priority_fee_resolves_without_rpc() {
+        let api = ApiClient::new("https://api.frogpump.fun/v1");
+        let engine = LaunchEngine::new(api, Settings::default())
+            .with_priority_fee(Some(PriorityFee::MicroLamports(25_000)));
+        assert_eq!(
+            engine.resolve_priority_fee(&[]).await.unwrap(),
+            Some(25_000)
+        );
+    }
+
+    #[test]
+    fn test_compute_budget_replaced_locally() {
+        let payer = [1; 32];
+        let transfer = Instruction {
+            program_id: programs::program_id(programs::SYSTEM_PROGRAM_ID),
+            accounts: vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new([2; 32], false),
+            ],
+            data: vec![2, 0, 0, 0],
+        };
+        let prepared = vec![
+            set_compute_unit_limit(100_000),
+            set_compute_unit_price(999_999),
+            transfer.clone(),
+        ];
+
+        // Only the requested limit and price remain, ahead of the launch itself.
+        let rebuilt = with_compute_budget(prepared.clone(), Some(250_000), Some(5_000));
+        assert_eq!(
+            rebuilt,
+            vec![
+                set_compute_unit_limit(250_000),
+                set_compute_unit_price(5_000),
+                transfer.clone(),
+            ]
+        );
+        // Without a request the backend's limit stays but its price is dropped.
+        let rebuilt = with_compute_budget(prepared, None, None);
+        assert_eq!(
+            rebuilt,
+            vec![set_compute_unit_limit(100_000), transfer.clone()]
+        );
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(set_compute_unit_price(7_500))
+            .add_instruction(transfer)
+            .set_fee_payer(payer)
+            .set_recent_blockhash("LbUiWL3xVV8hTFYBVdbTNrpDo41NKS6o3LHHuDzjfcY".to_string());
+        let tx = builder.build_versioned_transaction().unwrap();
+        assert_eq!(compute_unit_price(&tx.message), Some(7_500));
+        assert_eq!(
+            writable_accounts(&tx.message.decompile(&LoadedAddresses::default()).unwrap()),
+            vec![
+                bs58::encode([1; 32]).into_string(),
+                bs58::encode([2; 32]).into_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dev_buy_carried_into_request() {
+        let api = ApiClient::new("https://api.frogpump.fun/v1");
+        let engine =
+            LaunchEngine::new(api, Settings::default()).with_dev_buy(Some(500_000_000), 250);
+        let request = engine
+            .build_request(
+                "Frog".into(),
+                "FROG".into(),
+                None,
+                None,
+                Some("agent_1".into()),
+                true,
+            )
+            .unwrap();
+        assert_eq!(request.dev_buy_lamports, Some(500_000_000));
+        assert_eq!(request.dev_buy_slippage_bps, Some(250));
+        assert_eq!(dev_buy_limit(&request), Some(512_500_000));
+
+        let engine = engine.with_dev_buy(None, 250);
+        let request = engine
+            .build_request(
+                "Frog".into(),
+                "FROG".into(),
+                None,
+                None,
+                Some("agent_1".into()),
+                true,
+            )
+            .unwrap();
+        assert_eq!(request.dev_buy_slippage_bps, None);
+        assert_eq!(dev_buy_limit(&request), None);
     }
 }
 
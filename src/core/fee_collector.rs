@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use log::info;
 
 use crate::api::client::ApiClient;
-use crate::api::types::{ClaimRequest, ClaimResponse};
+use crate::api::types::{ClaimRequest, ClaimResponse, PreparedTransaction};
 use crate::models::Earning;
 
 /// Manages fee collection and claiming for agent earnings on launched tokens.
@@ -62,6 +62,26 @@ impl FeeCollector {
             .context(format!("Failed to claim earnings for token {}", token_id))
     }
 
+    /// Have the backend assemble a claim transaction without submitting it.
+    ///
+    /// Claims earnings for `token_id` when given, otherwise all earnings.
+    pub async fn prepare_claim(
+        &self,
+        agent_id: &str,
+        token_id: Option<&str>,
+    ) -> Result<PreparedTransaction> {
+        let request = ClaimRequest {
+            agent_id: agent_id.to_string(),
+            token_id: token_id.map(str::to_string),
+            claim_all: token_id.is_none(),
+        };
+
+        self.api
+            .prepare_claim(request)
+            .await
+            .context("Failed to prepare claim transaction")
+    }
+
     /// Calculate the total unclaimed amount from a slice of earnings.
     pub fn total_unclaimed(earnings: &[Earning]) -> f64 {
         earnings
@@ -0,0 +1,169 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use anyhow::{bail, Result};
+use ed25519_dalek::SigningKey;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::solana::keypair::SecretKeypair;
+
+/// Characters of a base58 Solana address.
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Attempts a worker makes between updates of the shared counter.
+const BATCH: u64 = 256;
+
+/// The prefix and suffix a vanity address must have.
+///
+/// Matching ignores case unless asked otherwise, which makes a match far
+/// cheaper to find: each letter has two chances instead of one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanityPattern {
+    prefix: String,
+    suffix: String,
+    case_sensitive: bool,
+}
+
+impl VanityPattern {
+    pub fn new(prefix: Option<&str>, suffix: Option<&str>, case_sensitive: bool) -> Result<Self> {
+        let prefix = prefix.unwrap_or_default();
+        let suffix = suffix.unwrap_or_default();
+        if prefix.is_empty() && suffix.is_empty() {
+            bail!("A vanity address needs a prefix, a suffix or both");
+        }
+        for c in prefix.chars().chain(suffix.chars()) {
+            if alphabet_matches(c, case_sensitive) == 0 {
+                bail!(
+                    "'{}' can never appear in a base58 address (0, O, I and l are excluded)",
+                    c
+                );
+            }
+        }
+        let normalize = |s: &str| {
+            if case_sensitive {
+                s.to_string()
+            } else {
+                s.to_ascii_lowercase()
+            }
+        };
+        Ok(Self {
+            prefix: normalize(prefix),
+            suffix: normalize(suffix),
+            case_sensitive,
+        })
+    }
+
+    /// Whether a base58 address has the pattern's prefix and suffix.
+    pub fn matches(&self, address: &str) -> bool {
+        if self.case_sensitive {
+            address.starts_with(&self.prefix) && address.ends_with(&self.suffix)
+        } else {
+            let len = address.len();
+            len >= self.prefix.len() + self.suffix.len()
+                && address[..self.prefix.len()].eq_ignore_ascii_case(&self.prefix)
+                && address[len - self.suffix.len()..].eq_ignore_ascii_case(&self.suffix)
+        }
+    }
+
+    /// Rough number of keys to try before finding a match, treating every
+    /// address character as uniformly distributed.
+    pub fn expected_attempts(&self) -> f64 {
+        self.prefix
+            .chars()
+            .chain(self.suffix.chars())
+            .map(|c| 58.0 / alphabet_matches(c, self.case_sensitive) as f64)
+            .product()
+    }
+}
+
+/// How many base58 characters `c` matches.
+fn alphabet_matches(c: char, case_sensitive: bool) -> usize {
+    BASE58_ALPHABET
+        .chars()
+        .filter(|&a| {
+            if case_sensitive {
+                a == c
+            } else {
+                a.eq_ignore_ascii_case(&c)
+            }
+        })
+        .count()
+}
+
+/// Generate keypairs on `threads` threads until one's address matches `pattern`.
+///
+/// `attempts` counts keys tried so far, for progress reporting. Setting `stop`
+/// makes every worker give up; `None` is returned if that happens first.
+pub fn grind(
+    pattern: &VanityPattern,
+    threads: usize,
+    attempts: &AtomicU64,
+    stop: &AtomicBool,
+) -> Option<SecretKeypair> {
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| scope.spawn(|| grind_worker(pattern, attempts, stop)))
+            .collect();
+        workers
+            .into_iter()
+            .filter_map(|worker| worker.join().ok().flatten())
+            .next()
+    })
+}
+
+fn grind_worker(
+    pattern: &VanityPattern,
+    attempts: &AtomicU64,
+    stop: &AtomicBool,
+) -> Option<SecretKeypair> {
+    let mut rng = StdRng::from_entropy();
+    while !stop.load(Ordering::Relaxed) {
+        for _ in 0..BATCH {
+            let signing_key = SigningKey::generate(&mut rng);
+            let address = bs58::encode(signing_key.verifying_key().as_bytes()).into_string();
+            if pattern.matches(&address) {
+                stop.store(true, Ordering::Relaxed);
+                return Some(SecretKeypair::from_secret(signing_key.as_bytes()));
+            }
+        }
+        attempts.fetch_add(BATCH, Ordering::Relaxed);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matching() {
+        let pattern = VanityPattern::new(None, Some("frog"), false).unwrap();
+        assert!(pattern.matches("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtfRoG"));
+        assert!(!pattern.matches("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"));
+        // f, r and g match both cases; o only matches 'o', as 'O' is not base58.
+        assert_eq!(pattern.expected_attempts(), 29.0 * 29.0 * 58.0 * 29.0);
+
+        let exact = VanityPattern::new(Some("Ab"), Some("Z"), true).unwrap();
+        assert!(exact.matches("AbcZ"));
+        assert!(!exact.matches("abcZ"));
+        assert!(!VanityPattern::new(Some("ab"), Some("ba"), false)
+            .unwrap()
+            .matches("aba"));
+
+        assert!(VanityPattern::new(None, None, false).is_err());
+        assert!(VanityPattern::new(Some("0x"), None, false).is_err());
+        assert!(VanityPattern::new(None, Some("Oil"), true).is_err());
+    }
+
+    #[test]
+    fn test_grind_finds_match() {
+        let pattern = VanityPattern::new(Some("a"), None, false).unwrap();
+        let attempts = AtomicU64::new(0);
+        let stop = AtomicBool::new(false);
+        let kp = grind(&pattern, 2, &attempts, &stop).unwrap();
+        assert!(pattern.matches(&kp.address()));
+
+        let stop = AtomicBool::new(true);
+        assert!(grind(&pattern, 2, &attempts, &stop).is_none());
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+
+use crate::solana::accounts::TokenAccount;
+use crate::solana::connection::SolanaConnection;
+use crate::solana::keypair;
+use crate::solana::pumpfun;
+
+/// Upper bounds of the histogram buckets, in basis points of circulating supply.
+/// Holders at or above the last bound fall into a final open-ended bucket.
+pub const HISTOGRAM_BOUNDS_BPS: [u64; 4] = [1, 10, 100, 500];
+
+/// A wallet and the tokens it holds across all of its token accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holder {
+    pub owner: String,
+    pub amount: u64,
+}
+
+/// How a token's circulating supply is spread across holders.
+#[derive(Debug, Clone)]
+pub struct HolderDistribution {
+    /// Holders with a non-zero balance, largest first.
+    pub holders: Vec<Holder>,
+    /// Tokens held outside the bonding curve.
+    pub circulating: u64,
+}
+
+impl HolderDistribution {
+    /// Aggregate token accounts by owner, leaving out empty balances and the given owners.
+    pub fn from_accounts(accounts: &[TokenAccount], excluded: &[[u8; 32]]) -> Self {
+        let mut balances: HashMap<[u8; 32], u64> = HashMap::new();
+        for account in accounts {
+            if account.amount == 0 || excluded.contains(&account.owner) {
+                continue;
+            }
+            *balances.entry(account.owner).or_default() += account.amount;
+        }
+
+        let mut holders: Vec<Holder> = balances
+            .into_iter()
+            .map(|(owner, amount)| Holder {
+                owner: bs58::encode(owner).into_string(),
+                amount,
+            })
+            .collect();
+        holders.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.owner.cmp(&b.owner)));
+        let circulating = holders.iter().map(|holder| holder.amount).sum();
+
+        Self {
+            holders,
+            circulating,
+        }
+    }
+
+    /// Share of circulating supply held by `amount` tokens, in basis points.
+    pub fn share_bps(&self, amount: u64) -> u64 {
+        if self.circulating == 0 {
+            return 0;
+        }
+        (amount as u128 * 10_000 / self.circulating as u128) as u64
+    }
+
+    /// Share of circulating supply held by the ten largest holders, in basis points.
+    pub fn top_ten_bps(&self) -> u64 {
+        self.share_bps(
+            self.holders
+                .iter()
+                .take(10)
+                .map(|holder| holder.amount)
+                .sum(),
+        )
+    }
+
+    /// Gini coefficient of holder balances: 0 when everyone holds the same amount,
+    /// approaching 1 when a single wallet holds everything.
+    pub fn gini(&self) -> f64 {
+        let n = self.holders.len();
+        if n == 0 || self.circulating == 0 {
+            return 0.0;
+        }
+        // Holders are sorted largest first, so rank them from the back.
+        let weighted: f64 = self
+            .holders
+            .iter()
+            .enumerate()
+            .map(|(i, holder)| (n - i) as f64 * holder.amount as f64)
+            .sum();
+        let n = n as f64;
+        2.0 * weighted / (n * self.circulating as f64) - (n + 1.0) / n
+    }
+
+    /// Number of holders in each bucket of `HISTOGRAM_BOUNDS_BPS`, plus the open-ended last bucket.
+    pub fn histogram(&self) -> [usize; HISTOGRAM_BOUNDS_BPS.len() + 1] {
+        let mut buckets = [0; HISTOGRAM_BOUNDS_BPS.len() + 1];
+        for holder in &self.holders {
+            let share = self.share_bps(holder.amount);
+            let bucket = HISTOGRAM_BOUNDS_BPS
+                .iter()
+                .position(|&bound| share < bound)
+                .unwrap_or(HISTOGRAM_BOUNDS_BPS.len());
+            buckets[bucket] += 1;
+        }
+        buckets
+    }
+
+    /// Render the holder list as CSV with owner, raw amount and share of circulating supply.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("owner,amount,share_percent\n");
+        for holder in &self.holders {
+            let share = self.share_bps(holder.amount);
+            let _ = writeln!(
+                csv,
+                "{},{},{}.{:02}",
+                holder.owner,
+                holder.amount,
+                share / 100,
+                share % 100
+            );
+        }
+        csv
+    }
+}
+
+/// Fetch every token account of `mint` and aggregate it by owner.
+///
+/// The pump.fun bonding curve's balance is unsold supply, not a holder, so it is left out.
+pub async fn fetch_distribution(
+    connection: &SolanaConnection,
+    mint: &str,
+) -> Result<HolderDistribution> {
+    let accounts: Vec<TokenAccount> = connection
+        .get_token_accounts_by_mint(mint)
+        .await
+        .context("Failed to fetch token accounts")?
+        .into_iter()
+        .map(|(_, account)| account)
+        .collect();
+    let curve = pumpfun::bonding_curve_address(&keypair::decode_pubkey(mint)?);
+    Ok(HolderDistribution::from_accounts(&accounts, &[curve]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::accounts::TokenAccountState;
+
+    fn account(owner: u8, amount: u64) -> TokenAccount {
+        TokenAccount {
+            mint: [0x11; 32],
+            owner: [owner; 32],
+            amount,
+            delegate: None,
+            state: TokenAccountState::Initialized,
+            is_native: None,
+            delegated_amount: 0,
+            close_authority: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregates_by_owner() {
+        let accounts = [
+            account(1, 300),
+            account(2, 500),
+            account(1, 400),
+            account(3, 0),
+            account(9, 10_000),
+        ];
+        let distribution = HolderDistribution::from_accounts(&accounts, &[[9; 32]]);
+        assert_eq!(distribution.holders.len(), 2);
+        assert_eq!(
+            distribution.holders[0].owner,
+            bs58::encode([1; 32]).into_string()
+        );
+        assert_eq!(distribution.holders[0].amount, 700);
+        assert_eq!(distribution.circulating, 1_200);
+        assert_eq!(distribution.top_ten_bps(), 10_000);
+        assert!(distribution.to_csv().ends_with(&format!(
+            "{},500,41.66\n",
+            bs58::encode([2; 32]).into_string()
+        )));
+    }
+
+    #[test]
+    fn test_gini() {
+        let equal = HolderDistribution::from_accounts(&[account(1, 50), account(2, 50)], &[]);
+        assert!(equal.gini().abs() < 1e-9);
+
+        let skewed = HolderDistribution::from_accounts(
+            &[account(1, 1), account(2, 1), account(3, 1), account(4, 97)],
+            &[],
+        );
+        // Mean absolute difference over twice the mean: 0.72.
+        assert!((skewed.gini() - 0.72).abs() < 1e-9);
+
+        assert_eq!(HolderDistribution::from_accounts(&[], &[]).gini(), 0.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut accounts = vec![account(1, 9_000), account(2, 600), account(3, 300)];
+        accounts.extend((10..110).map(|owner| account(owner, 1)));
+        let distribution = HolderDistribution::from_accounts(&accounts, &[]);
+        // 100 holders at 0.01%, one at 3% and two above 5%.
+        assert_eq!(distribution.histogram(), [0, 100, 0, 1, 2]);
+    }
+}
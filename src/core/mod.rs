@@ -2,3 +2,8 @@ pub mod engine;
 pub mod token;
 pub mod fee_collector;
 pub mod validator;
+pub mod cosign;
+pub mod audit;
+pub mod holders;
+pub mod grind;
+pub mod offline;
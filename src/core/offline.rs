@@ -0,0 +1,246 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::network::Network;
+use crate::solana::accounts::NonceAccount;
+use crate::solana::connection::SolanaConnection;
+use crate::solana::keypair;
+use crate::solana::transaction::{
+    advance_nonce_account, AddressLookupTable, TransactionBuilder, VersionedTransaction,
+};
+
+/// Version of the file format written by `--export-unsigned`.
+pub const OFFLINE_FORMAT_VERSION: u32 = 1;
+
+/// What an exported transaction does once submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineKind {
+    Claim,
+    Launch,
+}
+
+/// A transaction carried between an online machine and an air-gapped signer.
+///
+/// The file holds the wire-format transaction together with enough context
+/// for the offline machine to show what it is approving, and for `submit` to
+/// check it is sent to the cluster it was built for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineTransaction {
+    pub version: u32,
+    pub kind: OfflineKind,
+    /// One-line description shown before signing.
+    pub summary: String,
+    pub network: Network,
+    pub agent_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint_address: Option<String>,
+    /// Durable nonce account standing in for the blockhash, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_account: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Base64-encoded wire-format transaction, with the signatures collected so far.
+    pub transaction: String,
+}
+
+impl OfflineTransaction {
+    /// Wrap `tx` for export, recording its nonce account if it uses one.
+    pub fn new(
+        kind: OfflineKind,
+        summary: String,
+        network: Network,
+        agent_id: String,
+        tx: &VersionedTransaction,
+    ) -> Self {
+        Self {
+            version: OFFLINE_FORMAT_VERSION,
+            kind,
+            summary,
+            network,
+            agent_id,
+            mint_address: None,
+            nonce_account: tx
+                .message
+                .durable_nonce_account()
+                .map(|key| bs58::encode(key).into_string()),
+            created_at: Utc::now(),
+            transaction: tx.to_base64(),
+        }
+    }
+
+    /// Read an exported transaction from `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+        let offline: Self = serde_json::from_str(&content)
+            .context(format!("Failed to parse {}", path.display()))?;
+        if offline.version != OFFLINE_FORMAT_VERSION {
+            bail!(
+                "{} uses format version {}, but this build reads version {}",
+                path.display(),
+                offline.version,
+                OFFLINE_FORMAT_VERSION
+            );
+        }
+        Ok(offline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize offline transaction")?;
+        std::fs::write(path, content).context(format!("Failed to write {}", path.display()))
+    }
+
+    /// Decode the carried transaction.
+    pub fn transaction(&self) -> Result<VersionedTransaction> {
+        VersionedTransaction::from_base64(&self.transaction)
+            .context("File does not contain a valid transaction")
+    }
+
+    /// Replace the carried transaction, e.g. after adding a signature.
+    pub fn set_transaction(&mut self, tx: &VersionedTransaction) {
+        self.transaction = tx.to_base64();
+    }
+}
+
+/// Signers of `tx` whose signature slot is still empty.
+pub fn missing_signers(tx: &VersionedTransaction) -> Vec<String> {
+    tx.signatures
+        .iter()
+        .zip(tx.message.signer_keys())
+        .filter(|(signature, _)| *signature == &[0u8; 64])
+        .map(|(_, key)| bs58::encode(key).into_string())
+        .collect()
+}
+
+/// Rebuild an unsigned transaction to use the durable nonce in `nonce_account`.
+///
+/// A blockhash expires about a minute after it is fetched, too soon to carry a
+/// transaction to an offline machine and back. The rebuilt transaction starts
+/// by advancing the nonce and uses the stored nonce as its blockhash, so it
+/// stays valid until the nonce is advanced. The nonce authority must sign it.
+pub async fn bind_durable_nonce(
+    connection: &SolanaConnection,
+    tx: &VersionedTransaction,
+    nonce_account: &str,
+) -> Result<VersionedTransaction> {
+    let address = keypair::decode_pubkey(nonce_account).context("Invalid nonce account address")?;
+    let nonce = connection
+        .get_nonce_account(nonce_account)
+        .await?
+        .with_context(|| format!("Nonce account {} does not exist", nonce_account))?;
+    let tables = connection.get_lookup_tables(&tx.message).await?;
+    with_durable_nonce(tx, tables, address, &nonce)
+}
+
+fn with_durable_nonce(
+    tx: &VersionedTransaction,
+    tables: Vec<AddressLookupTable>,
+    address: [u8; 32],
+    nonce: &NonceAccount,
+) -> Result<VersionedTransaction> {
+    if tx.signatures.iter().any(|s| s != &[0u8; 64]) {
+        bail!("Transaction is already partly signed; a durable nonce would void its signatures");
+    }
+    if tx.message.durable_nonce_account().is_some() {
+        bail!("Transaction already uses a durable nonce");
+    }
+
+    let mut builder = TransactionBuilder::new();
+    builder.add_instruction(advance_nonce_account(&address, &nonce.authority));
+    for instruction in tx
+        .message
+        .decompile(&tx.message.resolve_addresses(&tables)?)?
+    {
+        builder.add_instruction(instruction);
+    }
+    // Keep the original lookups so the rebuilt transaction stays within the packet limit.
+    for table in tables {
+        builder.add_lookup_table(table);
+    }
+    builder
+        .set_fee_payer(tx.message.static_account_keys()[0])
+        .set_recent_blockhash(bs58::encode(nonce.nonce).into_string());
+    builder.build_versioned_transaction()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::keypair::SecretKeypair;
+    use crate::solana::programs::{self, program_id};
+    use crate::solana::transaction::{AccountMeta, Instruction};
+
+    const BLOCKHASH: &str = "LbUiWL3xVV8hTFYBVdbTNrpDo41NKS6o3LHHuDzjfcY";
+
+    fn unsigned_transfer(payer: [u8; 32]) -> VersionedTransaction {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(Instruction {
+                program_id: program_id(programs::SYSTEM_PROGRAM_ID),
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new([9; 32], false),
+                ],
+                data,
+            })
+            .set_fee_payer(payer)
+            .set_recent_blockhash(BLOCKHASH.to_string());
+        builder.build_versioned_transaction().unwrap()
+    }
+
+    #[test]
+    fn test_bind_durable_nonce() {
+        let agent = SecretKeypair::from_secret(&[1; 32]);
+        let nonce = NonceAccount {
+            authority: agent.pubkey(),
+            nonce: [7; 32],
+            lamports_per_signature: 5000,
+        };
+        let tx = unsigned_transfer(agent.pubkey());
+
+        let mut bound = with_durable_nonce(&tx, Vec::new(), [5; 32], &nonce).unwrap();
+        assert_eq!(bound.message.durable_nonce_account(), Some([5; 32]));
+        assert_eq!(bound.message.recent_blockhash(), &[7; 32]);
+        assert_eq!(bound.message.static_account_keys()[0], agent.pubkey());
+        assert_eq!(bound.message.instructions().len(), 2);
+        assert_eq!(missing_signers(&bound), vec![agent.address()]);
+
+        // Signed transactions cannot be rebound.
+        bound.sign(&agent).unwrap();
+        assert!(missing_signers(&bound).is_empty());
+        assert!(with_durable_nonce(&bound, Vec::new(), [5; 32], &nonce).is_err());
+    }
+
+    #[test]
+    fn test_offline_file_roundtrip() {
+        let agent = SecretKeypair::from_secret(&[2; 32]);
+        let tx = unsigned_transfer(agent.pubkey());
+        let mut offline = OfflineTransaction::new(
+            OfflineKind::Claim,
+            "Claim all earnings".to_string(),
+            Network::Devnet,
+            "agent-1".to_string(),
+            &tx,
+        );
+        assert_eq!(offline.nonce_account, None);
+
+        let path =
+            std::env::temp_dir().join(format!("frogpump-offline-{}.json", std::process::id()));
+        offline.save(&path).unwrap();
+        let loaded = OfflineTransaction::load(&path).unwrap();
+        assert_eq!(loaded.kind, OfflineKind::Claim);
+        assert_eq!(loaded.network, Network::Devnet);
+        assert_eq!(loaded.transaction().unwrap(), tx);
+
+        offline.version = OFFLINE_FORMAT_VERSION + 1;
+        offline.save(&path).unwrap();
+        assert!(OfflineTransaction::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
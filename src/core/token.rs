@@ -12,6 +12,8 @@ pub struct TokenBuilder {
     image_url: Option<String>,
     agent_id: Option<String>,
     launch_type: LaunchType,
+    dev_buy_lamports: Option<u64>,
+    dev_buy_slippage_bps: u64,
 }
 
 impl TokenBuilder {
@@ -24,6 +26,8 @@ impl TokenBuilder {
             image_url: None,
             agent_id: None,
             launch_type: LaunchType::Gasless,
+            dev_buy_lamports: None,
+            dev_buy_slippage_bps: 0,
         }
     }
 
@@ -47,6 +51,14 @@ impl TokenBuilder {
         self
     }
 
+    /// Buy the new token for `lamports` in the launch transaction, tolerating
+    /// `slippage_bps` of price movement.
+    pub fn dev_buy(mut self, lamports: Option<u64>, slippage_bps: u64) -> Self {
+        self.dev_buy_lamports = lamports;
+        self.dev_buy_slippage_bps = slippage_bps;
+        self
+    }
+
     /// Validate all fields and build the final LaunchRequest.
     pub fn build(self) -> Result<LaunchRequest> {
         validator::validate_token_name(&self.name)?;
@@ -61,6 +73,15 @@ impl TokenBuilder {
 
         let self_funded = matches!(self.launch_type, LaunchType::SelfFunded);
 
+        let dev_buy_slippage_bps = match self.dev_buy_lamports {
+            Some(0) => bail!("Dev buy must be greater than zero"),
+            Some(_) => {
+                validator::validate_slippage_bps(self.dev_buy_slippage_bps)?;
+                Some(self.dev_buy_slippage_bps)
+            }
+            None => None,
+        };
+
         Ok(LaunchRequest {
             name: self.name,
             symbol: self.symbol,
@@ -68,6 +89,11 @@ impl TokenBuilder {
             image_url: self.image_url,
             agent_id,
             self_funded,
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            dev_buy_lamports: self.dev_buy_lamports,
+            dev_buy_slippage_bps,
+            mint_address: None,
         })
     }
 }
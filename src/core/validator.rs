@@ -1,5 +1,7 @@
 use anyhow::{bail, Result};
 
+use crate::solana::transaction::MAX_COMPUTE_UNIT_LIMIT;
+
 /// Validate a token name: 1-32 characters, alphanumeric and spaces only.
 pub fn validate_token_name(name: &str) -> Result<()> {
     if name.is_empty() || name.len() > 32 {
@@ -49,6 +51,28 @@ pub fn validate_solana_address(address: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a compute unit limit: between 1 and the per-transaction maximum.
+pub fn validate_compute_unit_limit(units: u32) -> Result<()> {
+    if units == 0 || units > MAX_COMPUTE_UNIT_LIMIT {
+        bail!(
+            "Compute unit limit must be between 1 and {}, got {}",
+            MAX_COMPUTE_UNIT_LIMIT,
+            units
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate a slippage tolerance in basis points: at most 100%.
+pub fn validate_slippage_bps(bps: u64) -> Result<()> {
+    if bps > 10_000 {
+        bail!("Slippage must be at most 10000 bps (100%), got {}", bps);
+    }
+
+    Ok(())
+}
+
 /// Validate an agent identifier: non-empty, 1-64 characters, alphanumeric with hyphens/underscores.
 pub fn validate_agent_id(id: &str) -> Result<()> {
     if id.is_empty() || id.len() > 64 {
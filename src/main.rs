@@ -2,28 +2,63 @@ use clap::Parser;
 use env_logger::Env;
 use log::error;
 
-use frog_pump::cli::app::{Cli, Commands, ConfigSubcommand, WalletSubcommand};
-use frog_pump::commands;
-use frog_pump::config::settings::Settings;
+use frogpump::cli::app::{
+    Cli, Commands, ConfigSubcommand, DeriveSubcommand, TxSubcommand, WalletSubcommand,
+};
+use frogpump::commands;
+use frogpump::config::settings::Settings;
+use frogpump::solana::keypair;
 
 #[tokio::main]
 async fn main() {
     env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
 
     let cli = Cli::parse();
-    let config = Settings::load().unwrap_or_default();
+    let mut config = Settings::load().unwrap_or_default();
+    config.wallet_override = cli.wallet;
+    keypair::set_memory_locking(config.lock_memory);
 
     let result = match cli.command {
         Commands::Launch(args) => commands::launch::execute(args, &config).await,
         Commands::Status { agent_id } => commands::status::execute(agent_id, &config).await,
         Commands::Claim(args) => commands::claim::execute(args, &config).await,
         Commands::Wallet(sub) => match sub.command {
-            WalletSubcommand::Set { address, signature } => {
-                commands::wallet::execute_set(address, signature, &config).await
+            WalletSubcommand::Set(args) => commands::wallet::execute_set(args, &config).await,
+            WalletSubcommand::Challenge { address } => {
+                commands::wallet::execute_challenge(address, &config).await
             }
             WalletSubcommand::Show { agent_id } => {
                 commands::wallet::execute_show(agent_id, &config).await
             }
+            WalletSubcommand::New {
+                force,
+                mnemonic,
+                words,
+                passphrase,
+                encrypt,
+            } => {
+                if mnemonic {
+                    commands::wallet::execute_new_mnemonic(
+                        words, passphrase, force, encrypt, &config,
+                    )
+                } else {
+                    commands::wallet::execute_new(force, encrypt, &config)
+                }
+            }
+            WalletSubcommand::Recover(args) => commands::wallet::execute_recover(args, &config),
+            WalletSubcommand::Derive(args) => commands::wallet::execute_derive(args, &config),
+            WalletSubcommand::Import(args) => commands::wallet::execute_import(args, &config),
+            WalletSubcommand::Export { format, yes } => {
+                commands::wallet::execute_export(&format, yes, &config)
+            }
+            WalletSubcommand::Address => commands::wallet::execute_address(&config),
+            WalletSubcommand::Encrypt => commands::wallet::execute_encrypt(&config),
+            WalletSubcommand::Decrypt { yes } => commands::wallet::execute_decrypt(yes, &config),
+            WalletSubcommand::List => commands::wallet::execute_list(&config),
+            WalletSubcommand::Use { name, agent } => {
+                commands::wallet::execute_use(&name, agent, &config)
+            }
+            WalletSubcommand::Label { label } => commands::wallet::execute_label(label, &config),
         },
         Commands::Leaderboard(args) => commands::leaderboard::execute(args, &config).await,
         Commands::Config(sub) => match sub.command {
@@ -34,7 +69,29 @@ async fn main() {
         Commands::Tokens { agent_id, verbose } => {
             commands::tokens::execute(agent_id, verbose, &config).await
         }
+        Commands::Token { mint } => commands::token::execute(mint, &config).await,
+        Commands::Audit { mint, json } => commands::audit::execute(mint, json, &config).await,
+        Commands::Holders(args) => commands::holders::execute(args, &config).await,
+        Commands::Quote(args) => commands::quote::execute(args, &config).await,
+        Commands::Buy(args) => commands::trade::execute_buy(args, &config).await,
+        Commands::Sell(args) => commands::trade::execute_sell(args, &config).await,
         Commands::Stats => commands::stats::execute(&config).await,
+        Commands::Tx(sub) => match sub.command {
+            TxSubcommand::Decode { transaction } => commands::tx::execute_decode(&transaction),
+        },
+        Commands::Derive(sub) => match sub.command {
+            DeriveSubcommand::Pda { program, seeds } => {
+                commands::derive::execute_pda(&program, &seeds)
+            }
+            DeriveSubcommand::Ata { owner, mint } => commands::derive::execute_ata(&owner, &mint),
+            DeriveSubcommand::Metadata { mint } => commands::derive::execute_metadata(&mint),
+            DeriveSubcommand::BondingCurve { mint } => {
+                commands::derive::execute_bonding_curve(&mint)
+            }
+        },
+        Commands::Grind(args) => commands::grind::execute(args).await,
+        Commands::Sign(args) => commands::offline::execute_sign(args, &config),
+        Commands::Submit(args) => commands::offline::execute_submit(args, &config).await,
     };
 
     if let Err(e) = result {
@@ -0,0 +1,398 @@
+use anyhow::{bail, Result};
+
+use crate::solana::programs::{self, METADATA_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::solana::transaction::ByteReader;
+
+/// Size of an SPL Token mint account.
+pub const MINT_LEN: usize = 82;
+
+/// Size of an SPL Token account.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Size of a System program nonce account.
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+
+/// Leading key byte of a Metaplex `MetadataV1` account.
+const METADATA_KEY_V1: u8 = 4;
+
+/// An account as returned by `getAccountInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountInfo {
+    pub lamports: u64,
+    /// Program that owns the account.
+    pub owner: [u8; 32],
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+impl AccountInfo {
+    /// Whether the account is owned by the program at the given base58 address.
+    pub fn is_owned_by(&self, program: &str) -> bool {
+        self.owner == programs::program_id(program)
+    }
+}
+
+/// An SPL Token mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mint {
+    /// Key allowed to mint more supply; `None` once revoked.
+    pub mint_authority: Option<[u8; 32]>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    /// Key allowed to freeze holders' token accounts.
+    pub freeze_authority: Option<[u8; 32]>,
+}
+
+impl Mint {
+    /// Decode an SPL Token mint account.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        if data.len() != MINT_LEN {
+            bail!(
+                "Mint account must be {} bytes, got {}",
+                MINT_LEN,
+                data.len()
+            );
+        }
+        let mut reader = ByteReader::new(data);
+        Ok(Self {
+            mint_authority: read_coption_pubkey(&mut reader)?,
+            supply: reader.read_u64()?,
+            decimals: reader.read_u8()?,
+            is_initialized: reader.read_u8()? != 0,
+            freeze_authority: read_coption_pubkey(&mut reader)?,
+        })
+    }
+
+    /// Decode a mint from a fetched account, checking the SPL Token program owns it.
+    pub fn from_account(account: &AccountInfo) -> Result<Self> {
+        if !account.is_owned_by(TOKEN_PROGRAM_ID) {
+            bail!("Account is not owned by the SPL Token program");
+        }
+        Self::deserialize(&account.data)
+    }
+}
+
+/// State of an SPL token account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenAccountState {
+    Uninitialized,
+    Initialized,
+    Frozen,
+}
+
+/// An SPL token account holding a balance of one mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenAccount {
+    pub mint: [u8; 32],
+    pub owner: [u8; 32],
+    pub amount: u64,
+    pub delegate: Option<[u8; 32]>,
+    pub state: TokenAccountState,
+    /// Rent-exempt reserve of a wrapped SOL account.
+    pub is_native: Option<u64>,
+    pub delegated_amount: u64,
+    pub close_authority: Option<[u8; 32]>,
+}
+
+impl TokenAccount {
+    /// Decode an SPL token account.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        if data.len() != TOKEN_ACCOUNT_LEN {
+            bail!(
+                "Token account must be {} bytes, got {}",
+                TOKEN_ACCOUNT_LEN,
+                data.len()
+            );
+        }
+        let mut reader = ByteReader::new(data);
+        Ok(Self {
+            mint: reader.read_pubkey()?,
+            owner: reader.read_pubkey()?,
+            amount: reader.read_u64()?,
+            delegate: read_coption_pubkey(&mut reader)?,
+            state: match reader.read_u8()? {
+                0 => TokenAccountState::Uninitialized,
+                1 => TokenAccountState::Initialized,
+                2 => TokenAccountState::Frozen,
+                state => bail!("Invalid token account state {}", state),
+            },
+            is_native: match reader.read_u32()? {
+                0 => {
+                    reader.read_u64()?;
+                    None
+                }
+                _ => Some(reader.read_u64()?),
+            },
+            delegated_amount: reader.read_u64()?,
+            close_authority: read_coption_pubkey(&mut reader)?,
+        })
+    }
+
+    /// Decode a token account from a fetched account, checking the SPL Token program owns it.
+    pub fn from_account(account: &AccountInfo) -> Result<Self> {
+        if !account.is_owned_by(TOKEN_PROGRAM_ID) {
+            bail!("Account is not owned by the SPL Token program");
+        }
+        Self::deserialize(&account.data)
+    }
+}
+
+/// A creator listed in token metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Creator {
+    pub address: [u8; 32],
+    /// Whether the creator has signed to confirm they are one.
+    pub verified: bool,
+    /// Share of royalties, in percent.
+    pub share: u8,
+}
+
+/// The leading fields of a Metaplex Token Metadata account.
+///
+/// Fields added by later versions of the program (editions, token standard,
+/// collections and so on) are not decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub update_authority: [u8; 32],
+    pub mint: [u8; 32],
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+}
+
+impl Metadata {
+    /// Decode a Metaplex metadata account.
+    ///
+    /// The program pads name, symbol and URI with NUL bytes to fixed lengths;
+    /// the padding is stripped.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(data);
+        if reader.read_u8()? != METADATA_KEY_V1 {
+            bail!("Account is not a Metaplex metadata account");
+        }
+        let update_authority = reader.read_pubkey()?;
+        let mint = reader.read_pubkey()?;
+        let name = read_padded_string(&mut reader)?;
+        let symbol = read_padded_string(&mut reader)?;
+        let uri = read_padded_string(&mut reader)?;
+        let seller_fee_basis_points = reader.read_u16()?;
+
+        let mut creators = Vec::new();
+        if reader.read_u8()? != 0 {
+            for _ in 0..reader.read_u32()? {
+                creators.push(Creator {
+                    address: reader.read_pubkey()?,
+                    verified: reader.read_u8()? != 0,
+                    share: reader.read_u8()?,
+                });
+            }
+        }
+
+        Ok(Self {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened: reader.read_u8()? != 0,
+            is_mutable: reader.read_u8()? != 0,
+        })
+    }
+
+    /// Decode metadata from a fetched account, checking the Metadata program owns it.
+    pub fn from_account(account: &AccountInfo) -> Result<Self> {
+        if !account.is_owned_by(METADATA_PROGRAM_ID) {
+            bail!("Account is not owned by the Metaplex Token Metadata program");
+        }
+        Self::deserialize(&account.data)
+    }
+}
+
+/// An initialized durable nonce account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceAccount {
+    /// Key that must sign to advance the nonce.
+    pub authority: [u8; 32],
+    /// Value a durable transaction uses as its recent blockhash; changes every advance.
+    pub nonce: [u8; 32],
+    pub lamports_per_signature: u64,
+}
+
+impl NonceAccount {
+    /// Decode a nonce account, in either the legacy or the current layout.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        if data.len() != NONCE_ACCOUNT_LEN {
+            bail!(
+                "Nonce account must be {} bytes, got {}",
+                NONCE_ACCOUNT_LEN,
+                data.len()
+            );
+        }
+        let mut reader = ByteReader::new(data);
+        let version = reader.read_u32()?;
+        if version > 1 {
+            bail!("Unknown nonce account version {}", version);
+        }
+        if reader.read_u32()? != 1 {
+            bail!("Nonce account is not initialized");
+        }
+        Ok(Self {
+            authority: reader.read_pubkey()?,
+            nonce: reader.read_pubkey()?,
+            lamports_per_signature: reader.read_u64()?,
+        })
+    }
+
+    /// Decode a nonce account from a fetched account, checking the System program owns it.
+    pub fn from_account(account: &AccountInfo) -> Result<Self> {
+        if !account.is_owned_by(SYSTEM_PROGRAM_ID) {
+            bail!("Account is not owned by the System program");
+        }
+        Self::deserialize(&account.data)
+    }
+}
+
+/// An SPL `COption<Pubkey>`: a 4-byte tag followed by the key, present either way.
+fn read_coption_pubkey(reader: &mut ByteReader) -> Result<Option<[u8; 32]>> {
+    let tag = reader.read_u32()?;
+    let key = reader.read_pubkey()?;
+    match tag {
+        0 => Ok(None),
+        1 => Ok(Some(key)),
+        tag => bail!("Invalid option tag {}", tag),
+    }
+}
+
+fn read_padded_string(reader: &mut ByteReader) -> Result<String> {
+    Ok(reader.read_string()?.trim_end_matches('\0').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coption(key: Option<[u8; 32]>) -> Vec<u8> {
+        let mut bytes = (key.is_some() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&key.unwrap_or_default());
+        bytes
+    }
+
+    fn padded(value: &str, len: usize) -> Vec<u8> {
+        let mut bytes = (len as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(value.as_bytes());
+        bytes.resize(4 + len, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_mint() {
+        let mut data = coption(None);
+        data.extend_from_slice(&1_000_000_000_000_000u64.to_le_bytes());
+        data.extend_from_slice(&[6, 1]);
+        data.extend(coption(Some([0x22; 32])));
+
+        let mint = Mint::deserialize(&data).unwrap();
+        assert_eq!(mint.mint_authority, None);
+        assert_eq!(mint.supply, 1_000_000_000_000_000);
+        assert_eq!(mint.decimals, 6);
+        assert!(mint.is_initialized);
+        assert_eq!(mint.freeze_authority, Some([0x22; 32]));
+
+        let account = AccountInfo {
+            lamports: 1_461_600,
+            owner: [0; 32],
+            data: data.clone(),
+            executable: false,
+        };
+        assert!(Mint::from_account(&account).is_err());
+        assert!(Mint::deserialize(&data[..81]).is_err());
+    }
+
+    #[test]
+    fn test_decode_token_account() {
+        let mut data = [0x11; 32].to_vec();
+        data.extend_from_slice(&[0x44; 32]);
+        data.extend_from_slice(&2_500_000u64.to_le_bytes());
+        data.extend(coption(None));
+        data.push(2);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend(coption(Some([0x44; 32])));
+
+        let account = TokenAccount::deserialize(&data).unwrap();
+        assert_eq!(account.mint, [0x11; 32]);
+        assert_eq!(account.owner, [0x44; 32]);
+        assert_eq!(account.amount, 2_500_000);
+        assert_eq!(account.state, TokenAccountState::Frozen);
+        assert_eq!(account.is_native, None);
+        assert_eq!(account.close_authority, Some([0x44; 32]));
+    }
+
+    #[test]
+    fn test_decode_metadata() {
+        let mut data = vec![METADATA_KEY_V1];
+        data.extend_from_slice(&[0x33; 32]);
+        data.extend_from_slice(&[0x11; 32]);
+        data.extend(padded("Frog Coin", 32));
+        data.extend(padded("FROG", 10));
+        data.extend(padded("https://example.com/frog.json", 200));
+        data.extend_from_slice(&500u16.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0x44; 32]);
+        data.extend_from_slice(&[1, 100]);
+        data.extend_from_slice(&[0, 1]);
+        // Trailing fields from newer program versions are ignored.
+        data.extend_from_slice(&[1, 255, 1, 0]);
+
+        let metadata = Metadata::deserialize(&data).unwrap();
+        assert_eq!(metadata.name, "Frog Coin");
+        assert_eq!(metadata.symbol, "FROG");
+        assert_eq!(metadata.uri, "https://example.com/frog.json");
+        assert_eq!(metadata.seller_fee_basis_points, 500);
+        assert_eq!(
+            metadata.creators,
+            vec![Creator {
+                address: [0x44; 32],
+                verified: true,
+                share: 100,
+            }]
+        );
+        assert!(!metadata.primary_sale_happened);
+        assert!(metadata.is_mutable);
+
+        data[0] = 0;
+        assert!(Metadata::deserialize(&data).is_err());
+    }
+
+    // Layout written by solana-sdk for `Versions::Current(State::Initialized(..))`.
+    #[test]
+    fn test_decode_nonce_account() {
+        use base64::Engine;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode("AQAAAAEAAADqSmxj4pxSCr71UHsTLsX5lUd2rr6+e5JCHuppFEbSLIPxBhEO/yejXLkGK40IMZ6BLvIYzv8rciMz80a60lGdiBMAAAAAAAA=")
+            .unwrap();
+        let nonce = NonceAccount::deserialize(&data).unwrap();
+        assert_eq!(
+            bs58::encode(nonce.authority).into_string(),
+            "GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB"
+        );
+        assert_eq!(
+            bs58::encode(nonce.nonce).into_string(),
+            "9t3a7oPtPTwKyY3d62K8fiUXesw8PsyG6nAJDSNrKpk4"
+        );
+        assert_eq!(nonce.lamports_per_signature, 5000);
+
+        let mut uninitialized = data;
+        uninitialized[4] = 0;
+        assert!(NonceAccount::deserialize(&uninitialized).is_err());
+    }
+}
@@ -1,7 +1,186 @@
-use anyhow::{bail, Context, Result};
-use log::debug;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use futures_util::StreamExt;
+use log::{debug, warn};
+use serde::Deserialize;
 use serde_json::{json, Value};
 
+use crate::config::network::Commitment;
+use crate::solana::accounts::{
+    AccountInfo, Metadata, Mint, NonceAccount, TokenAccount, TOKEN_ACCOUNT_LEN,
+};
+use crate::solana::keypair;
+use crate::solana::pda;
+use crate::solana::programs::TOKEN_PROGRAM_ID;
+use crate::solana::pubsub::PubsubClient;
+use crate::solana::pumpfun::{self, BondingCurve, Global};
+use crate::solana::transaction::{AddressLookupTable, VersionedMessage, VersionedTransaction};
+
+/// Percentile of recent prioritization fees used by the automatic estimator.
+const PRIORITY_FEE_PERCENTILE: usize = 75;
+
+/// Upper bound on automatically estimated priority fees, in micro-lamports per compute unit.
+pub const MAX_AUTO_PRIORITY_FEE: u64 = 5_000_000;
+
+/// How often signature statuses are polled while waiting for confirmation.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often an unconfirmed transaction is re-sent to the cluster.
+const REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often a WebSocket-based wait double-checks the status over HTTP, in
+/// case the notification was missed during a reconnect.
+const SUBSCRIPTION_RESYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long to wait for a transaction to confirm, whether sent here or by someone else.
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Prioritization fee paid by transactions landed in a recent slot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritizationFee {
+    pub slot: u64,
+    pub prioritization_fee: u64,
+}
+
+/// A token account returned by `getTokenLargestAccounts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargestTokenAccount {
+    pub address: String,
+    /// Raw token amount, in base units.
+    pub amount: u64,
+}
+
+/// Why a transaction failed, as reported by the RPC node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    /// A transaction-level failure such as `BlockhashNotFound` or `InsufficientFundsForFee`.
+    Transaction(String),
+    /// An instruction failed; `custom_code` is set for program-specific `Custom` errors.
+    Instruction {
+        index: u8,
+        error: String,
+        custom_code: Option<u32>,
+    },
+}
+
+impl TransactionError {
+    /// Parse the `err` field of an RPC response, returning `None` when it is null.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::String(name) => Some(TransactionError::Transaction(name.clone())),
+            Value::Object(map) => {
+                if let Some(Value::Array(parts)) = map.get("InstructionError") {
+                    let index = parts.first().and_then(Value::as_u64).unwrap_or(0) as u8;
+                    let detail = parts.get(1).cloned().unwrap_or(Value::Null);
+                    let custom_code = detail
+                        .get("Custom")
+                        .and_then(Value::as_u64)
+                        .map(|c| c as u32);
+                    let error = match (&detail, custom_code) {
+                        (_, Some(code)) => format!("Custom({})", code),
+                        (Value::String(name), None) => name.clone(),
+                        (other, None) => other.to_string(),
+                    };
+                    return Some(TransactionError::Instruction {
+                        index,
+                        error,
+                        custom_code,
+                    });
+                }
+                Some(TransactionError::Transaction(value.to_string()))
+            }
+            other => Some(TransactionError::Transaction(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::Transaction(name) => write!(f, "{}", name),
+            TransactionError::Instruction { index, error, .. } => {
+                write!(f, "instruction {} failed: {}", index, error)
+            }
+        }
+    }
+}
+
+/// Outcome of `simulateTransaction`.
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+/// Entry returned by `getSignatureStatuses` for a transaction the node has seen.
+#[derive(Debug, Clone)]
+pub struct SignatureStatus {
+    pub slot: u64,
+    pub confirmation_status: Commitment,
+    pub err: Option<TransactionError>,
+}
+
+impl SignatureStatus {
+    /// Parse one element of the `getSignatureStatuses` value array; `null` means unknown.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        if value.is_null() {
+            return None;
+        }
+        let confirmation_status = match value["confirmationStatus"].as_str() {
+            Some(status) => status.parse().ok()?,
+            // Nodes that omit the field report a null confirmation count once rooted.
+            None if value["confirmations"].is_null() => Commitment::Finalized,
+            None => Commitment::Processed,
+        };
+        Some(Self {
+            slot: value["slot"].as_u64()?,
+            confirmation_status,
+            err: TransactionError::from_value(&value["err"]),
+        })
+    }
+
+    /// Whether the transaction has either failed or reached the requested commitment.
+    pub fn is_settled(&self, commitment: Commitment) -> bool {
+        self.err.is_some() || self.confirmation_status >= commitment
+    }
+}
+
+/// A transaction that has landed on-chain, successfully or not.
+#[derive(Debug, Clone)]
+pub struct ConfirmedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub commitment: Commitment,
+    pub err: Option<TransactionError>,
+}
+
+impl ConfirmedTransaction {
+    fn new(signature: String, status: SignatureStatus) -> Self {
+        Self {
+            signature,
+            slot: status.slot,
+            commitment: status.confirmation_status,
+            err: status.err,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
 /// Manages a connection to a Solana RPC node.
 pub struct SolanaConnection {
     rpc_url: String,
@@ -77,6 +256,331 @@ impl SolanaConnection {
         Ok(blockhash.to_string())
     }
 
+    /// Get the prioritization fees paid in recent slots by transactions locking `accounts`.
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        accounts: &[String],
+    ) -> Result<Vec<PrioritizationFee>> {
+        let resp = self
+            .rpc_request("getRecentPrioritizationFees", json!([accounts]))
+            .await?;
+        serde_json::from_value(resp["result"].clone())
+            .context("Invalid prioritization fee response")
+    }
+
+    /// Estimate a competitive priority fee in micro-lamports per compute unit.
+    ///
+    /// Takes a high percentile of recent fees so launches land under congestion,
+    /// capped at `MAX_AUTO_PRIORITY_FEE`.
+    pub async fn estimate_priority_fee(&self, accounts: &[String]) -> Result<u64> {
+        let fees = self.get_recent_prioritization_fees(accounts).await?;
+        let mut samples: Vec<u64> = fees.iter().map(|f| f.prioritization_fee).collect();
+        let estimate = fee_percentile(&mut samples, PRIORITY_FEE_PERCENTILE);
+        debug!(
+            "Priority fee estimate from {} slots: {} micro-lamports",
+            samples.len(),
+            estimate
+        );
+        Ok(estimate.min(MAX_AUTO_PRIORITY_FEE))
+    }
+
+    /// Simulate a transaction without submitting it.
+    ///
+    /// Signatures are not verified and the blockhash is replaced with a recent one,
+    /// so unsigned or stale transactions can still be simulated.
+    pub async fn simulate_transaction(
+        &self,
+        tx: &VersionedTransaction,
+    ) -> Result<SimulationResult> {
+        let resp = self
+            .rpc_request(
+                "simulateTransaction",
+                json!([
+                    tx.to_base64(),
+                    {
+                        "encoding": "base64",
+                        "sigVerify": false,
+                        "replaceRecentBlockhash": true,
+                        "commitment": "processed",
+                    }
+                ]),
+            )
+            .await?;
+
+        let value = &resp["result"]["value"];
+        if value.is_null() {
+            bail!("Invalid simulation response");
+        }
+
+        let logs = value["logs"]
+            .as_array()
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(|l| l.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(SimulationResult {
+            err: TransactionError::from_value(&value["err"]),
+            logs,
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+
+    /// Submit a signed transaction and return its signature.
+    ///
+    /// Preflight runs at the given commitment; RPC-side retries are disabled
+    /// because `send_and_confirm` rebroadcasts on its own schedule.
+    pub async fn send_transaction(
+        &self,
+        tx: &VersionedTransaction,
+        commitment: Commitment,
+    ) -> Result<String> {
+        self.send_raw_transaction(tx, false, commitment).await
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        tx: &VersionedTransaction,
+        skip_preflight: bool,
+        commitment: Commitment,
+    ) -> Result<String> {
+        let resp = self
+            .rpc_request(
+                "sendTransaction",
+                json!([
+                    tx.to_base64(),
+                    {
+                        "encoding": "base64",
+                        "skipPreflight": skip_preflight,
+                        "preflightCommitment": commitment.as_str(),
+                        "maxRetries": 0,
+                    }
+                ]),
+            )
+            .await?;
+
+        resp["result"]
+            .as_str()
+            .map(str::to_string)
+            .context("Invalid sendTransaction response")
+    }
+
+    /// Submit a signed transaction and wait until it reaches `commitment`.
+    ///
+    /// The transaction is re-sent every few seconds until the node reports it,
+    /// and the wait ends with an error once its blockhash has expired, or for a
+    /// durable-nonce transaction once the nonce has moved on. Either way it
+    /// gives up after `DEFAULT_CONFIRMATION_TIMEOUT`. RPC errors while waiting
+    /// are logged and retried. A transaction that lands but fails is returned
+    /// with `err` set.
+    pub async fn send_and_confirm(
+        &self,
+        tx: &VersionedTransaction,
+        commitment: Commitment,
+    ) -> Result<ConfirmedTransaction> {
+        if !tx.is_fully_signed() {
+            bail!("Transaction is missing required signatures");
+        }
+        let signature = self.send_transaction(tx, commitment).await?;
+        debug!("Sent transaction {}", signature);
+
+        let deadline = Instant::now() + DEFAULT_CONFIRMATION_TIMEOUT;
+        let mut last_broadcast = Instant::now();
+        loop {
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+
+            let polled = self.get_signature_status(&signature).await;
+            if let Ok(Some(ref status)) = polled {
+                if status.is_settled(commitment) {
+                    return Ok(ConfirmedTransaction::new(signature, status.clone()));
+                }
+            }
+            // A durable-nonce transaction never expires on its own, so a dropped
+            // one would otherwise be rebroadcast forever.
+            if Instant::now() >= deadline {
+                bail!(
+                    "Transaction {} was not {} within {}s",
+                    signature,
+                    commitment.as_str(),
+                    DEFAULT_CONFIRMATION_TIMEOUT.as_secs()
+                );
+            }
+            // The transaction may already be in flight or landed, so a failed
+            // check is no reason to give up and invite a second send.
+            match polled {
+                Ok(Some(_)) => {
+                    // Landed but not yet deep enough; resending would achieve nothing.
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Status check of {} failed: {}", signature, e);
+                    continue;
+                }
+            }
+
+            if last_broadcast.elapsed() < REBROADCAST_INTERVAL {
+                continue;
+            }
+            let expired = match self.has_expired(tx).await {
+                Ok(expired) => expired,
+                Err(e) => {
+                    warn!("Expiry check of {} failed: {}", signature, e);
+                    false
+                }
+            };
+            if expired {
+                // The transaction may have landed between the two calls above.
+                match self.get_signature_status(&signature).await {
+                    Ok(Some(status)) if status.is_settled(commitment) => {
+                        return Ok(ConfirmedTransaction::new(signature, status));
+                    }
+                    Ok(Some(_)) => continue,
+                    Ok(None) => {}
+                    Err(e) => {
+                        warn!("Status check of {} failed: {}", signature, e);
+                        continue;
+                    }
+                }
+                bail!(
+                    "Transaction {} expired before it was {}: its blockhash or nonce is no longer valid",
+                    signature,
+                    commitment.as_str()
+                );
+            }
+
+            debug!("Rebroadcasting {}", signature);
+            if let Err(e) = self.send_raw_transaction(tx, true, commitment).await {
+                warn!("Rebroadcast of {} failed: {}", signature, e);
+            }
+            last_broadcast = Instant::now();
+        }
+    }
+
+    /// Whether `tx` can no longer land: its blockhash has expired or, for a
+    /// durable-nonce transaction, the nonce account holds a different nonce.
+    async fn has_expired(&self, tx: &VersionedTransaction) -> Result<bool> {
+        let blockhash = tx.message.recent_blockhash();
+        match tx.message.durable_nonce_account() {
+            Some(account) => {
+                let address = bs58::encode(account).into_string();
+                let nonce = self.get_nonce_account(&address).await?;
+                Ok(nonce.is_none_or(|nonce| nonce.nonce != *blockhash))
+            }
+            None => {
+                let blockhash = bs58::encode(blockhash).into_string();
+                Ok(!self.is_blockhash_valid(&blockhash).await?)
+            }
+        }
+    }
+
+    /// Wait for a transaction submitted elsewhere to reach `commitment`.
+    ///
+    /// Uses a `signatureSubscribe` stream when the WebSocket endpoint is
+    /// reachable and falls back to polling `getSignatureStatuses` otherwise.
+    pub async fn wait_for_confirmation(
+        &self,
+        signature: &str,
+        commitment: Commitment,
+        timeout: Duration,
+    ) -> Result<ConfirmedTransaction> {
+        let wait = async {
+            match self.pubsub().await {
+                Ok(pubsub) => self.watch_signature(&pubsub, signature, commitment).await,
+                Err(e) => {
+                    debug!("WebSocket unavailable, polling instead: {:#}", e);
+                    self.poll_signature(signature, commitment).await
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow!(
+                "Transaction {} was not {} within {}s",
+                signature,
+                commitment.as_str(),
+                timeout.as_secs()
+            )
+        })?
+    }
+
+    async fn watch_signature(
+        &self,
+        pubsub: &PubsubClient,
+        signature: &str,
+        commitment: Commitment,
+    ) -> Result<ConfirmedTransaction> {
+        let mut subscription = pubsub.signature_subscribe(signature, commitment).await?;
+        loop {
+            // Covers both a transaction that settled before the subscription
+            // existed and a notification lost while the socket reconnected.
+            if let Some(status) = self.get_signature_status(signature).await? {
+                if status.is_settled(commitment) {
+                    return Ok(ConfirmedTransaction::new(signature.to_string(), status));
+                }
+            }
+            tokio::select! {
+                notification = subscription.next() => {
+                    let notification = notification
+                        .context("Signature subscription closed before confirmation")?;
+                    return Ok(ConfirmedTransaction {
+                        signature: signature.to_string(),
+                        slot: notification.slot,
+                        commitment,
+                        err: notification.err,
+                    });
+                }
+                _ = tokio::time::sleep(SUBSCRIPTION_RESYNC_INTERVAL) => {}
+            }
+        }
+    }
+
+    async fn poll_signature(
+        &self,
+        signature: &str,
+        commitment: Commitment,
+    ) -> Result<ConfirmedTransaction> {
+        loop {
+            if let Some(status) = self.get_signature_status(signature).await? {
+                if status.is_settled(commitment) {
+                    return Ok(ConfirmedTransaction::new(signature.to_string(), status));
+                }
+            }
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Fetch the status of a single signature, or `None` if the node has not seen it.
+    pub async fn get_signature_status(&self, signature: &str) -> Result<Option<SignatureStatus>> {
+        let resp = self
+            .rpc_request(
+                "getSignatureStatuses",
+                json!([[signature], {"searchTransactionHistory": false}]),
+            )
+            .await?;
+
+        let statuses = resp["result"]["value"]
+            .as_array()
+            .context("Invalid signature status response")?;
+
+        Ok(statuses.first().and_then(SignatureStatus::from_value))
+    }
+
+    /// Whether a blockhash can still be used by new transactions.
+    pub async fn is_blockhash_valid(&self, blockhash: &str) -> Result<bool> {
+        let resp = self
+            .rpc_request(
+                "isBlockhashValid",
+                json!([blockhash, {"commitment": "processed"}]),
+            )
+            .await?;
+
+        resp["result"]["value"]
+            .as_bool()
+            .context("Invalid isBlockhashValid response")
+    }
+
     /// Confirm whether a transaction has been finalized on-chain.
     pub async fn confirm_transaction(&self, signature: &str) -> Result<bool> {
         let resp = self
@@ -101,10 +605,286 @@ impl SolanaConnection {
         }
     }
 
+    /// Fetch an account, or `None` if it does not exist.
+    pub async fn get_account_info(&self, address: &str) -> Result<Option<AccountInfo>> {
+        let resp = self
+            .rpc_request(
+                "getAccountInfo",
+                json!([address, {"encoding": "base64", "commitment": "confirmed"}]),
+            )
+            .await?;
+
+        let value = &resp["result"]["value"];
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        let encoded = value["data"][0]
+            .as_str()
+            .context("Invalid account info response")?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .context("Invalid base64 account data")?;
+        let owner = value["owner"]
+            .as_str()
+            .context("Invalid account info response")?;
+        Ok(Some(AccountInfo {
+            lamports: value["lamports"].as_u64().unwrap_or(0),
+            owner: keypair::decode_pubkey(owner)?,
+            data,
+            executable: value["executable"].as_bool().unwrap_or(false),
+        }))
+    }
+
+    /// Fetch the raw data of an account, or `None` if the account does not exist.
+    pub async fn get_account_data(&self, address: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.get_account_info(address).await?.map(|account| account.data))
+    }
+
+    /// Fetch and decode an address lookup table account.
+    pub async fn get_address_lookup_table(&self, address: &str) -> Result<AddressLookupTable> {
+        let key = keypair::decode_pubkey(address)?;
+        let data = self
+            .get_account_data(address)
+            .await?
+            .context(format!("Lookup table {} not found", address))?;
+        AddressLookupTable::deserialize(key, &data)
+    }
+
+    /// Fetch and decode the pump.fun bonding curve of a mint, or `None` if it has none.
+    pub async fn get_bonding_curve(&self, mint: &str) -> Result<Option<BondingCurve>> {
+        let address = keypair::pubkey_from_bytes(&pumpfun::bonding_curve_for_mint(mint)?)?;
+        match self.get_account_data(&address).await? {
+            Some(data) => Ok(Some(BondingCurve::deserialize(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Raw token amount held by an SPL token account, or `None` if the account does not exist.
+    pub async fn get_token_account_balance(&self, address: &str) -> Result<Option<u64>> {
+        Ok(self
+            .get_token_account(address)
+            .await?
+            .map(|account| account.amount))
+    }
+
+    /// Fetch and decode an SPL token account, or `None` if it does not exist.
+    pub async fn get_token_account(&self, address: &str) -> Result<Option<TokenAccount>> {
+        self.get_account_info(address)
+            .await?
+            .map(|account| TokenAccount::from_account(&account))
+            .transpose()
+    }
+
+    /// Fetch every SPL token account holding `mint`, keyed by account address.
+    pub async fn get_token_accounts_by_mint(
+        &self,
+        mint: &str,
+    ) -> Result<Vec<(String, TokenAccount)>> {
+        let filters = json!([
+            {"dataSize": TOKEN_ACCOUNT_LEN},
+            {"memcmp": {"offset": 0, "bytes": mint}}
+        ]);
+        let resp = self
+            .rpc_request(
+                "getProgramAccounts",
+                json!([
+                    TOKEN_PROGRAM_ID,
+                    {"encoding": "base64", "commitment": "confirmed", "filters": filters}
+                ]),
+            )
+            .await?;
+        let accounts = resp["result"]
+            .as_array()
+            .context("Invalid program accounts response")?;
+        accounts
+            .iter()
+            .map(|entry| {
+                let address = entry["pubkey"]
+                    .as_str()
+                    .context("Invalid program accounts response")?;
+                let encoded = entry["account"]["data"][0]
+                    .as_str()
+                    .context("Invalid program accounts response")?;
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .context("Invalid base64 account data")?;
+                Ok((address.to_string(), TokenAccount::deserialize(&data)?))
+            })
+            .collect()
+    }
+
+    /// Fetch and decode an SPL Token mint, or `None` if it does not exist.
+    pub async fn get_mint(&self, mint: &str) -> Result<Option<Mint>> {
+        self.get_account_info(mint)
+            .await?
+            .map(|account| Mint::from_account(&account))
+            .transpose()
+    }
+
+    /// Fetch and decode a durable nonce account, or `None` if it does not exist.
+    pub async fn get_nonce_account(&self, address: &str) -> Result<Option<NonceAccount>> {
+        self.get_account_info(address)
+            .await?
+            .map(|account| NonceAccount::from_account(&account))
+            .transpose()
+    }
+
+    /// Fetch and decode the Metaplex metadata for a mint, or `None` if it has none.
+    pub async fn get_token_metadata(&self, mint: &str) -> Result<Option<Metadata>> {
+        let mint = keypair::decode_pubkey(mint)?;
+        let address = keypair::pubkey_from_bytes(&pda::metadata_address(&mint))?;
+        self.get_account_info(&address)
+            .await?
+            .map(|account| Metadata::from_account(&account))
+            .transpose()
+    }
+
+    /// The largest token accounts of a mint, biggest first (at most 20).
+    pub async fn get_token_largest_accounts(&self, mint: &str) -> Result<Vec<LargestTokenAccount>> {
+        let resp = self
+            .rpc_request(
+                "getTokenLargestAccounts",
+                json!([mint, {"commitment": "confirmed"}]),
+            )
+            .await?;
+        let accounts = resp["result"]["value"]
+            .as_array()
+            .context("Invalid largest accounts response")?;
+        accounts
+            .iter()
+            .map(|account| {
+                Ok(LargestTokenAccount {
+                    address: account["address"]
+                        .as_str()
+                        .context("Invalid largest accounts response")?
+                        .to_string(),
+                    amount: account["amount"]
+                        .as_str()
+                        .and_then(|amount| amount.parse().ok())
+                        .context("Invalid largest accounts response")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Fetch and decode the pump.fun `Global` configuration account.
+    pub async fn get_pump_fun_global(&self) -> Result<Global> {
+        let address = keypair::pubkey_from_bytes(&pumpfun::global_address())?;
+        let data = self
+            .get_account_data(&address)
+            .await?
+            .context("pump.fun global config not found on this cluster")?;
+        Global::deserialize(&data)
+    }
+
+    /// Fetch every address lookup table a message loads accounts from; none for a legacy message.
+    pub async fn get_lookup_tables(
+        &self,
+        message: &VersionedMessage,
+    ) -> Result<Vec<AddressLookupTable>> {
+        let VersionedMessage::V0(message) = message else {
+            return Ok(Vec::new());
+        };
+        let mut tables = Vec::with_capacity(message.address_table_lookups.len());
+        for lookup in &message.address_table_lookups {
+            let address = keypair::pubkey_from_bytes(&lookup.account_key)?;
+            tables.push(self.get_address_lookup_table(&address).await?);
+        }
+        Ok(tables)
+    }
+
     /// Get the WebSocket URL derived from the RPC URL.
     pub fn ws_url(&self) -> Option<&str> {
         self.ws_url.as_deref()
     }
+
+    /// Open a pubsub client on the WebSocket endpoint derived from the RPC URL.
+    pub async fn pubsub(&self) -> Result<PubsubClient> {
+        let url = self.ws_url().context("No WebSocket endpoint configured")?;
+        PubsubClient::connect(url).await
+    }
+}
+
+/// Nearest-rank percentile of the given fee samples, or 0 when there are none.
+fn fee_percentile(samples: &mut [u64], percentile: usize) -> u64 {
+    if samples.is_empty() {
+        return 0;
+    }
+    samples.sort_unstable();
+    let rank = (samples.len() * percentile).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_error_parsing() {
+        assert_eq!(TransactionError::from_value(&Value::Null), None);
+        assert_eq!(
+            TransactionError::from_value(&json!("BlockhashNotFound")),
+            Some(TransactionError::Transaction(
+                "BlockhashNotFound".to_string()
+            ))
+        );
+
+        let custom =
+            TransactionError::from_value(&json!({"InstructionError": [2, {"Custom": 6002}]}))
+                .unwrap();
+        assert_eq!(
+            custom,
+            TransactionError::Instruction {
+                index: 2,
+                error: "Custom(6002)".to_string(),
+                custom_code: Some(6002),
+            }
+        );
+        assert_eq!(custom.to_string(), "instruction 2 failed: Custom(6002)");
+
+        let builtin =
+            TransactionError::from_value(&json!({"InstructionError": [0, "InvalidAccountData"]}))
+                .unwrap();
+        assert_eq!(
+            builtin.to_string(),
+            "instruction 0 failed: InvalidAccountData"
+        );
+    }
+
+    #[test]
+    fn test_fee_percentile() {
+        assert_eq!(fee_percentile(&mut [], 75), 0);
+        assert_eq!(fee_percentile(&mut [500], 75), 500);
+        let mut fees = [0, 10, 40, 30, 20, 0, 0, 100];
+        assert_eq!(fee_percentile(&mut fees, 75), 30);
+        assert_eq!(fee_percentile(&mut fees, 100), 100);
+    }
+
+    #[test]
+    fn test_signature_status_parsing() {
+        assert!(SignatureStatus::from_value(&Value::Null).is_none());
+
+        let processed = SignatureStatus::from_value(&json!({
+            "slot": 72, "confirmations": 0, "err": null, "confirmationStatus": "processed"
+        }))
+        .unwrap();
+        assert_eq!(processed.slot, 72);
+        assert!(processed.is_settled(Commitment::Processed));
+        assert!(!processed.is_settled(Commitment::Confirmed));
+
+        let failed = SignatureStatus::from_value(&json!({
+            "slot": 80, "confirmations": 1, "confirmationStatus": "processed",
+            "err": {"InstructionError": [1, {"Custom": 1}]}
+        }))
+        .unwrap();
+        assert!(failed.is_settled(Commitment::Finalized));
+
+        let rooted =
+            SignatureStatus::from_value(&json!({"slot": 9, "confirmations": null, "err": null}))
+                .unwrap();
+        assert_eq!(rooted.confirmation_status, Commitment::Finalized);
+    }
 }
 
 // iteration 89
@@ -1,17 +1,242 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
 use anyhow::{bail, Context, Result};
-use ed25519_dalek::SigningKey;
+use ed25519_dalek::{Signer, SigningKey};
+use log::warn;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Whether new secret keypairs are locked into RAM, set from the `lock_memory` setting.
+static LOCK_MEMORY: AtomicBool = AtomicBool::new(false);
+
+/// How many locked keypairs each locked page holds, by page number. `munlock`
+/// does not nest, so a page is only unlocked once its last keypair is dropped.
+static LOCKED_PAGES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// Lock the memory of keypairs created from now on, keeping them out of swap
+/// and core dumps.
+pub fn set_memory_locking(enabled: bool) {
+    LOCK_MEMORY.store(enabled, Ordering::Relaxed);
+}
 
-/// Generate a new random ed25519 keypair.
+/// A 64-byte ed25519 keypair (secret key followed by public key) that is
+/// wiped from memory when dropped.
 ///
-/// Returns a tuple of (secret_key, public_key) as 32-byte arrays.
-pub fn generate_keypair() -> ([u8; 32], [u8; 32]) {
-    let mut csprng = rand::rngs::OsRng;
-    let signing_key = SigningKey::generate(&mut csprng);
-    let public_key = signing_key.verifying_key();
+/// This is the only form in which secret keys move between modules. The bytes
+/// live on the heap so moving a keypair never leaves copies behind, `Debug`
+/// shows only the address, and every constructor checks that the public half
+/// belongs to the secret half.
+pub struct SecretKeypair {
+    bytes: Box<[u8; 64]>,
+    locked: bool,
+}
+
+impl SecretKeypair {
+    /// Generate a new random keypair.
+    pub fn generate() -> Self {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        Self::from_signing_key(&signing_key)
+    }
+
+    /// Build the keypair for a 32-byte secret key.
+    pub fn from_secret(secret: &[u8; 32]) -> Self {
+        Self::from_signing_key(&SigningKey::from_bytes(secret))
+    }
+
+    fn from_signing_key(signing_key: &SigningKey) -> Self {
+        let mut keypair = Self::zeroed();
+        keypair.bytes[..32].copy_from_slice(signing_key.as_bytes());
+        keypair.bytes[32..].copy_from_slice(signing_key.verifying_key().as_bytes());
+        keypair
+    }
+
+    /// Allocate (and, if enabled, lock) the storage before any secret is written to it.
+    fn zeroed() -> Self {
+        Self::allocate(LOCK_MEMORY.load(Ordering::Relaxed))
+    }
+
+    fn allocate(lock: bool) -> Self {
+        let bytes = Box::new([0u8; 64]);
+        let locked = lock && lock_memory(&bytes[..]);
+        Self { bytes, locked }
+    }
+
+    /// Check and copy 64 keypair bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 64 {
+            bail!(
+                "Invalid keypair length: expected 64 bytes, got {}",
+                bytes.len()
+            );
+        }
+        let secret: &[u8; 32] = bytes[..32].try_into().expect("32-byte secret");
+        let keypair = Self::from_secret(secret);
+        if keypair.bytes[32..] != bytes[32..] {
+            bail!("Invalid keypair: public key does not match secret key");
+        }
+        Ok(keypair)
+    }
+
+    /// Decode a base58-encoded keypair.
+    pub fn from_base58(encoded: &str) -> Result<Self> {
+        let bytes = Zeroizing::new(
+            bs58::decode(encoded)
+                .into_vec()
+                .context("Invalid base58 keypair")?,
+        );
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a keypair in the Solana CLI `id.json` format: a JSON array of 64 bytes.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let bytes: Zeroizing<Vec<u8>> =
+            Zeroizing::new(serde_json::from_str(json).context("Invalid keypair file format")?);
+        Self::from_bytes(&bytes)
+    }
+
+    /// The 32-byte public key.
+    pub fn pubkey(&self) -> [u8; 32] {
+        self.bytes[32..].try_into().expect("32-byte public key")
+    }
+
+    /// The base58-encoded public key, i.e. the wallet address.
+    pub fn address(&self) -> String {
+        bs58::encode(&self.bytes[32..]).into_string()
+    }
+
+    /// Sign `message`, returning the 64-byte ed25519 signature.
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        let secret: &[u8; 32] = self.bytes[..32].try_into().expect("32-byte secret");
+        SigningKey::from_bytes(secret).sign(message).to_bytes()
+    }
+
+    /// The raw keypair bytes, for encrypting or exporting the key.
+    pub fn expose_secret(&self) -> &[u8; 64] {
+        &self.bytes
+    }
+
+    /// Encode the keypair as base58.
+    pub fn to_base58(&self) -> Zeroizing<String> {
+        Zeroizing::new(bs58::encode(&self.bytes[..]).into_string())
+    }
+
+    /// Encode the keypair in the Solana CLI `id.json` format.
+    pub fn to_json(&self) -> Zeroizing<String> {
+        use std::fmt::Write as _;
+
+        // Sized for the longest encoding up front, so the buffer never reallocates
+        // and leaves unwiped copies of the key behind.
+        let mut json = Zeroizing::new(String::with_capacity(64 * 4 + 2));
+        json.push('[');
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(json, "{}", byte).expect("writing to a String cannot fail");
+        }
+        json.push(']');
+        json
+    }
+}
 
-    (signing_key.to_bytes(), public_key.to_bytes())
+impl Clone for SecretKeypair {
+    fn clone(&self) -> Self {
+        let mut keypair = Self::zeroed();
+        keypair.bytes.copy_from_slice(&self.bytes[..]);
+        keypair
+    }
+}
+
+impl Drop for SecretKeypair {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+        if self.locked {
+            unlock_memory(&self.bytes[..]);
+        }
+    }
+}
+
+impl std::fmt::Debug for SecretKeypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretKeypair")
+            .field("address", &self.address())
+            .field("secret", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
+#[cfg(unix)]
+fn page_size() -> usize {
+    // SAFETY: sysconf has no preconditions.
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[cfg(unix)]
+fn pages(bytes: &[u8]) -> std::ops::Range<usize> {
+    let start = bytes.as_ptr() as usize;
+    start / page_size()..(start + bytes.len() - 1) / page_size() + 1
+}
+
+/// Lock the pages holding `bytes` into RAM and, on Linux, leave them out of core dumps.
+#[cfg(unix)]
+fn lock_memory(bytes: &[u8]) -> bool {
+    let mut locked = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+    // SAFETY: the range is a live allocation owned by the caller.
+    if unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) } != 0 {
+        warn!(
+            "Failed to lock key memory: {}",
+            std::io::Error::last_os_error()
+        );
+        return false;
+    }
+    for page in pages(bytes) {
+        *locked.entry(page).or_insert(0) += 1;
+        #[cfg(target_os = "linux")]
+        // SAFETY: the page is mapped, as it holds part of `bytes`.
+        unsafe {
+            libc::madvise(
+                (page * page_size()) as *mut libc::c_void,
+                page_size(),
+                libc::MADV_DONTDUMP,
+            );
+        }
+    }
+    true
+}
+
+#[cfg(unix)]
+fn unlock_memory(bytes: &[u8]) {
+    let mut locked = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+    for page in pages(bytes) {
+        let Some(count) = locked.get_mut(&page) else {
+            continue;
+        };
+        *count -= 1;
+        if *count == 0 {
+            locked.remove(&page);
+            let address = (page * page_size()) as *mut libc::c_void;
+            // SAFETY: the page is still mapped; `bytes` has not been freed yet.
+            unsafe {
+                libc::munlock(address, page_size());
+                #[cfg(target_os = "linux")]
+                libc::madvise(address, page_size(), libc::MADV_DODUMP);
+            }
+        }
+    }
 }
 
+#[cfg(not(unix))]
+fn lock_memory(_bytes: &[u8]) -> bool {
+    warn!("Locking key memory is not supported on this platform");
+    false
+}
+
+#[cfg(not(unix))]
+fn unlock_memory(_bytes: &[u8]) {}
+
 /// Convert raw public key bytes to a base58-encoded Solana address string.
 pub fn pubkey_from_bytes(bytes: &[u8]) -> Result<String> {
     if bytes.len() != 32 {
@@ -36,27 +261,101 @@ pub fn bytes_from_pubkey(address: &str) -> Result<Vec<u8>> {
     Ok(bytes)
 }
 
-/// Encode a 64-byte secret keypair (secret + public) to base58.
-pub fn keypair_to_base58(secret: &[u8; 64]) -> String {
-    bs58::encode(secret).into_string()
+/// Decode a base58 Solana address into a 32-byte array.
+pub fn decode_pubkey(address: &str) -> Result<[u8; 32]> {
+    let bytes = bytes_from_pubkey(address)?;
+    let mut pubkey = [0u8; 32];
+    pubkey.copy_from_slice(&bytes);
+    Ok(pubkey)
 }
 
-/// Decode a base58-encoded keypair string back into 64 raw bytes.
-pub fn keypair_from_base58(encoded: &str) -> Result<[u8; 64]> {
-    let bytes = bs58::decode(encoded)
-        .into_vec()
-        .context("Invalid base58 keypair")?;
+/// Write secret material to `path`, readable only by the current user.
+pub fn write_secret_file(path: &Path, contents: &str) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files; tighten an existing one too.
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .context(format!("Failed to restrict permissions on {}", path.display()))?;
+        }
+    }
+    let mut file = options
+        .open(path)
+        .context(format!("Failed to write {}", path.display()))?;
+    file.write_all(contents.as_bytes())
+        .context(format!("Failed to write {}", path.display()))
+}
 
-    if bytes.len() != 64 {
-        bail!(
-            "Decoded keypair is {} bytes, expected 64",
-            bytes.len()
+/// Write a keypair in the Solana CLI `id.json` format, readable only by the current user.
+pub fn write_keypair_file(path: &Path, keypair: &SecretKeypair) -> Result<()> {
+    write_secret_file(path, &keypair.to_json())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keypair_round_trips() {
+        let keypair = SecretKeypair::generate();
+        let json = keypair.to_json();
+        let bytes: Vec<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&bytes[..], &keypair.expose_secret()[..]);
+        assert_eq!(
+            SecretKeypair::from_json(&json).unwrap().address(),
+            keypair.address()
+        );
+        assert_eq!(
+            SecretKeypair::from_base58(&keypair.to_base58())
+                .unwrap()
+                .expose_secret(),
+            keypair.expose_secret()
         );
+        assert_eq!(
+            pubkey_from_bytes(&keypair.pubkey()).unwrap(),
+            keypair.address()
+        );
+
+        let mut mismatched = bytes;
+        mismatched[40] ^= 1;
+        assert!(SecretKeypair::from_bytes(&mismatched).is_err());
+        assert!(SecretKeypair::from_json("[1, 2, 3]").is_err());
     }
 
-    let mut keypair = [0u8; 64];
-    keypair.copy_from_slice(&bytes);
-    Ok(keypair)
+    #[test]
+    fn test_debug_redacts_secret() {
+        let keypair = SecretKeypair::from_secret(&[7; 32]);
+        let debug = format!("{:?}", keypair);
+        assert!(debug.contains(&keypair.address()));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("[7, 7"));
+        assert!(!debug.contains(keypair.to_base58().as_str()));
+    }
+
+    #[test]
+    fn test_memory_locking() {
+        // Lock these two directly; flipping the global setting would leak into
+        // tests running in parallel.
+        let mut keypair = SecretKeypair::allocate(true);
+        keypair
+            .bytes
+            .copy_from_slice(SecretKeypair::from_secret(&[3; 32]).expose_secret());
+        let mut copy = SecretKeypair::allocate(true);
+        copy.bytes.copy_from_slice(&keypair.bytes[..]);
+        assert_eq!(keypair.expose_secret(), copy.expose_secret());
+
+        // mlock fails under a low RLIMIT_MEMLOCK; the keys work either way.
+        #[cfg(unix)]
+        if copy.locked {
+            drop(keypair);
+            let locked = LOCKED_PAGES.lock().unwrap();
+            assert!(pages(&copy.bytes[..]).all(|page| locked.contains_key(&page)));
+        }
+    }
 }
 
 // iteration 92
@@ -0,0 +1,337 @@
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use rand::RngCore;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::solana::keypair::SecretKeypair;
+use crate::utils::display;
+
+/// Current keystore format version.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Environment variable holding the wallet passphrase for unattended use.
+pub const PASSPHRASE_ENV: &str = "FROGPUMP_WALLET_PASSPHRASE";
+
+/// Environment variable naming a file descriptor to read the wallet passphrase from.
+pub const PASSPHRASE_FD_ENV: &str = "FROGPUMP_WALLET_PASSPHRASE_FD";
+
+const KDF_SCRYPT: &str = "scrypt";
+const CIPHER_CHACHA20_POLY1305: &str = "chacha20-poly1305";
+const SALT_LEN: usize = 16;
+
+/// Upper bound on accepted scrypt cost, so a tampered file cannot exhaust memory:
+/// at most `128 * MAX_R << MAX_LOG_N` bytes, 1 GiB, per derivation.
+const MAX_LOG_N: u8 = 20;
+const MAX_R: u32 = 8;
+const MAX_P: u32 = 4;
+
+/// scrypt cost parameters: `2^log_n` iterations over blocks of `128 * r` bytes, `p` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    /// 32 MiB of memory per derivation.
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A wallet keypair encrypted under a passphrase.
+///
+/// The key is derived with scrypt and the keypair sealed with ChaCha20-Poly1305.
+/// The public key is stored in the clear, and bound to the ciphertext as
+/// associated data, so the address can be shown without the passphrase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub pubkey: String,
+    pub kdf: String,
+    pub kdf_params: ScryptParams,
+    /// Base64 scrypt salt.
+    pub salt: String,
+    pub cipher: String,
+    /// Base64 AEAD nonce.
+    pub nonce: String,
+    /// Base64 encrypted keypair followed by the authentication tag.
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt a keypair under `passphrase`.
+    pub fn encrypt(
+        keypair: &SecretKeypair,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<Self> {
+        let pubkey = keypair.address();
+
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+        let key = derive_key(passphrase, &salt, &params)?;
+        let mut sealed = keypair.expose_secret().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(associated_data(KEYSTORE_VERSION, &pubkey)),
+            &mut sealed,
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt keypair"))?;
+
+        let b64 = base64::engine::general_purpose::STANDARD;
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey,
+            kdf: KDF_SCRYPT.to_string(),
+            kdf_params: params,
+            salt: b64.encode(salt),
+            cipher: CIPHER_CHACHA20_POLY1305.to_string(),
+            nonce: b64.encode(nonce),
+            ciphertext: b64.encode(sealed),
+        })
+    }
+
+    /// Decrypt the keypair, failing on a wrong passphrase or any tampering.
+    pub fn decrypt(&self, passphrase: &str) -> Result<SecretKeypair> {
+        if self.version != KEYSTORE_VERSION {
+            bail!("Unsupported keystore version {}", self.version);
+        }
+        if self.kdf != KDF_SCRYPT || self.cipher != CIPHER_CHACHA20_POLY1305 {
+            bail!(
+                "Unsupported keystore algorithms {} / {}",
+                self.kdf,
+                self.cipher
+            );
+        }
+
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let salt = b64.decode(&self.salt).context("Invalid keystore salt")?;
+        let nonce: [u8; aead::NONCE_LEN] = b64
+            .decode(&self.nonce)
+            .ok()
+            .and_then(|n| n.try_into().ok())
+            .context("Invalid keystore nonce")?;
+        let mut sealed = Zeroizing::new(
+            b64.decode(&self.ciphertext)
+                .context("Invalid keystore ciphertext")?,
+        );
+
+        let key = derive_key(passphrase, &salt, &self.kdf_params)?;
+        let plain = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(associated_data(self.version, &self.pubkey)),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted keystore"))?;
+
+        let keypair = SecretKeypair::from_bytes(plain)?;
+        if keypair.address() != self.pubkey {
+            bail!("Keystore public key does not match the encrypted keypair");
+        }
+        Ok(keypair)
+    }
+
+    /// Whether wallet file contents are a keystore rather than a plaintext keypair.
+    pub fn is_keystore(contents: &str) -> bool {
+        contents.trim_start().starts_with('{')
+    }
+}
+
+/// Read the wallet passphrase from `FROGPUMP_WALLET_PASSPHRASE`, the file descriptor
+/// in `FROGPUMP_WALLET_PASSPHRASE_FD`, or an interactive prompt, in that order.
+pub fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    if let Some(passphrase) = passphrase_from_env()? {
+        return Ok(passphrase);
+    }
+    display::prompt_secret(prompt).context("Failed to read passphrase")
+}
+
+/// Like `read_passphrase`, but asks twice when prompting and rejects an empty passphrase.
+pub fn read_new_passphrase() -> Result<Zeroizing<String>> {
+    let passphrase = match passphrase_from_env()? {
+        Some(passphrase) => passphrase,
+        None => {
+            let first = display::prompt_secret("New wallet passphrase: ")
+                .context("Failed to read passphrase")?;
+            let second = display::prompt_secret("Repeat passphrase: ")
+                .context("Failed to read passphrase")?;
+            if first != second {
+                bail!("Passphrases do not match");
+            }
+            first
+        }
+    };
+    if passphrase.is_empty() {
+        bail!("Passphrase must not be empty");
+    }
+    Ok(passphrase)
+}
+
+fn passphrase_from_env() -> Result<Option<Zeroizing<String>>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Some(Zeroizing::new(passphrase)));
+    }
+    let Ok(fd) = std::env::var(PASSPHRASE_FD_ENV) else {
+        return Ok(None);
+    };
+    let fd: u32 = fd
+        .parse()
+        .with_context(|| format!("{} must be a file descriptor number", PASSPHRASE_FD_ENV))?;
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(format!("/dev/fd/{}", fd))
+            .with_context(|| format!("Failed to read passphrase from file descriptor {}", fd))?,
+    );
+    Ok(Some(Zeroizing::new(
+        contents.lines().next().unwrap_or_default().to_string(),
+    )))
+}
+
+fn associated_data(version: u32, pubkey: &str) -> Vec<u8> {
+    format!("frogpump-keystore:{}:{}", version, pubkey).into_bytes()
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: &ScryptParams) -> Result<LessSafeKey> {
+    if params.log_n == 0
+        || params.log_n > MAX_LOG_N
+        || params.r == 0
+        || params.r > MAX_R
+        || params.p == 0
+        || params.p > MAX_P
+    {
+        bail!("Keystore scrypt parameters are out of range");
+    }
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt(passphrase.as_bytes(), salt, params, &mut key[..])?;
+    let unbound = UnboundKey::new(&CHACHA20_POLY1305, &key[..])
+        .map_err(|_| anyhow::anyhow!("Failed to build encryption key"))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// scrypt as specified in RFC 7914.
+fn scrypt(passphrase: &[u8], salt: &[u8], params: &ScryptParams, out: &mut [u8]) -> Result<()> {
+    let params = ::scrypt::Params::new(params.log_n, params.r, params.p, out.len())
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    ::scrypt::scrypt(passphrase, salt, &params, out)
+        .map_err(|e| anyhow::anyhow!("Failed to derive keystore key: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: ScryptParams = ScryptParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Test vectors from RFC 7914, section 12.
+    #[test]
+    fn test_scrypt_rfc7914_vectors() {
+        let mut out = [0u8; 64];
+        scrypt(
+            b"",
+            b"",
+            &ScryptParams {
+                log_n: 4,
+                r: 1,
+                p: 1,
+            },
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            hex(&out),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+
+        scrypt(
+            b"password",
+            b"NaCl",
+            &ScryptParams {
+                log_n: 10,
+                r: 8,
+                p: 16,
+            },
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            hex(&out),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let kp = SecretKeypair::generate();
+        let keystore = Keystore::encrypt(&kp, "correct horse", FAST).unwrap();
+        assert_eq!(keystore.pubkey, kp.address());
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        assert!(Keystore::is_keystore(&json));
+        assert!(!Keystore::is_keystore(&kp.to_json()));
+
+        let loaded: Keystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.decrypt("correct horse").unwrap().expose_secret(),
+            kp.expose_secret()
+        );
+        assert!(loaded.decrypt("wrong horse").is_err());
+
+        // The clear-text address is authenticated along with the ciphertext.
+        let mut swapped = loaded.clone();
+        swapped.pubkey = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string();
+        assert!(swapped.decrypt("correct horse").is_err());
+
+        let mut expensive = loaded;
+        expensive.kdf_params.log_n = 40;
+        assert!(expensive.decrypt("correct horse").is_err());
+    }
+
+    #[test]
+    fn test_scrypt_cost_capped() {
+        let keystore = Keystore::encrypt(&SecretKeypair::generate(), "pass", FAST).unwrap();
+        // Each of these would need over 1 GiB; they must be refused before any
+        // of it is allocated.
+        for params in [
+            ScryptParams {
+                log_n: MAX_LOG_N + 1,
+                ..FAST
+            },
+            ScryptParams {
+                log_n: MAX_LOG_N,
+                r: MAX_R + 1,
+                p: 1,
+            },
+            ScryptParams {
+                log_n: 4,
+                r: 8,
+                p: MAX_P + 1,
+            },
+        ] {
+            let mut tampered = keystore.clone();
+            tampered.kdf_params = params;
+            let err = tampered.decrypt("pass").unwrap_err();
+            assert!(err.to_string().contains("out of range"));
+        }
+    }
+}
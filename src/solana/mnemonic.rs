@@ -0,0 +1,262 @@
+use std::num::NonZeroU32;
+
+use anyhow::{bail, Context, Result};
+use rand::RngCore;
+use ring::{hmac, pbkdf2};
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::solana::keypair::SecretKeypair;
+
+/// The BIP39 English wordlist, one word per line.
+const ENGLISH: &str = include_str!("bip39_english.txt");
+
+/// PBKDF2 rounds used to stretch a mnemonic into a seed.
+const SEED_ROUNDS: u32 = 2048;
+
+/// Coin type registered for Solana in SLIP-0044.
+pub const SOLANA_COIN_TYPE: u32 = 501;
+
+const HARDENED: u32 = 0x8000_0000;
+
+fn wordlist() -> Vec<&'static str> {
+    ENGLISH.lines().collect()
+}
+
+/// A checksummed BIP39 recovery phrase.
+///
+/// Only the English wordlist is supported, matching Phantom, Solflare and the Solana CLI.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    phrase: String,
+}
+
+impl Mnemonic {
+    /// Generate a random phrase of 12 or 24 words.
+    pub fn generate(words: usize) -> Result<Self> {
+        let mut entropy = Zeroizing::new(vec![0u8; entropy_len(words)?]);
+        rand::rngs::OsRng.fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy)
+    }
+
+    /// Encode 16 or 32 bytes of entropy as a phrase.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self> {
+        if entropy.len() != 16 && entropy.len() != 32 {
+            bail!(
+                "Mnemonic entropy must be 16 or 32 bytes, got {}",
+                entropy.len()
+            );
+        }
+        let checksum = Sha256::digest(entropy);
+        let mut bits: Zeroizing<Vec<bool>> =
+            Zeroizing::new(entropy.iter().flat_map(|byte| byte_bits(*byte)).collect());
+        bits.extend(byte_bits(checksum[0]).take(entropy.len() / 4));
+
+        let words = wordlist();
+        let phrase = bits
+            .chunks(11)
+            .map(|chunk| {
+                words[chunk
+                    .iter()
+                    .fold(0, |index, &bit| index << 1 | bit as usize)]
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Self { phrase })
+    }
+
+    /// Parse a phrase, checking each word and the checksum.
+    ///
+    /// Case and surrounding whitespace are ignored.
+    pub fn parse(phrase: &str) -> Result<Self> {
+        let words = wordlist();
+        let input: Zeroizing<Vec<String>> =
+            Zeroizing::new(phrase.split_whitespace().map(str::to_lowercase).collect());
+        let entropy_len = entropy_len(input.len())?;
+
+        let mut bits = Zeroizing::new(Vec::with_capacity(input.len() * 11));
+        for (position, word) in input.iter().enumerate() {
+            let index = words.binary_search(&word.as_str()).map_err(|_| {
+                anyhow::anyhow!(
+                    "Word {} ({}) is not in the BIP39 wordlist",
+                    position + 1,
+                    word
+                )
+            })?;
+            bits.extend((0..11).rev().map(|shift| index >> shift & 1 == 1));
+        }
+
+        let entropy: Zeroizing<Vec<u8>> = Zeroizing::new(
+            bits[..entropy_len * 8]
+                .chunks(8)
+                .map(|chunk| chunk.iter().fold(0u8, |byte, &bit| byte << 1 | bit as u8))
+                .collect(),
+        );
+        let mnemonic = Self::from_entropy(&entropy)?;
+        if mnemonic.phrase != *Zeroizing::new(input.join(" ")) {
+            bail!("Invalid recovery phrase: checksum mismatch");
+        }
+        Ok(mnemonic)
+    }
+
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.phrase.split(' ').count()
+    }
+
+    /// Stretch the phrase and an optional passphrase into a 64-byte BIP39 seed.
+    ///
+    /// BIP39 normalizes both to Unicode NFKD; ASCII is already normalized, so
+    /// other passphrases are rejected rather than risk deriving different keys
+    /// than other wallets.
+    pub fn to_seed(&self, passphrase: &str) -> Result<Zeroizing<[u8; 64]>> {
+        if !passphrase.is_ascii() {
+            bail!("Mnemonic passphrases must be ASCII");
+        }
+        let mut seed = Zeroizing::new([0u8; 64]);
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA512,
+            NonZeroU32::new(SEED_ROUNDS).expect("non-zero"),
+            Zeroizing::new(format!("mnemonic{}", passphrase)).as_bytes(),
+            self.phrase.as_bytes(),
+            &mut seed[..],
+        );
+        Ok(seed)
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mnemonic({} words)", self.word_count())
+    }
+}
+
+/// The derivation path of Solana account `index`, as used by Phantom and Solflare.
+pub fn solana_path(index: u32) -> [u32; 4] {
+    [44, SOLANA_COIN_TYPE, index, 0]
+}
+
+/// Render a path of hardened indices, e.g. `m/44'/501'/0'/0'`.
+pub fn format_path(path: &[u32]) -> String {
+    path.iter().fold(String::from("m"), |out, index| {
+        format!("{}/{}'", out, index)
+    })
+}
+
+/// Derive an ed25519 secret key from a seed along a path of hardened indices (SLIP-0010).
+///
+/// ed25519 only supports hardened derivation, so every index is hardened.
+pub fn derive_secret(seed: &[u8], path: &[u32]) -> Result<Zeroizing<[u8; 32]>> {
+    if path.iter().any(|&index| index >= HARDENED) {
+        bail!("Derivation indices must be below 2^31");
+    }
+    let mut node = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA512, b"ed25519 seed"), seed);
+    for &index in path {
+        let (key, chain_code) = node.as_ref().split_at(32);
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        data.push(0);
+        data.extend_from_slice(key);
+        data.extend_from_slice(&(index | HARDENED).to_be_bytes());
+        node = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA512, chain_code), &data);
+    }
+    Ok(Zeroizing::new(
+        node.as_ref()[..32].try_into().expect("32-byte key"),
+    ))
+}
+
+/// Derive the keypair of Solana account `index` from a BIP39 seed.
+pub fn derive_keypair(seed: &[u8; 64], index: u32) -> Result<SecretKeypair> {
+    let secret = derive_secret(seed, &solana_path(index))
+        .with_context(|| format!("Failed to derive account {}", index))?;
+    Ok(SecretKeypair::from_secret(&secret))
+}
+
+fn entropy_len(words: usize) -> Result<usize> {
+    match words {
+        12 => Ok(16),
+        24 => Ok(32),
+        n => bail!("Recovery phrases must have 12 or 24 words, got {}", n),
+    }
+}
+
+fn byte_bits(byte: u8) -> impl Iterator<Item = bool> {
+    (0..8).rev().map(move |shift| byte >> shift & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Vectors from the BIP39 reference implementation (trezor/python-mnemonic).
+    #[test]
+    fn test_bip39_vectors() {
+        assert_eq!(wordlist().len(), 2048);
+
+        let zero = Mnemonic::from_entropy(&[0; 16]).unwrap();
+        assert_eq!(zero.phrase(), format!("{}about", "abandon ".repeat(11)));
+        assert_eq!(
+            hex(&zero.to_seed("TREZOR").unwrap()[..]),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553\
+             1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+        assert_eq!(
+            Mnemonic::from_entropy(&[0x7f; 16]).unwrap().phrase(),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        );
+        assert_eq!(
+            Mnemonic::from_entropy(&[0xff; 32]).unwrap().phrase(),
+            format!("{}vote", "zoo ".repeat(23))
+        );
+
+        let parsed = Mnemonic::parse(
+            "  Legal winner thank year wave sausage worth useful legal winner thank   yellow\n",
+        )
+        .unwrap();
+        assert_eq!(parsed, Mnemonic::from_entropy(&[0x7f; 16]).unwrap());
+        assert!(Mnemonic::parse(&format!("{}abandon", "abandon ".repeat(11))).is_err());
+        assert!(Mnemonic::parse(&format!("{}frogs", "abandon ".repeat(11))).is_err());
+        assert!(Mnemonic::parse("abandon about").is_err());
+        assert_eq!(Mnemonic::generate(24).unwrap().word_count(), 24);
+    }
+
+    // Test vector 1 for ed25519 from SLIP-0010.
+    #[test]
+    fn test_slip10_vectors() {
+        let seed: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            hex(&derive_secret(&seed, &[]).unwrap()[..]),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex(&derive_secret(&seed, &[0, 1, 2, 2, 1_000_000_000]).unwrap()[..]),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+        assert!(derive_secret(&seed, &[HARDENED]).is_err());
+    }
+
+    // Addresses match Phantom and the Solana CLI for the all-`abandon` phrase.
+    #[test]
+    fn test_solana_accounts() {
+        let seed = Mnemonic::from_entropy(&[0; 16])
+            .unwrap()
+            .to_seed("")
+            .unwrap();
+        assert_eq!(format_path(&solana_path(3)), "m/44'/501'/3'/0'");
+        let address = |index| derive_keypair(&seed, index).unwrap().address();
+        assert_eq!(address(0), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(address(1), "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb");
+    }
+}
@@ -2,3 +2,11 @@ pub mod connection;
 pub mod transaction;
 pub mod wallet;
 pub mod keypair;
+pub mod keystore;
+pub mod mnemonic;
+pub mod accounts;
+pub mod offchain;
+pub mod pda;
+pub mod programs;
+pub mod pubsub;
+pub mod pumpfun;
@@ -0,0 +1,107 @@
+use anyhow::{bail, Result};
+
+/// Prefix of every off-chain message, chosen so it can never be a valid transaction.
+pub const SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+
+/// Header version of the format implemented here.
+pub const VERSION: u8 = 0;
+
+/// Longest message body a v0 off-chain message can carry.
+pub const MAX_LEN: usize = u16::MAX as usize - 20;
+
+/// Longest message body Ledger devices will display and sign.
+pub const MAX_LEN_LEDGER: usize = 1212;
+
+/// How the body of an off-chain message is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Printable ASCII only, short enough for a hardware wallet.
+    RestrictedAscii = 0,
+    /// Any UTF-8, short enough for a hardware wallet.
+    LimitedUtf8 = 1,
+    /// Any UTF-8, up to `MAX_LEN` bytes.
+    ExtendedUtf8 = 2,
+}
+
+/// A message signed in the Solana off-chain message format, as produced by
+/// `solana sign-offchain-message` and hardware wallets.
+///
+/// The signature covers the serialized envelope rather than the bare text, so a
+/// signature over a message can never be replayed as a transaction signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffchainMessage {
+    format: MessageFormat,
+    message: Vec<u8>,
+}
+
+impl OffchainMessage {
+    /// Wrap `message`, picking the most restrictive format that fits it.
+    pub fn new(message: &str) -> Result<Self> {
+        let bytes = message.as_bytes();
+        let format = if bytes.is_empty() {
+            bail!("Off-chain message must not be empty");
+        } else if bytes.len() <= MAX_LEN_LEDGER {
+            if bytes.iter().all(|b| (0x20..=0x7e).contains(b)) {
+                MessageFormat::RestrictedAscii
+            } else {
+                MessageFormat::LimitedUtf8
+            }
+        } else if bytes.len() <= MAX_LEN {
+            MessageFormat::ExtendedUtf8
+        } else {
+            bail!(
+                "Off-chain message is {} bytes, at most {} allowed",
+                bytes.len(),
+                MAX_LEN
+            );
+        };
+        Ok(Self {
+            format,
+            message: bytes.to_vec(),
+        })
+    }
+
+    pub fn format(&self) -> MessageFormat {
+        self.format
+    }
+
+    /// The bytes a signer signs: signing domain, version, format, length and body.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(SIGNING_DOMAIN.len() + 4 + self.message.len());
+        data.extend_from_slice(SIGNING_DOMAIN);
+        data.push(VERSION);
+        data.push(self.format as u8);
+        data.extend_from_slice(&(self.message.len() as u16).to_le_bytes());
+        data.extend_from_slice(&self.message);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Expected bytes from `OffchainMessage::serialize` in solana-sdk 2.2.
+    #[test]
+    fn test_serialize_matches_solana_sdk() {
+        let ascii = OffchainMessage::new("hello").unwrap();
+        assert_eq!(ascii.format(), MessageFormat::RestrictedAscii);
+        assert_eq!(
+            ascii.serialize(),
+            [
+                255, 115, 111, 108, 97, 110, 97, 32, 111, 102, 102, 99, 104, 97, 105, 110, 0, 0,
+                5, 0, 104, 101, 108, 108, 111
+            ]
+        );
+
+        let multiline = OffchainMessage::new("line one\nline two").unwrap();
+        assert_eq!(multiline.format(), MessageFormat::LimitedUtf8);
+        assert_eq!(&multiline.serialize()[16..20], &[0, 1, 17, 0]);
+
+        let long = OffchainMessage::new(&"a".repeat(MAX_LEN_LEDGER + 1)).unwrap();
+        assert_eq!(long.format(), MessageFormat::ExtendedUtf8);
+
+        assert!(OffchainMessage::new("").is_err());
+        assert!(OffchainMessage::new(&"a".repeat(MAX_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{bail, Result};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+use crate::solana::programs::{
+    self, ASSOCIATED_TOKEN_PROGRAM_ID, METADATA_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+/// Maximum number of seeds in a program-derived address.
+pub const MAX_SEEDS: usize = 16;
+
+/// Maximum length of a single seed, in bytes.
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Domain separator appended to every PDA hash.
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Whether the bytes decode to a point on the ed25519 curve, i.e. could have a private key.
+pub fn is_on_curve(key: &[u8; 32]) -> bool {
+    CompressedEdwardsY(*key).decompress().is_some()
+}
+
+/// Hash seeds and a program ID into an address, rejecting results that lie on the curve.
+pub fn create_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Result<[u8; 32]> {
+    if seeds.len() > MAX_SEEDS {
+        bail!("Too many seeds: {} (max {})", seeds.len(), MAX_SEEDS);
+    }
+    if let Some(seed) = seeds.iter().find(|seed| seed.len() > MAX_SEED_LEN) {
+        bail!("Seed is {} bytes long (max {})", seed.len(), MAX_SEED_LEN);
+    }
+
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+    let address: [u8; 32] = hasher.finalize().into();
+
+    if is_on_curve(&address) {
+        bail!("Seeds produce an address on the ed25519 curve");
+    }
+    Ok(address)
+}
+
+/// Find the canonical program-derived address: the first off-curve address
+/// found when appending a bump seed counting down from 255.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Result<([u8; 32], u8)> {
+    // Leave room for the bump seed.
+    if seeds.len() >= MAX_SEEDS {
+        bail!(
+            "Too many seeds: {} (max {} plus a bump)",
+            seeds.len(),
+            MAX_SEEDS - 1
+        );
+    }
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut with_bump = seeds.to_vec();
+        with_bump.push(&bump_seed);
+        if let Ok(address) = create_program_address(&with_bump, program_id) {
+            return Ok((address, bump));
+        }
+    }
+    bail!("No valid bump seed found for program address")
+}
+
+/// Derive the associated token account holding `mint` tokens for `owner`.
+pub fn associated_token_address(owner: &[u8; 32], mint: &[u8; 32]) -> [u8; 32] {
+    let token_program = programs::program_id(TOKEN_PROGRAM_ID);
+    find_program_address(
+        &[owner, &token_program, mint],
+        &programs::program_id(ASSOCIATED_TOKEN_PROGRAM_ID),
+    )
+    .expect("associated token seeds are within PDA limits")
+    .0
+}
+
+/// Derive the Metaplex Token Metadata account for `mint`.
+pub fn metadata_address(mint: &[u8; 32]) -> [u8; 32] {
+    let metadata_program = programs::program_id(METADATA_PROGRAM_ID);
+    find_program_address(&[b"metadata", &metadata_program, mint], &metadata_program)
+        .expect("metadata seeds are within PDA limits")
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Expected values from `Pubkey::find_program_address` in solana-sdk 2.2.
+    #[test]
+    fn test_find_program_address_matches_solana_sdk() {
+        let (address, bump) = find_program_address(&[b"frog"], &[0x22; 32]).unwrap();
+        assert_eq!(
+            bs58::encode(address).into_string(),
+            "979VDW7Wv5eJ4ufeJ64e9dKSVoDbEoJmyEwPfFmqVzUS"
+        );
+        assert_eq!(bump, 254);
+    }
+
+    #[test]
+    fn test_associated_token_address_matches_solana_sdk() {
+        assert_eq!(
+            bs58::encode(associated_token_address(&[0x44; 32], &[0x11; 32])).into_string(),
+            "AkzQgbq7wuwUcLU59hgo8ehfbnEw4kxFfxvioHLKwven"
+        );
+    }
+
+    #[test]
+    fn test_metadata_address_matches_solana_sdk() {
+        assert_eq!(
+            bs58::encode(metadata_address(&[0x11; 32])).into_string(),
+            "EpqE2tvAEF2oDquuRL8pXN6kdPFXHEAGPFK3i6jkL8xy"
+        );
+    }
+
+    #[test]
+    fn test_create_program_address_rejects_on_curve() {
+        // Bump 255 lands on the curve for these seeds, which is why 254 is canonical.
+        assert!(create_program_address(&[b"frog", &[255]], &[0x22; 32]).is_err());
+        assert!(create_program_address(&[&[0u8; 33]], &[0x22; 32]).is_err());
+    }
+
+    #[test]
+    fn test_is_on_curve() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        assert!(is_on_curve(signing_key.verifying_key().as_bytes()));
+        let (address, _) = find_program_address(&[b"frog"], &[0x22; 32]).unwrap();
+        assert!(!is_on_curve(&address));
+    }
+}
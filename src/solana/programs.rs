@@ -0,0 +1,45 @@
+use crate::solana::keypair;
+
+/// Native System program.
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+/// Native ComputeBudget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+/// SPL Token program.
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Associated Token Account program.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Metaplex Token Metadata program.
+pub const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// pump.fun bonding curve program.
+pub const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+/// RecentBlockhashes sysvar, read by the System program when advancing a nonce.
+pub const RECENT_BLOCKHASHES_SYSVAR_ID: &str = "SysvarRecentB1ockHashes11111111111111111111";
+
+/// Display names of the programs above, used when printing transactions.
+const PROGRAM_NAMES: [(&str, &str); 6] = [
+    (SYSTEM_PROGRAM_ID, "System"),
+    (COMPUTE_BUDGET_PROGRAM_ID, "ComputeBudget"),
+    (TOKEN_PROGRAM_ID, "SPL Token"),
+    (ASSOCIATED_TOKEN_PROGRAM_ID, "Associated Token"),
+    (METADATA_PROGRAM_ID, "Metaplex Token Metadata"),
+    (PUMP_FUN_PROGRAM_ID, "pump.fun"),
+];
+
+/// Decode one of the program ID constants above into raw bytes.
+pub fn program_id(address: &str) -> [u8; 32] {
+    keypair::decode_pubkey(address).expect("well-known program IDs are valid base58")
+}
+
+/// Name of a well-known program, or `None` for any other account.
+pub fn program_name(key: &[u8; 32]) -> Option<&'static str> {
+    PROGRAM_NAMES
+        .iter()
+        .find(|(id, _)| program_id(id) == *key)
+        .map(|(_, name)| *name)
+}
@@ -0,0 +1,703 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use futures_util::{SinkExt, Stream, StreamExt};
+use log::{debug, warn};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::config::network::Commitment;
+use crate::solana::connection::TransactionError;
+
+/// Delay before the first reconnection attempt; doubles on every failure.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(250);
+
+/// Upper bound on the delay between reconnection attempts.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A transaction reached the subscribed commitment.
+#[derive(Debug, Clone)]
+pub struct SignatureNotification {
+    pub slot: u64,
+    pub err: Option<TransactionError>,
+}
+
+impl SignatureNotification {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(Self {
+            slot: value["context"]["slot"].as_u64()?,
+            // "receivedSignature" notifications carry a string instead of an object.
+            err: TransactionError::from_value(value["value"].as_object()?.get("err")?),
+        })
+    }
+}
+
+/// New state of a watched account.
+#[derive(Debug, Clone)]
+pub struct AccountNotification {
+    pub slot: u64,
+    pub lamports: u64,
+    pub owner: String,
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+impl AccountNotification {
+    fn from_value(value: &Value) -> Option<Self> {
+        let account = &value["value"];
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(account["data"][0].as_str()?)
+            .ok()?;
+        Some(Self {
+            slot: value["context"]["slot"].as_u64()?,
+            lamports: account["lamports"].as_u64()?,
+            owner: account["owner"].as_str()?.to_string(),
+            data,
+            executable: account["executable"].as_bool().unwrap_or(false),
+        })
+    }
+}
+
+/// Logs emitted by a transaction matching a logs subscription.
+#[derive(Debug, Clone)]
+pub struct LogsNotification {
+    pub slot: u64,
+    pub signature: String,
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+}
+
+impl LogsNotification {
+    fn from_value(value: &Value) -> Option<Self> {
+        let entry = &value["value"];
+        Some(Self {
+            slot: value["context"]["slot"].as_u64()?,
+            signature: entry["signature"].as_str()?.to_string(),
+            err: TransactionError::from_value(&entry["err"]),
+            logs: entry["logs"]
+                .as_array()?
+                .iter()
+                .filter_map(|l| l.as_str().map(str::to_string))
+                .collect(),
+        })
+    }
+}
+
+/// Which transactions a logs subscription reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogsFilter {
+    /// All transactions except simple vote transactions.
+    All,
+    /// All transactions including simple vote transactions.
+    AllWithVotes,
+    /// Transactions that mention the given address.
+    Mentions(String),
+}
+
+impl LogsFilter {
+    fn to_value(&self) -> Value {
+        match self {
+            LogsFilter::All => json!("all"),
+            LogsFilter::AllWithVotes => json!("allWithVotes"),
+            LogsFilter::Mentions(address) => json!({ "mentions": [address] }),
+        }
+    }
+}
+
+enum Command {
+    Subscribe {
+        method: &'static str,
+        unsubscribe_method: &'static str,
+        params: Value,
+        notifications: mpsc::UnboundedSender<Value>,
+        ack: oneshot::Sender<Result<u64>>,
+    },
+    Unsubscribe(u64),
+}
+
+/// Stream of notifications for one subscription.
+///
+/// Dropping the stream unsubscribes on the server. The stream ends when the
+/// owning [`PubsubClient`] is dropped, or after the single notification of a
+/// signature subscription.
+pub struct Subscription<T> {
+    id: u64,
+    notifications: mpsc::UnboundedReceiver<Value>,
+    commands: mpsc::UnboundedSender<Command>,
+    parse: fn(&Value) -> Option<T>,
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match this.notifications.poll_recv(cx) {
+                Poll::Ready(Some(value)) => match (this.parse)(&value) {
+                    Some(notification) => return Poll::Ready(Some(notification)),
+                    None => warn!("Ignoring malformed notification: {}", value),
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        let _ = self.commands.send(Command::Unsubscribe(self.id));
+    }
+}
+
+/// Client for the Solana JSON-RPC pubsub (WebSocket) API.
+///
+/// A background task owns the socket. When the connection drops it reconnects
+/// with exponential backoff and re-issues every live subscription, so streams
+/// handed out by this client survive transient network failures.
+pub struct PubsubClient {
+    commands: mpsc::UnboundedSender<Command>,
+    task: JoinHandle<()>,
+}
+
+impl PubsubClient {
+    /// Connect to a pubsub endpoint such as `wss://api.mainnet-beta.solana.com`.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let socket = open(url).await?;
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run(url.to_string(), socket, receiver));
+        Ok(Self { commands, task })
+    }
+
+    /// Notify once when a transaction reaches `commitment`.
+    pub async fn signature_subscribe(
+        &self,
+        signature: &str,
+        commitment: Commitment,
+    ) -> Result<Subscription<SignatureNotification>> {
+        self.subscribe(
+            "signatureSubscribe",
+            "signatureUnsubscribe",
+            json!([signature, { "commitment": commitment.as_str() }]),
+            SignatureNotification::from_value,
+        )
+        .await
+    }
+
+    /// Notify whenever the lamports or data of an account change.
+    pub async fn account_subscribe(
+        &self,
+        address: &str,
+        commitment: Commitment,
+    ) -> Result<Subscription<AccountNotification>> {
+        self.subscribe(
+            "accountSubscribe",
+            "accountUnsubscribe",
+            json!([address, { "encoding": "base64", "commitment": commitment.as_str() }]),
+            AccountNotification::from_value,
+        )
+        .await
+    }
+
+    /// Notify with the logs of every transaction matching `filter`.
+    pub async fn logs_subscribe(
+        &self,
+        filter: LogsFilter,
+        commitment: Commitment,
+    ) -> Result<Subscription<LogsNotification>> {
+        self.subscribe(
+            "logsSubscribe",
+            "logsUnsubscribe",
+            json!([filter.to_value(), { "commitment": commitment.as_str() }]),
+            LogsNotification::from_value,
+        )
+        .await
+    }
+
+    /// Register a subscription and wait for the server to acknowledge it.
+    async fn subscribe<T>(
+        &self,
+        method: &'static str,
+        unsubscribe_method: &'static str,
+        params: Value,
+        parse: fn(&Value) -> Option<T>,
+    ) -> Result<Subscription<T>> {
+        let (notifications_tx, notifications) = mpsc::unbounded_channel();
+        let (ack, acked) = oneshot::channel();
+        self.commands
+            .send(Command::Subscribe {
+                method,
+                unsubscribe_method,
+                params,
+                notifications: notifications_tx,
+                ack,
+            })
+            .map_err(|_| anyhow!("Pubsub connection task has stopped"))?;
+
+        let id = acked
+            .await
+            .map_err(|_| anyhow!("Pubsub connection task has stopped"))??;
+
+        Ok(Subscription {
+            id,
+            notifications,
+            commands: self.commands.clone(),
+            parse,
+        })
+    }
+}
+
+impl Drop for PubsubClient {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn open(url: &str) -> Result<WsStream> {
+    let (socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .context(format!("Failed to connect to {}", url))?;
+    debug!("Connected to pubsub endpoint {}", url);
+    Ok(socket)
+}
+
+/// A subscription as tracked by the connection task.
+struct Entry {
+    method: &'static str,
+    unsubscribe_method: &'static str,
+    params: Value,
+    notifications: mpsc::UnboundedSender<Value>,
+    ack: Option<oneshot::Sender<Result<u64>>>,
+    server_id: Option<u64>,
+}
+
+/// Bookkeeping that maps client-side subscription IDs to the IDs issued by
+/// the server, which change on every reconnection.
+#[derive(Default)]
+struct Subscriptions {
+    next_id: u64,
+    next_request_id: u64,
+    entries: HashMap<u64, Entry>,
+    pending: HashMap<u64, u64>,
+    by_server_id: HashMap<u64, u64>,
+}
+
+impl Subscriptions {
+    fn insert(&mut self, entry: Entry) -> u64 {
+        self.next_id += 1;
+        self.entries.insert(self.next_id, entry);
+        self.next_id
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> (u64, String) {
+        self.next_request_id += 1;
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_request_id,
+            "method": method,
+            "params": params,
+        });
+        (self.next_request_id, body.to_string())
+    }
+
+    /// Build the subscribe request for an entry and remember which entry it belongs to.
+    fn subscribe_request(&mut self, id: u64) -> Option<String> {
+        let entry = self.entries.get(&id)?;
+        let (method, params) = (entry.method, entry.params.clone());
+        let (request_id, body) = self.request(method, params);
+        self.pending.insert(request_id, id);
+        Some(body)
+    }
+
+    /// Forget an entry, returning the unsubscribe request to send if the server knows it.
+    fn remove(&mut self, id: u64) -> Option<String> {
+        let entry = self.entries.remove(&id)?;
+        let server_id = entry.server_id?;
+        self.by_server_id.remove(&server_id);
+        Some(self.request(entry.unsubscribe_method, json!([server_id])).1)
+    }
+
+    /// Start over on a fresh connection: server IDs are void and every entry must be re-sent.
+    fn reset(&mut self) -> Vec<String> {
+        self.pending.clear();
+        self.by_server_id.clear();
+        let mut ids: Vec<u64> = self.entries.keys().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| {
+                self.entries.get_mut(&id)?.server_id = None;
+                self.subscribe_request(id)
+            })
+            .collect()
+    }
+
+    /// Route one incoming message, returning any requests that should be sent in reply.
+    fn handle(&mut self, message: &Value) -> Vec<String> {
+        if let Some(request_id) = message["id"].as_u64() {
+            return self.handle_response(request_id, message);
+        }
+
+        let params = &message["params"];
+        let Some(id) = params["subscription"]
+            .as_u64()
+            .and_then(|server_id| self.by_server_id.get(&server_id).copied())
+        else {
+            return Vec::new();
+        };
+
+        let delivered = self.entries[&id]
+            .notifications
+            .send(params["result"].clone())
+            .is_ok();
+        if message["method"] == "signatureNotification" {
+            // The server drops signature subscriptions after their only notification.
+            self.entries.remove(&id);
+            self.by_server_id.retain(|_, local| *local != id);
+            return Vec::new();
+        }
+        if delivered {
+            Vec::new()
+        } else {
+            self.remove(id).into_iter().collect()
+        }
+    }
+
+    fn handle_response(&mut self, request_id: u64, message: &Value) -> Vec<String> {
+        // Responses to unsubscribe requests are not tracked.
+        let Some(id) = self.pending.remove(&request_id) else {
+            return Vec::new();
+        };
+        let Some(entry) = self.entries.get_mut(&id) else {
+            return Vec::new();
+        };
+
+        match message["result"].as_u64() {
+            Some(server_id) => {
+                entry.server_id = Some(server_id);
+                if let Some(ack) = entry.ack.take() {
+                    let _ = ack.send(Ok(id));
+                }
+                self.by_server_id.insert(server_id, id);
+            }
+            None => {
+                let error = anyhow!("{} rejected: {}", entry.method, message["error"]);
+                if let Some(entry) = self.entries.remove(&id) {
+                    match entry.ack {
+                        Some(ack) => {
+                            let _ = ack.send(Err(error));
+                        }
+                        None => warn!("{}", error),
+                    }
+                }
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Connection task: forwards commands to the socket and notifications to
+/// subscribers, reconnecting until the client is dropped.
+async fn run(url: String, socket: WsStream, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut subscriptions = Subscriptions::default();
+    let mut socket = Some(socket);
+    let mut delay = INITIAL_RECONNECT_DELAY;
+
+    loop {
+        let mut ws = match socket.take() {
+            Some(ws) => ws,
+            None => match open(&url).await {
+                Ok(ws) => ws,
+                Err(e) => {
+                    warn!("{}; retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                    continue;
+                }
+            },
+        };
+        delay = INITIAL_RECONNECT_DELAY;
+
+        let mut outgoing = subscriptions.reset();
+        'connection: loop {
+            for body in outgoing.drain(..) {
+                if let Err(e) = ws.send(WsMessage::Text(body)).await {
+                    warn!("Pubsub send failed: {}", e);
+                    break 'connection;
+                }
+            }
+
+            tokio::select! {
+                command = commands.recv() => match command {
+                    None => {
+                        let _ = ws.close(None).await;
+                        return;
+                    }
+                    Some(Command::Subscribe { method, unsubscribe_method, params, notifications, ack }) => {
+                        let id = subscriptions.insert(Entry {
+                            method,
+                            unsubscribe_method,
+                            params,
+                            notifications,
+                            ack: Some(ack),
+                            server_id: None,
+                        });
+                        outgoing.extend(subscriptions.subscribe_request(id));
+                    }
+                    Some(Command::Unsubscribe(id)) => outgoing.extend(subscriptions.remove(id)),
+                },
+                message = ws.next() => match message {
+                    Some(Ok(WsMessage::Text(text))) => match serde_json::from_str::<Value>(&text) {
+                        Ok(value) => outgoing.extend(subscriptions.handle(&value)),
+                        Err(e) => warn!("Ignoring unparseable pubsub message: {}", e),
+                    },
+                    Some(Ok(WsMessage::Close(_))) | None => break 'connection,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!("Pubsub connection error: {}", e);
+                        break 'connection;
+                    }
+                },
+            }
+        }
+
+        warn!("Pubsub connection to {} lost; reconnecting", url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    type ServerSocket = WebSocketStream<TcpStream>;
+
+    async fn mock_server() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    async fn accept(listener: &TcpListener) -> ServerSocket {
+        let (stream, _) = listener.accept().await.unwrap();
+        tokio_tungstenite::accept_async(stream).await.unwrap()
+    }
+
+    async fn recv_json(ws: &mut ServerSocket) -> Value {
+        loop {
+            match ws.next().await.unwrap().unwrap() {
+                WsMessage::Text(text) => return serde_json::from_str(&text).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    async fn send_json(ws: &mut ServerSocket, value: Value) {
+        ws.send(WsMessage::Text(value.to_string())).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_account_subscription_round_trip() {
+        let (listener, url) = mock_server().await;
+        let server = tokio::spawn(async move {
+            let mut ws = accept(&listener).await;
+            let request = recv_json(&mut ws).await;
+            assert_eq!(request["method"], "accountSubscribe");
+            assert_eq!(request["params"][1]["encoding"], "base64");
+            send_json(
+                &mut ws,
+                json!({"jsonrpc": "2.0", "result": 7, "id": request["id"]}),
+            )
+            .await;
+            send_json(
+                &mut ws,
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "accountNotification",
+                    "params": {
+                        "subscription": 7,
+                        "result": {
+                            "context": {"slot": 310},
+                            "value": {
+                                "lamports": 2_039_280,
+                                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                                "data": ["AQID", "base64"],
+                                "executable": false,
+                                "rentEpoch": 0
+                            }
+                        }
+                    }
+                }),
+            )
+            .await;
+            let unsubscribe = recv_json(&mut ws).await;
+            assert_eq!(unsubscribe["method"], "accountUnsubscribe");
+            assert_eq!(unsubscribe["params"], json!([7]));
+        });
+
+        let client = PubsubClient::connect(&url).await.unwrap();
+        let mut subscription = client
+            .account_subscribe(
+                "So11111111111111111111111111111111111111112",
+                Commitment::Confirmed,
+            )
+            .await
+            .unwrap();
+        let notification = subscription.next().await.unwrap();
+        assert_eq!(notification.slot, 310);
+        assert_eq!(notification.lamports, 2_039_280);
+        assert_eq!(notification.data, vec![1, 2, 3]);
+        drop(subscription);
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resubscribes_after_reconnect() {
+        let (listener, url) = mock_server().await;
+        let server = tokio::spawn(async move {
+            let mut ws = accept(&listener).await;
+            let request = recv_json(&mut ws).await;
+            assert_eq!(request["method"], "logsSubscribe");
+            send_json(
+                &mut ws,
+                json!({"jsonrpc": "2.0", "result": 1, "id": request["id"]}),
+            )
+            .await;
+            ws.close(None).await.unwrap();
+
+            let mut ws = accept(&listener).await;
+            let request = recv_json(&mut ws).await;
+            assert_eq!(request["method"], "logsSubscribe");
+            assert_eq!(request["params"][0], json!({"mentions": ["Mint111"]}));
+            send_json(
+                &mut ws,
+                json!({"jsonrpc": "2.0", "result": 2, "id": request["id"]}),
+            )
+            .await;
+            send_json(
+                &mut ws,
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "logsNotification",
+                    "params": {
+                        "subscription": 2,
+                        "result": {
+                            "context": {"slot": 44},
+                            "value": {
+                                "signature": "5h6xBEauJ3PK6SWC",
+                                "err": null,
+                                "logs": ["Program log: Instruction: Buy"]
+                            }
+                        }
+                    }
+                }),
+            )
+            .await;
+            // Keep the socket open until the client has read the notification.
+            let _ = ws.next().await;
+        });
+
+        let client = PubsubClient::connect(&url).await.unwrap();
+        let mut subscription = client
+            .logs_subscribe(
+                LogsFilter::Mentions("Mint111".to_string()),
+                Commitment::Processed,
+            )
+            .await
+            .unwrap();
+        let notification = subscription.next().await.unwrap();
+        assert_eq!(notification.slot, 44);
+        assert_eq!(notification.signature, "5h6xBEauJ3PK6SWC");
+        assert_eq!(notification.logs, vec!["Program log: Instruction: Buy"]);
+        drop(subscription);
+        drop(client);
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signature_subscription_ends_after_notification() {
+        let (listener, url) = mock_server().await;
+        let server = tokio::spawn(async move {
+            let mut ws = accept(&listener).await;
+            let request = recv_json(&mut ws).await;
+            assert_eq!(request["params"][1]["commitment"], "finalized");
+            send_json(
+                &mut ws,
+                json!({"jsonrpc": "2.0", "result": 3, "id": request["id"]}),
+            )
+            .await;
+            send_json(
+                &mut ws,
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "signatureNotification",
+                    "params": {
+                        "subscription": 3,
+                        "result": {
+                            "context": {"slot": 99},
+                            "value": {"err": {"InstructionError": [0, {"Custom": 6001}]}}
+                        }
+                    }
+                }),
+            )
+            .await;
+            let _ = ws.next().await;
+        });
+
+        let client = PubsubClient::connect(&url).await.unwrap();
+        let mut subscription = client
+            .signature_subscribe("5h6xBEauJ3PK6SWC", Commitment::Finalized)
+            .await
+            .unwrap();
+        let notification = subscription.next().await.unwrap();
+        assert_eq!(notification.slot, 99);
+        assert_eq!(
+            notification.err.unwrap().to_string(),
+            "instruction 0 failed: Custom(6001)"
+        );
+        assert!(subscription.next().await.is_none());
+        drop(client);
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_subscription_returns_error() {
+        let (listener, url) = mock_server().await;
+        let server = tokio::spawn(async move {
+            let mut ws = accept(&listener).await;
+            let request = recv_json(&mut ws).await;
+            send_json(
+                &mut ws,
+                json!({
+                    "jsonrpc": "2.0",
+                    "error": {"code": -32602, "message": "Invalid params"},
+                    "id": request["id"]
+                }),
+            )
+            .await;
+            let _ = ws.next().await;
+        });
+
+        let client = PubsubClient::connect(&url).await.unwrap();
+        let result = client
+            .account_subscribe("not-a-key", Commitment::Confirmed)
+            .await;
+        assert!(result.is_err());
+        drop(client);
+
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,741 @@
+use anyhow::{bail, Context, Result};
+
+use crate::solana::programs::{self, PUMP_FUN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::solana::transaction::{AccountMeta, ByteReader, Instruction};
+use crate::solana::{keypair, pda};
+
+/// Lamports in one SOL.
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Seed of the program's `Global` configuration account.
+pub const GLOBAL_SEED: &[u8] = b"global";
+
+/// Seed prefix of the bonding-curve PDA, followed by the mint address.
+pub const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+
+/// Seed prefix of the vault collecting creator fees, followed by the creator.
+pub const CREATOR_VAULT_SEED: &[u8] = b"creator-vault";
+
+/// Seed of the Anchor event authority the program emits trade events through.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Decimals of every token minted through pump.fun.
+pub const TOKEN_DECIMALS: u8 = 6;
+
+/// Tokens a fresh curve can sell before it graduates (793.1M with 6 decimals).
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+const BASIS_POINTS: u64 = 10_000;
+
+/// Error for a quote whose amounts do not fit the program's `u64` math.
+const OVERFLOW: &str = "Amount is too large for the bonding curve";
+
+/// Anchor account discriminator of `Global` (`sha256("account:Global")[..8]`).
+const GLOBAL_DISCRIMINATOR: [u8; 8] = [167, 232, 232, 177, 200, 108, 114, 127];
+
+/// Anchor account discriminator of `BondingCurve` (`sha256("account:BondingCurve")[..8]`).
+const BONDING_CURVE_DISCRIMINATOR: [u8; 8] = [23, 183, 248, 55, 96, 216, 172, 96];
+
+/// Anchor instruction discriminator of `create` (`sha256("global:create")[..8]`).
+pub const CREATE_DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+
+/// Anchor instruction discriminator of `buy`.
+pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+
+/// Anchor instruction discriminator of `sell`.
+pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+
+/// Derive the program's `Global` configuration account.
+pub fn global_address() -> [u8; 32] {
+    let program_id = programs::program_id(PUMP_FUN_PROGRAM_ID);
+    pda::find_program_address(&[GLOBAL_SEED], &program_id)
+        .expect("global seed is within PDA limits")
+        .0
+}
+
+/// Derive the bonding-curve account of a pump.fun mint.
+pub fn bonding_curve_address(mint: &[u8; 32]) -> [u8; 32] {
+    let program_id = programs::program_id(PUMP_FUN_PROGRAM_ID);
+    pda::find_program_address(&[BONDING_CURVE_SEED, mint], &program_id)
+        .expect("bonding-curve seeds are within PDA limits")
+        .0
+}
+
+/// Derive the vault that collects creator fees for `creator`.
+pub fn creator_vault_address(creator: &[u8; 32]) -> [u8; 32] {
+    let program_id = programs::program_id(PUMP_FUN_PROGRAM_ID);
+    pda::find_program_address(&[CREATOR_VAULT_SEED, creator], &program_id)
+        .expect("creator vault seeds are within PDA limits")
+        .0
+}
+
+/// Derive the program's event authority.
+pub fn event_authority_address() -> [u8; 32] {
+    let program_id = programs::program_id(PUMP_FUN_PROGRAM_ID);
+    pda::find_program_address(&[EVENT_AUTHORITY_SEED], &program_id)
+        .expect("event authority seed is within PDA limits")
+        .0
+}
+
+/// Derive the bonding-curve account of a base58 mint address.
+pub fn bonding_curve_for_mint(mint: &str) -> Result<[u8; 32]> {
+    let mint = keypair::decode_pubkey(mint).context("Invalid mint address")?;
+    Ok(bonding_curve_address(&mint))
+}
+
+/// Program-wide pump.fun configuration.
+///
+/// Only the leading fields are decoded; later program versions append more.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Global {
+    pub initialized: bool,
+    pub authority: [u8; 32],
+    /// Account that receives the protocol fee on every trade.
+    pub fee_recipient: [u8; 32],
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+}
+
+impl Global {
+    /// Decode the raw data of the `Global` account.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(data);
+        if reader.read_bytes(8)? != GLOBAL_DISCRIMINATOR {
+            bail!("Account is not the pump.fun global config");
+        }
+        Ok(Self {
+            initialized: reader.read_u8()? != 0,
+            authority: reader.read_pubkey()?,
+            fee_recipient: reader.read_pubkey()?,
+            initial_virtual_token_reserves: reader.read_u64()?,
+            initial_virtual_sol_reserves: reader.read_u64()?,
+            initial_real_token_reserves: reader.read_u64()?,
+            token_total_supply: reader.read_u64()?,
+            fee_basis_points: reader.read_u64()?,
+        })
+    }
+}
+
+/// Result of quoting a buy against a bonding curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Lamports the buy costs, fee included.
+    pub sol_in: u64,
+    /// Token units received.
+    pub tokens_out: u64,
+    /// Protocol fee in lamports.
+    pub fee: u64,
+    /// How much worse than the spot price the trade executes, before fees.
+    pub price_impact_bps: u64,
+}
+
+impl BuyQuote {
+    /// Most the buy may cost once the curve moves by up to `slippage_bps` against it.
+    pub fn max_sol_cost(&self, slippage_bps: u64) -> u64 {
+        // A limit beyond any balance is no limit at all, so it saturates.
+        mul_div(
+            self.sol_in,
+            BASIS_POINTS.saturating_add(slippage_bps),
+            BASIS_POINTS,
+        )
+        .unwrap_or(u64::MAX)
+    }
+}
+
+/// Result of quoting a sell against a bonding curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SellQuote {
+    /// Token units sold.
+    pub tokens_in: u64,
+    /// Lamports received, after the fee.
+    pub sol_out: u64,
+    /// Protocol fee in lamports.
+    pub fee: u64,
+    /// How much worse than the spot price the trade executes, before fees.
+    pub price_impact_bps: u64,
+}
+
+impl SellQuote {
+    /// Least the sell may return once the curve moves by up to `slippage_bps` against it.
+    pub fn min_sol_output(&self, slippage_bps: u64) -> u64 {
+        mul_div(
+            self.sol_out,
+            BASIS_POINTS.saturating_sub(slippage_bps),
+            BASIS_POINTS,
+        )
+        .expect("at most sol_out")
+    }
+}
+
+/// State of a pump.fun bonding curve.
+///
+/// Trades are priced against the virtual reserves; the real reserves track
+/// what the curve actually holds. Token amounts use [`TOKEN_DECIMALS`] and SOL
+/// amounts are in lamports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BondingCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    /// Set once the curve has sold out and liquidity has migrated.
+    pub complete: bool,
+    /// Creator recorded by newer versions of the program.
+    pub creator: Option<[u8; 32]>,
+}
+
+impl BondingCurve {
+    /// The curve `create` sets up, used to quote a buy in the launch transaction itself.
+    pub fn initial(global: &Global, creator: [u8; 32]) -> Self {
+        Self {
+            virtual_token_reserves: global.initial_virtual_token_reserves,
+            virtual_sol_reserves: global.initial_virtual_sol_reserves,
+            real_token_reserves: global.initial_real_token_reserves,
+            real_sol_reserves: 0,
+            token_total_supply: global.token_total_supply,
+            complete: false,
+            creator: Some(creator),
+        }
+    }
+
+    /// Decode the raw data of a bonding-curve account.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(data);
+        if reader.read_bytes(8)? != BONDING_CURVE_DISCRIMINATOR {
+            bail!("Account is not a pump.fun bonding curve");
+        }
+        let virtual_token_reserves = reader.read_u64()?;
+        let virtual_sol_reserves = reader.read_u64()?;
+        let real_token_reserves = reader.read_u64()?;
+        let real_sol_reserves = reader.read_u64()?;
+        let token_total_supply = reader.read_u64()?;
+        let complete = reader.read_u8()? != 0;
+        let creator = if reader.remaining() >= 32 {
+            Some(reader.read_pubkey()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            virtual_token_reserves,
+            virtual_sol_reserves,
+            real_token_reserves,
+            real_sol_reserves,
+            token_total_supply,
+            complete,
+            creator,
+        })
+    }
+
+    /// Spot price of one whole token, in SOL.
+    pub fn price_sol(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        let sol = self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL as f64;
+        let tokens = self.virtual_token_reserves as f64 / token_scale();
+        sol / tokens
+    }
+
+    /// Fully diluted market cap at the spot price, in SOL.
+    pub fn market_cap_sol(&self) -> f64 {
+        self.price_sol() * self.token_total_supply as f64 / token_scale()
+    }
+
+    /// How far the curve is towards graduating, from 0 to 100.
+    pub fn progress_percent(&self) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+        let sold = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(self.real_token_reserves);
+        sold as f64 * 100.0 / INITIAL_REAL_TOKEN_RESERVES as f64
+    }
+
+    /// Quote spending `sol_in` lamports, fee included, on the curve.
+    ///
+    /// Mirrors the program's integer math: the fee is taken off the input,
+    /// tokens come out of the constant product `virtual_sol * virtual_token`,
+    /// and the cost is then recomputed for that token amount the way the
+    /// `buy` instruction charges it. Buys larger than the remaining real
+    /// reserves are capped and cost correspondingly less.
+    pub fn quote_buy(&self, sol_in: u64, fee_basis_points: u64) -> Result<BuyQuote> {
+        self.ensure_tradable()?;
+        if sol_in == 0 {
+            bail!("Buy amount must be greater than zero");
+        }
+
+        let net = mul_div(
+            sol_in,
+            BASIS_POINTS,
+            BASIS_POINTS
+                .checked_add(fee_basis_points)
+                .context(OVERFLOW)?,
+        )?;
+        let tokens_out = mul_div(
+            net,
+            self.virtual_token_reserves,
+            self.virtual_sol_reserves
+                .checked_add(net)
+                .context(OVERFLOW)?,
+        )?
+        .min(self.real_token_reserves);
+        if tokens_out == 0 {
+            bail!("Buy amount is too small to receive any tokens");
+        }
+
+        let cost = self.buy_cost(tokens_out)?;
+        let fee = fee_for(cost, fee_basis_points)?;
+        // Spot cost is tokens_out * vsol / vtok; impact is how far the cost is above it.
+        let spot = tokens_out as u128 * self.virtual_sol_reserves as u128;
+        let paid = cost as u128 * self.virtual_token_reserves as u128;
+        Ok(BuyQuote {
+            sol_in: cost.checked_add(fee).context(OVERFLOW)?,
+            tokens_out,
+            fee,
+            price_impact_bps: impact_bps(spot, paid.saturating_sub(spot)),
+        })
+    }
+
+    /// Quote selling `tokens_in` token units back to the curve.
+    pub fn quote_sell(&self, tokens_in: u64, fee_basis_points: u64) -> Result<SellQuote> {
+        self.ensure_tradable()?;
+        if tokens_in == 0 {
+            bail!("Sell amount must be greater than zero");
+        }
+
+        let gross = mul_div(
+            tokens_in,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves
+                .checked_add(tokens_in)
+                .context(OVERFLOW)?,
+        )?;
+        if gross > self.real_sol_reserves {
+            bail!("Sell exceeds the SOL held by the bonding curve");
+        }
+        let fee = fee_for(gross, fee_basis_points)?;
+        let spot = tokens_in as u128 * self.virtual_sol_reserves as u128;
+        let received = gross as u128 * self.virtual_token_reserves as u128;
+        Ok(SellQuote {
+            tokens_in,
+            sol_out: gross
+                .checked_sub(fee)
+                .context("Sell fee exceeds the proceeds")?,
+            fee,
+            price_impact_bps: impact_bps(spot, spot.saturating_sub(received)),
+        })
+    }
+
+    /// Lamports the `buy` instruction charges for `tokens`, before the fee.
+    fn buy_cost(&self, tokens: u64) -> Result<u64> {
+        let remaining = self
+            .virtual_token_reserves
+            .checked_sub(tokens)
+            .filter(|remaining| *remaining > 0)
+            .context("Buy would empty the bonding curve's virtual token reserves")?;
+        mul_div(tokens, self.virtual_sol_reserves, remaining)?
+            .checked_add(1)
+            .context(OVERFLOW)
+    }
+
+    fn ensure_tradable(&self) -> Result<()> {
+        if self.complete {
+            bail!("Bonding curve is complete; the token now trades on its AMM pool");
+        }
+        if self.virtual_token_reserves == 0 || self.virtual_sol_reserves == 0 {
+            bail!("Bonding curve has no reserves");
+        }
+        Ok(())
+    }
+}
+
+/// `a * b / c` in 128-bit arithmetic, rounded down.
+fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    if c == 0 {
+        bail!("Bonding curve has no reserves");
+    }
+    u64::try_from(a as u128 * b as u128 / c as u128).context(OVERFLOW)
+}
+
+/// Protocol fee on `lamports`, rounded up like the program does.
+fn fee_for(lamports: u64, fee_basis_points: u64) -> Result<u64> {
+    u64::try_from((lamports as u128 * fee_basis_points as u128).div_ceil(BASIS_POINTS as u128))
+        .context(OVERFLOW)
+}
+
+fn impact_bps(spot: u128, slippage: u128) -> u64 {
+    if spot == 0 {
+        return 0;
+    }
+    // Saturates only for amounts far beyond any real curve.
+    (slippage.saturating_mul(BASIS_POINTS as u128) / spot).min(u64::MAX as u128) as u64
+}
+
+fn token_scale() -> f64 {
+    10f64.powi(TOKEN_DECIMALS as i32)
+}
+
+/// Accounts shared by the `buy` and `sell` instructions.
+pub struct TradeAccounts {
+    pub mint: [u8; 32],
+    /// Creator recorded on the bonding curve, whose vault receives creator fees.
+    pub creator: [u8; 32],
+    /// Trader paying SOL on buys and receiving it on sells.
+    pub user: [u8; 32],
+    /// Protocol fee recipient from [`Global`].
+    pub fee_recipient: [u8; 32],
+}
+
+impl TradeAccounts {
+    /// Resolve the accounts of a trade by `user` on the curve of `mint`.
+    pub fn new(
+        global: &Global,
+        curve: &BondingCurve,
+        mint: [u8; 32],
+        user: [u8; 32],
+    ) -> Result<Self> {
+        let creator = curve
+            .creator
+            .context("Bonding curve does not record a creator; it predates creator fees")?;
+        Ok(Self {
+            mint,
+            creator,
+            user,
+            fee_recipient: global.fee_recipient,
+        })
+    }
+
+    fn metas(&self, buy: bool) -> Vec<AccountMeta> {
+        let bonding_curve = bonding_curve_address(&self.mint);
+        let creator_vault = AccountMeta::new(creator_vault_address(&self.creator), false);
+        let token_program =
+            AccountMeta::new_readonly(programs::program_id(TOKEN_PROGRAM_ID), false);
+        let mut accounts = vec![
+            AccountMeta::new_readonly(global_address(), false),
+            AccountMeta::new(self.fee_recipient, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(bonding_curve, false),
+            AccountMeta::new(
+                pda::associated_token_address(&bonding_curve, &self.mint),
+                false,
+            ),
+            AccountMeta::new(pda::associated_token_address(&self.user, &self.mint), false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(programs::program_id(SYSTEM_PROGRAM_ID), false),
+        ];
+        // The two instructions list the token program and creator vault in opposite order.
+        if buy {
+            accounts.extend([token_program, creator_vault]);
+        } else {
+            accounts.extend([creator_vault, token_program]);
+        }
+        accounts.extend([
+            AccountMeta::new_readonly(event_authority_address(), false),
+            AccountMeta::new_readonly(programs::program_id(PUMP_FUN_PROGRAM_ID), false),
+        ]);
+        accounts
+    }
+}
+
+/// Creator that a pump.fun `create` instruction records, or `None` for other instructions.
+///
+/// Newer program versions take the creator as an argument after the metadata
+/// strings; older ones record the `user` paying for the launch.
+pub fn create_instruction_creator(ix: &Instruction) -> Option<[u8; 32]> {
+    if ix.program_id != programs::program_id(PUMP_FUN_PROGRAM_ID)
+        || !ix.data.starts_with(&CREATE_DISCRIMINATOR)
+    {
+        return None;
+    }
+    let mut reader = ByteReader::new(&ix.data[8..]);
+    for _ in 0..3 {
+        reader.read_string().ok()?;
+    }
+    if reader.remaining() >= 32 {
+        return reader.read_pubkey().ok();
+    }
+    ix.accounts.get(7).map(|meta| meta.pubkey)
+}
+
+/// pump.fun `buy`: receive exactly `tokens`, paying at most `max_sol_cost` lamports including fees.
+///
+/// The user's associated token account must exist; see
+/// [`create_associated_token_account_idempotent`](crate::solana::transaction::create_associated_token_account_idempotent).
+pub fn buy_instruction(accounts: &TradeAccounts, tokens: u64, max_sol_cost: u64) -> Instruction {
+    let mut data = BUY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&tokens.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    Instruction {
+        program_id: programs::program_id(PUMP_FUN_PROGRAM_ID),
+        accounts: accounts.metas(true),
+        data,
+    }
+}
+
+/// pump.fun `sell`: sell exactly `tokens`, receiving at least `min_sol_output` lamports after fees.
+pub fn sell_instruction(accounts: &TradeAccounts, tokens: u64, min_sol_output: u64) -> Instruction {
+    let mut data = SELL_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&tokens.to_le_bytes());
+    data.extend_from_slice(&min_sol_output.to_le_bytes());
+    Instruction {
+        program_id: programs::program_id(PUMP_FUN_PROGRAM_ID),
+        accounts: accounts.metas(false),
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A curve as created by pump.fun, before any trades.
+    fn fresh_curve_data() -> Vec<u8> {
+        let mut data = BONDING_CURVE_DISCRIMINATOR.to_vec();
+        for value in [
+            1_073_000_000_000_000u64,
+            30_000_000_000,
+            INITIAL_REAL_TOKEN_RESERVES,
+            0,
+            1_000_000_000_000_000,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn test_bonding_curve_address_matches_solana_sdk() {
+        // `Pubkey::find_program_address(&[b"bonding-curve", mint], &pump)` in solana-sdk 2.2.
+        assert_eq!(
+            bs58::encode(bonding_curve_address(&[0x11; 32])).into_string(),
+            "3P8DRyUSauz4yDfNrANMog1xHa2FL1n4Pr5puQSVQFNL"
+        );
+        // The live mainnet config account.
+        assert_eq!(
+            bs58::encode(global_address()).into_string(),
+            "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf"
+        );
+    }
+
+    #[test]
+    fn test_decode_fresh_curve() {
+        let curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+        assert_eq!(curve.virtual_sol_reserves, 30_000_000_000);
+        assert!(!curve.complete);
+        assert_eq!(curve.creator, None);
+        assert_eq!(curve.progress_percent(), 0.0);
+        // 30 SOL / 1.073B tokens
+        assert!((curve.price_sol() - 2.795899e-8).abs() < 1e-13);
+        assert!((curve.market_cap_sol() - 27.95899).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_progress_and_creator() {
+        let mut data = fresh_curve_data();
+        let half_sold = INITIAL_REAL_TOKEN_RESERVES / 2;
+        data[24..32].copy_from_slice(&half_sold.to_le_bytes());
+        data.extend_from_slice(&[0x33; 32]);
+
+        let curve = BondingCurve::deserialize(&data).unwrap();
+        assert!((curve.progress_percent() - 50.0).abs() < 1e-9);
+        assert_eq!(curve.creator, Some([0x33; 32]));
+
+        data[48] = 1;
+        assert_eq!(
+            BondingCurve::deserialize(&data).unwrap().progress_percent(),
+            100.0
+        );
+    }
+
+    #[test]
+    fn test_quote_buy_and_sell_back() {
+        let mut curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+
+        let buy = curve.quote_buy(1_000_000_000, 100).unwrap();
+        assert_eq!(
+            buy,
+            BuyQuote {
+                sol_in: 1_000_000_000,
+                tokens_out: 34_281_150_129_545,
+                fee: 9_900_991,
+                price_impact_bps: 330,
+            }
+        );
+
+        let cost = buy.sol_in - buy.fee;
+        curve.virtual_token_reserves -= buy.tokens_out;
+        curve.real_token_reserves -= buy.tokens_out;
+        curve.virtual_sol_reserves += cost;
+        curve.real_sol_reserves += cost;
+
+        let sell = curve.quote_sell(buy.tokens_out, 100).unwrap();
+        assert_eq!(
+            sell,
+            SellQuote {
+                tokens_in: buy.tokens_out,
+                sol_out: 980_198_017,
+                fee: 9_900_991,
+                price_impact_bps: 319,
+            }
+        );
+    }
+
+    #[test]
+    fn test_quote_buy_caps_at_real_reserves() {
+        let curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+        let buy = curve.quote_buy(1_000 * LAMPORTS_PER_SOL, 100).unwrap();
+        assert_eq!(buy.tokens_out, INITIAL_REAL_TOKEN_RESERVES);
+        assert_eq!(buy.sol_in, 85_855_412_648);
+        assert_eq!(buy.fee, 850_053_591);
+    }
+
+    #[test]
+    fn test_quote_rejects_unusable_trades() {
+        let mut curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+        assert!(curve.quote_buy(0, 100).is_err());
+        assert!(curve.quote_buy(1, 100).is_err());
+        // A fresh curve holds no real SOL to pay sellers.
+        assert!(curve.quote_sell(1_000_000, 100).is_err());
+
+        curve.complete = true;
+        assert!(curve.quote_buy(LAMPORTS_PER_SOL, 100).is_err());
+    }
+
+    #[test]
+    fn test_quote_rejects_overflow_and_degenerate_curves() {
+        let curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+        assert_eq!(
+            curve.quote_buy(u64::MAX, 100).unwrap().tokens_out,
+            INITIAL_REAL_TOKEN_RESERVES
+        );
+        assert!(curve.quote_buy(LAMPORTS_PER_SOL, u64::MAX).is_err());
+        assert!(curve.quote_sell(u64::MAX, 100).is_err());
+        assert!(curve.quote_sell(1_000_000, 20_000).is_err());
+
+        let mut huge = curve.clone();
+        huge.virtual_sol_reserves = u64::MAX;
+        huge.real_sol_reserves = u64::MAX;
+        assert!(huge.quote_buy(u64::MAX, 100).is_err());
+        assert!(huge
+            .quote_sell(u64::MAX - huge.virtual_token_reserves, 100)
+            .is_ok());
+
+        // Real reserves at or above the virtual ones no longer cap the buy, which
+        // must still leave some virtual reserves to divide by.
+        let mut degenerate = curve;
+        degenerate.real_token_reserves = u64::MAX;
+        let buy = degenerate.quote_buy(u64::MAX, 100).unwrap();
+        assert!(buy.tokens_out < degenerate.virtual_token_reserves);
+        assert!(degenerate
+            .buy_cost(degenerate.virtual_token_reserves)
+            .is_err());
+        assert!(degenerate.buy_cost(u64::MAX).is_err());
+        degenerate.virtual_token_reserves = 1;
+        assert!(degenerate.quote_buy(u64::MAX, 100).is_err());
+        assert!(mul_div(1, 1, 0).is_err());
+
+        let buy = BuyQuote {
+            sol_in: u64::MAX,
+            tokens_out: 1,
+            fee: 0,
+            price_impact_bps: 0,
+        };
+        assert_eq!(buy.max_sol_cost(250), u64::MAX);
+        assert_eq!(buy.max_sol_cost(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_slippage_bounds() {
+        let curve = BondingCurve::deserialize(&fresh_curve_data()).unwrap();
+        let buy = curve.quote_buy(LAMPORTS_PER_SOL, 100).unwrap();
+        assert_eq!(buy.max_sol_cost(250), 1_025_000_000);
+        let sell = SellQuote {
+            tokens_in: 1,
+            sol_out: 1_000_000_000,
+            fee: 0,
+            price_impact_bps: 0,
+        };
+        assert_eq!(sell.min_sol_output(250), 975_000_000);
+        assert_eq!(sell.min_sol_output(20_000), 0);
+    }
+
+    #[test]
+    fn test_trade_instruction_accounts() {
+        let accounts = TradeAccounts {
+            mint: [0x11; 32],
+            creator: [0x33; 32],
+            user: [0x44; 32],
+            fee_recipient: [0x55; 32],
+        };
+        let key = |meta: &AccountMeta| bs58::encode(meta.pubkey).into_string();
+
+        let buy = buy_instruction(&accounts, 5, 7);
+        assert_eq!(buy.data[..8], BUY_DISCRIMINATOR);
+        assert_eq!(buy.data[8..16], 5u64.to_le_bytes());
+        assert_eq!(buy.data[16..], 7u64.to_le_bytes());
+        assert_eq!(buy.accounts.len(), 12);
+        assert_eq!(
+            key(&buy.accounts[3]),
+            "3P8DRyUSauz4yDfNrANMog1xHa2FL1n4Pr5puQSVQFNL"
+        );
+        assert_eq!(
+            key(&buy.accounts[5]),
+            "AkzQgbq7wuwUcLU59hgo8ehfbnEw4kxFfxvioHLKwven"
+        );
+        assert!(buy.accounts[6].is_signer && buy.accounts[6].is_writable);
+        assert_eq!(key(&buy.accounts[8]), TOKEN_PROGRAM_ID);
+        // `Pubkey::find_program_address` in solana-sdk 2.2.
+        assert_eq!(
+            key(&buy.accounts[9]),
+            "4C5dma4SK8RTF722U5Gfa7rKW3gKRD22mYQpmNqNezyY"
+        );
+        assert!(buy.accounts[9].is_writable);
+        // The live mainnet event authority.
+        assert_eq!(
+            key(&buy.accounts[10]),
+            "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1"
+        );
+
+        let sell = sell_instruction(&accounts, 5, 7);
+        assert_eq!(sell.data[..8], SELL_DISCRIMINATOR);
+        assert_eq!(key(&sell.accounts[8]), key(&buy.accounts[9]));
+        assert_eq!(key(&sell.accounts[9]), TOKEN_PROGRAM_ID);
+    }
+
+    #[test]
+    fn test_create_instruction_creator() {
+        let mut data = CREATE_DISCRIMINATOR.to_vec();
+        for field in ["Frog", "FROG", "https://example.com/frog.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        let mut create = Instruction {
+            program_id: programs::program_id(PUMP_FUN_PROGRAM_ID),
+            accounts: (0..14)
+                .map(|i| AccountMeta::new_readonly([i; 32], false))
+                .collect(),
+            data,
+        };
+        // Older layout: the paying user is the creator.
+        assert_eq!(create_instruction_creator(&create), Some([7; 32]));
+
+        create.data.extend_from_slice(&[0x33; 32]);
+        assert_eq!(create_instruction_creator(&create), Some([0x33; 32]));
+
+        create.data[0] ^= 0xFF;
+        assert_eq!(create_instruction_creator(&create), None);
+    }
+
+    #[test]
+    fn test_rejects_other_accounts() {
+        let mut data = fresh_curve_data();
+        data[0] ^= 0xFF;
+        assert!(BondingCurve::deserialize(&data).is_err());
+        assert!(BondingCurve::deserialize(&fresh_curve_data()[..20]).is_err());
+    }
+}
@@ -1,30 +1,813 @@
-use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+
+use crate::solana::keypair::SecretKeypair;
+use crate::solana::{pda, programs, pumpfun};
+use crate::utils::display::{format_lamports, format_token_amount};
+
+/// Maximum size of a serialized transaction accepted by the cluster.
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Prefix bit marking a versioned message; the low bits carry the version.
+pub const MESSAGE_VERSION_PREFIX: u8 = 0x80;
+
+/// Size of the metadata header that precedes the addresses in a lookup table account.
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Largest compute unit limit a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 
 /// Metadata describing an account referenced by an instruction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AccountMeta {
     pub pubkey: [u8; 32],
     pub is_signer: bool,
     pub is_writable: bool,
 }
 
+impl AccountMeta {
+    /// A writable account reference.
+    pub fn new(pubkey: [u8; 32], is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    /// A read-only account reference.
+    pub fn new_readonly(pubkey: [u8; 32], is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
 /// A single instruction to be included in a transaction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Instruction {
     pub program_id: [u8; 32],
     pub accounts: Vec<AccountMeta>,
     pub data: Vec<u8>,
 }
 
+/// Header describing how many of a message's account keys sign and which are read-only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// An instruction whose program and accounts are indexes into the message's account keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// A legacy Solana transaction message in its compiled form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub header: MessageHeader,
+    pub account_keys: Vec<[u8; 32]>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+impl Message {
+    /// Compile instructions into a message paid for by `payer`.
+    ///
+    /// Account keys are deduplicated and ordered the same way the Solana SDK
+    /// orders them: the fee payer, then writable signers, read-only signers,
+    /// writable non-signers and read-only non-signers, each group sorted by key.
+    pub fn compile(
+        instructions: &[Instruction],
+        payer: &[u8; 32],
+        recent_blockhash: [u8; 32],
+    ) -> Result<Self> {
+        let (header, account_keys) = CompiledKeys::new(instructions, payer).into_static_keys()?;
+        let instructions = compile_instructions(instructions, &account_keys)?;
+
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+
+    /// Whether the account at `index` must sign the transaction.
+    pub fn is_signer(&self, index: usize) -> bool {
+        index < self.header.num_required_signatures as usize
+    }
+
+    /// Whether the account at `index` is writable.
+    pub fn is_writable(&self, index: usize) -> bool {
+        is_static_key_writable(&self.header, self.account_keys.len(), index)
+    }
+
+    /// The public keys that must sign this message, in signature order.
+    pub fn signer_keys(&self) -> &[[u8; 32]] {
+        signer_keys(&self.header, &self.account_keys)
+    }
+
+    /// Serialize the message into the bytes that signers sign.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_message_body(
+            &self.header,
+            &self.account_keys,
+            &self.recent_blockhash,
+            &self.instructions,
+            &mut out,
+        );
+        out
+    }
+
+    /// Parse a serialized legacy message.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+        let message = Self::read(&mut reader)?;
+        if !reader.is_empty() {
+            bail!(
+                "Unexpected {} trailing bytes after message",
+                reader.remaining()
+            );
+        }
+        Ok(message)
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self> {
+        let (header, account_keys, recent_blockhash, instructions) = read_message_body(reader)?;
+        sanitize(
+            &header,
+            account_keys.len(),
+            account_keys.len(),
+            &instructions,
+        )?;
+
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+}
+
+/// Reference to an address lookup table and the entries a v0 message loads from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageAddressTableLookup {
+    pub account_key: [u8; 32],
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// Addresses loaded from lookup tables, appended after a v0 message's static keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedAddresses {
+    pub writable: Vec<[u8; 32]>,
+    pub readonly: Vec<[u8; 32]>,
+}
+
+impl LoadedAddresses {
+    pub fn len(&self) -> usize {
+        self.writable.len() + self.readonly.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An on-chain address lookup table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressLookupTable {
+    pub key: [u8; 32],
+    pub authority: Option<[u8; 32]>,
+    pub deactivation_slot: u64,
+    pub addresses: Vec<[u8; 32]>,
+}
+
+impl AddressLookupTable {
+    /// An active lookup table with the given addresses, e.g. for building messages offline.
+    pub fn new(key: [u8; 32], addresses: Vec<[u8; 32]>) -> Self {
+        Self {
+            key,
+            authority: None,
+            deactivation_slot: u64::MAX,
+            addresses,
+        }
+    }
+
+    /// Decode the account data of an Address Lookup Table program account.
+    pub fn deserialize(key: [u8; 32], data: &[u8]) -> Result<Self> {
+        if data.len() < LOOKUP_TABLE_META_SIZE {
+            bail!(
+                "Lookup table account is {} bytes, expected at least {}",
+                data.len(),
+                LOOKUP_TABLE_META_SIZE
+            );
+        }
+
+        let mut reader = ByteReader::new(&data[..LOOKUP_TABLE_META_SIZE]);
+        match reader.read_u32()? {
+            1 => {}
+            0 => bail!("Lookup table account is not initialized"),
+            other => bail!("Unknown lookup table account state {}", other),
+        }
+        let deactivation_slot = reader.read_u64()?;
+        let _last_extended_slot = reader.read_u64()?;
+        let _last_extended_slot_start_index = reader.read_u8()?;
+        let authority = match reader.read_u8()? {
+            0 => None,
+            1 => Some(reader.read_pubkey()?),
+            tag => bail!("Invalid lookup table authority tag {}", tag),
+        };
+
+        let raw = &data[LOOKUP_TABLE_META_SIZE..];
+        if !raw.len().is_multiple_of(32) {
+            bail!("Lookup table address data is not a multiple of 32 bytes");
+        }
+        let addresses = raw
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().expect("chunk is 32 bytes"))
+            .collect();
+
+        Ok(Self {
+            key,
+            authority,
+            deactivation_slot,
+            addresses,
+        })
+    }
+
+    /// Whether the table has been deactivated and can no longer be used for new lookups.
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivation_slot != u64::MAX
+    }
+}
+
+/// A version 0 message, which may load accounts from address lookup tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageV0 {
+    pub header: MessageHeader,
+    pub account_keys: Vec<[u8; 32]>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+impl MessageV0 {
+    /// Compile instructions into a v0 message, moving eligible accounts into lookup tables.
+    ///
+    /// Only non-signer accounts that are not invoked as programs can be loaded
+    /// from a table; everything else stays in the static key list. Deactivated
+    /// tables are skipped, since the runtime rejects lookups through them.
+    pub fn compile(
+        instructions: &[Instruction],
+        payer: &[u8; 32],
+        lookup_tables: &[AddressLookupTable],
+        recent_blockhash: [u8; 32],
+    ) -> Result<Self> {
+        let mut keys = CompiledKeys::new(instructions, payer);
+
+        let mut address_table_lookups = Vec::new();
+        let mut loaded = LoadedAddresses::default();
+        for table in lookup_tables.iter().filter(|table| !table.is_deactivated()) {
+            if let Some((lookup, addresses)) = keys.extract_table_lookup(table) {
+                address_table_lookups.push(lookup);
+                loaded.writable.extend(addresses.writable);
+                loaded.readonly.extend(addresses.readonly);
+            }
+        }
+
+        let (header, account_keys) = keys.into_static_keys()?;
+        let all_keys: Vec<[u8; 32]> = account_keys
+            .iter()
+            .chain(&loaded.writable)
+            .chain(&loaded.readonly)
+            .copied()
+            .collect();
+        let instructions = compile_instructions(instructions, &all_keys)?;
+
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+        })
+    }
+
+    /// Number of accounts this message loads from lookup tables.
+    pub fn num_loaded_accounts(&self) -> usize {
+        self.address_table_lookups
+            .iter()
+            .map(|l| l.writable_indexes.len() + l.readonly_indexes.len())
+            .sum()
+    }
+
+    /// Whether the account at `index` must sign the transaction.
+    pub fn is_signer(&self, index: usize) -> bool {
+        index < self.header.num_required_signatures as usize
+    }
+
+    /// Whether the account at `index` (static keys first, then loaded keys) is writable.
+    pub fn is_writable(&self, index: usize) -> bool {
+        let num_static = self.account_keys.len();
+        if index < num_static {
+            return is_static_key_writable(&self.header, num_static, index);
+        }
+        let num_writable_loaded: usize = self
+            .address_table_lookups
+            .iter()
+            .map(|l| l.writable_indexes.len())
+            .sum();
+        index - num_static < num_writable_loaded
+    }
+
+    /// The public keys that must sign this message, in signature order.
+    pub fn signer_keys(&self) -> &[[u8; 32]] {
+        signer_keys(&self.header, &self.account_keys)
+    }
+
+    /// Resolve this message's table lookups against already-fetched lookup tables.
+    pub fn resolve_addresses(&self, tables: &[AddressLookupTable]) -> Result<LoadedAddresses> {
+        let mut loaded = LoadedAddresses::default();
+        for lookup in &self.address_table_lookups {
+            let table = tables
+                .iter()
+                .find(|t| t.key == lookup.account_key)
+                .context(format!(
+                    "Lookup table {} was not provided",
+                    bs58::encode(lookup.account_key).into_string()
+                ))?;
+
+            let fetch = |index: &u8| -> Result<[u8; 32]> {
+                table
+                    .addresses
+                    .get(*index as usize)
+                    .copied()
+                    .context(format!(
+                        "Index {} is out of range for lookup table {}",
+                        index,
+                        bs58::encode(table.key).into_string()
+                    ))
+            };
+            for index in &lookup.writable_indexes {
+                loaded.writable.push(fetch(index)?);
+            }
+            for index in &lookup.readonly_indexes {
+                loaded.readonly.push(fetch(index)?);
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// The full account list: static keys followed by loaded writable and read-only keys.
+    pub fn account_keys_with(&self, loaded: &LoadedAddresses) -> Vec<[u8; 32]> {
+        self.account_keys
+            .iter()
+            .chain(&loaded.writable)
+            .chain(&loaded.readonly)
+            .copied()
+            .collect()
+    }
+
+    /// Serialize the message, including its version prefix byte.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = vec![MESSAGE_VERSION_PREFIX];
+        write_message_body(
+            &self.header,
+            &self.account_keys,
+            &self.recent_blockhash,
+            &self.instructions,
+            &mut out,
+        );
+
+        encode_compact_u16(self.address_table_lookups.len(), &mut out);
+        for lookup in &self.address_table_lookups {
+            out.extend_from_slice(&lookup.account_key);
+            encode_compact_u16(lookup.writable_indexes.len(), &mut out);
+            out.extend_from_slice(&lookup.writable_indexes);
+            encode_compact_u16(lookup.readonly_indexes.len(), &mut out);
+            out.extend_from_slice(&lookup.readonly_indexes);
+        }
+
+        out
+    }
+
+    /// Read the message body that follows the version prefix byte.
+    fn read(reader: &mut ByteReader) -> Result<Self> {
+        let (header, account_keys, recent_blockhash, instructions) = read_message_body(reader)?;
+
+        let num_lookups = reader.read_compact_u16()?;
+        let mut address_table_lookups = Vec::with_capacity(num_lookups);
+        for _ in 0..num_lookups {
+            let account_key = reader.read_pubkey()?;
+            let num_writable = reader.read_compact_u16()?;
+            let writable_indexes = reader.read_bytes(num_writable)?.to_vec();
+            let num_readonly = reader.read_compact_u16()?;
+            let readonly_indexes = reader.read_bytes(num_readonly)?.to_vec();
+            address_table_lookups.push(MessageAddressTableLookup {
+                account_key,
+                writable_indexes,
+                readonly_indexes,
+            });
+        }
+
+        let message = Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+        };
+        let num_static = message.account_keys.len();
+        let total = num_static + message.num_loaded_accounts();
+        sanitize(&message.header, num_static, total, &message.instructions)?;
+        Ok(message)
+    }
+}
+
+/// Either a legacy or a version 0 message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionedMessage {
+    Legacy(Message),
+    V0(MessageV0),
+}
+
+impl VersionedMessage {
+    pub fn header(&self) -> &MessageHeader {
+        match self {
+            VersionedMessage::Legacy(m) => &m.header,
+            VersionedMessage::V0(m) => &m.header,
+        }
+    }
+
+    /// Account keys stored directly in the message (excluding lookup-table loads).
+    pub fn static_account_keys(&self) -> &[[u8; 32]] {
+        match self {
+            VersionedMessage::Legacy(m) => &m.account_keys,
+            VersionedMessage::V0(m) => &m.account_keys,
+        }
+    }
+
+    pub fn recent_blockhash(&self) -> &[u8; 32] {
+        match self {
+            VersionedMessage::Legacy(m) => &m.recent_blockhash,
+            VersionedMessage::V0(m) => &m.recent_blockhash,
+        }
+    }
+
+    pub fn instructions(&self) -> &[CompiledInstruction] {
+        match self {
+            VersionedMessage::Legacy(m) => &m.instructions,
+            VersionedMessage::V0(m) => &m.instructions,
+        }
+    }
+
+    /// Whether the account at `index` must sign the transaction.
+    pub fn is_signer(&self, index: usize) -> bool {
+        match self {
+            VersionedMessage::Legacy(m) => m.is_signer(index),
+            VersionedMessage::V0(m) => m.is_signer(index),
+        }
+    }
+
+    /// Whether the account at `index` is writable.
+    pub fn is_writable(&self, index: usize) -> bool {
+        match self {
+            VersionedMessage::Legacy(m) => m.is_writable(index),
+            VersionedMessage::V0(m) => m.is_writable(index),
+        }
+    }
+
+    /// The nonce account advanced by the first instruction, if the message
+    /// uses a durable nonce in place of a recent blockhash.
+    pub fn durable_nonce_account(&self) -> Option<[u8; 32]> {
+        let ix = self.instructions().first()?;
+        let keys = self.static_account_keys();
+        let program = keys.get(ix.program_id_index as usize)?;
+        if *program != programs::program_id(programs::SYSTEM_PROGRAM_ID)
+            || ix.data.get(..4)? != ADVANCE_NONCE_ACCOUNT.to_le_bytes()
+        {
+            return None;
+        }
+        keys.get(*ix.accounts.first()? as usize).copied()
+    }
+
+    /// The accounts a v0 message loads from `tables`; none for a legacy message.
+    pub fn resolve_addresses(&self, tables: &[AddressLookupTable]) -> Result<LoadedAddresses> {
+        match self {
+            VersionedMessage::Legacy(_) => Ok(LoadedAddresses::default()),
+            VersionedMessage::V0(m) => m.resolve_addresses(tables),
+        }
+    }
+
+    /// Expand the compiled instructions back into instructions with full account metas.
+    ///
+    /// Signer and writable flags come from the message, so each account carries
+    /// the union of what every instruction asked for. A v0 message needs the
+    /// addresses its lookups load; a legacy message ignores `loaded`.
+    pub fn decompile(&self, loaded: &LoadedAddresses) -> Result<Vec<Instruction>> {
+        let keys = match self {
+            VersionedMessage::Legacy(m) => m.account_keys.clone(),
+            VersionedMessage::V0(m) => {
+                if loaded.len() != m.num_loaded_accounts() {
+                    bail!(
+                        "Message loads {} accounts from lookup tables but {} were provided",
+                        m.num_loaded_accounts(),
+                        loaded.len()
+                    );
+                }
+                m.account_keys_with(loaded)
+            }
+        };
+        let key = |index: u8| {
+            keys.get(index as usize)
+                .copied()
+                .context(format!("Instruction references missing account {}", index))
+        };
+
+        self.instructions()
+            .iter()
+            .map(|ix| {
+                let accounts = ix
+                    .accounts
+                    .iter()
+                    .map(|&index| {
+                        Ok(AccountMeta {
+                            pubkey: key(index)?,
+                            is_signer: self.is_signer(index as usize),
+                            is_writable: self.is_writable(index as usize),
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Instruction {
+                    program_id: key(ix.program_id_index)?,
+                    accounts,
+                    data: ix.data.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// The public keys that must sign this message, in signature order.
+    pub fn signer_keys(&self) -> &[[u8; 32]] {
+        match self {
+            VersionedMessage::Legacy(m) => m.signer_keys(),
+            VersionedMessage::V0(m) => m.signer_keys(),
+        }
+    }
+
+    /// Serialize the message into the bytes that signers sign.
+    pub fn serialize(&self) -> Vec<u8> {
+        match self {
+            VersionedMessage::Legacy(m) => m.serialize(),
+            VersionedMessage::V0(m) => m.serialize(),
+        }
+    }
+
+    /// Parse a serialized message, dispatching on the version prefix.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+        let message = Self::read(&mut reader)?;
+        if !reader.is_empty() {
+            bail!(
+                "Unexpected {} trailing bytes after message",
+                reader.remaining()
+            );
+        }
+        Ok(message)
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self> {
+        let first = reader.peek_u8()?;
+        if first & MESSAGE_VERSION_PREFIX == 0 {
+            return Ok(VersionedMessage::Legacy(Message::read(reader)?));
+        }
+
+        reader.read_u8()?;
+        match first & !MESSAGE_VERSION_PREFIX {
+            0 => Ok(VersionedMessage::V0(MessageV0::read(reader)?)),
+            version => bail!("Unsupported transaction message version {}", version),
+        }
+    }
+}
+
+/// A signed (or partially signed) legacy transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub signatures: Vec<[u8; 64]>,
+    pub message: Message,
+}
+
+impl Transaction {
+    /// Wrap a message with empty signature slots for every required signer.
+    pub fn new_unsigned(message: Message) -> Self {
+        let signatures = vec![[0u8; 64]; message.header.num_required_signatures as usize];
+        Self {
+            signatures,
+            message,
+        }
+    }
+
+    /// Sign the message with `keypair`.
+    ///
+    /// The signature is placed in the slot matching the signer's account key.
+    pub fn sign(&mut self, keypair: &SecretKeypair) -> Result<()> {
+        let position = signer_position(self.message.signer_keys(), keypair)?;
+        self.signatures[position] = keypair.sign(&self.message.serialize());
+        Ok(())
+    }
+
+    /// Whether every required signature slot has been filled.
+    pub fn is_fully_signed(&self) -> bool {
+        self.signatures.iter().all(|s| s != &[0u8; 64])
+    }
+
+    /// The transaction ID: the base58-encoded fee payer signature.
+    pub fn signature(&self) -> Option<String> {
+        self.signatures
+            .first()
+            .map(|s| bs58::encode(s).into_string())
+    }
+
+    /// Serialize into the wire format accepted by `sendTransaction`.
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_with_signatures(&self.signatures, &self.message.serialize())
+    }
+
+    /// Parse a legacy transaction from its wire format.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        match VersionedTransaction::deserialize(bytes)? {
+            VersionedTransaction {
+                signatures,
+                message: VersionedMessage::Legacy(message),
+            } => Ok(Self {
+                signatures,
+                message,
+            }),
+            _ => bail!("Expected a legacy transaction but found a versioned one"),
+        }
+    }
+
+    /// Encode the wire format as base64 (the `encoding: "base64"` form of `sendTransaction`).
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.serialize())
+    }
+
+    /// Encode the wire format as base58 (the legacy default of `sendTransaction`).
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.serialize()).into_string()
+    }
+
+    /// Decode a base64-encoded wire transaction.
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        Self::deserialize(&decode_base64(encoded)?)
+    }
+
+    /// Decode a base58-encoded wire transaction.
+    pub fn from_base58(encoded: &str) -> Result<Self> {
+        Self::deserialize(&decode_base58(encoded)?)
+    }
+}
+
+/// A transaction carrying either a legacy or a version 0 message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedTransaction {
+    pub signatures: Vec<[u8; 64]>,
+    pub message: VersionedMessage,
+}
+
+impl VersionedTransaction {
+    /// Wrap a message with empty signature slots for every required signer.
+    pub fn new_unsigned(message: VersionedMessage) -> Self {
+        let signatures = vec![[0u8; 64]; message.header().num_required_signatures as usize];
+        Self {
+            signatures,
+            message,
+        }
+    }
+
+    /// Sign the message with `keypair`.
+    pub fn sign(&mut self, keypair: &SecretKeypair) -> Result<()> {
+        let position = signer_position(self.message.signer_keys(), keypair)?;
+        self.signatures[position] = keypair.sign(&self.message.serialize());
+        Ok(())
+    }
+
+    /// Whether every required signature slot has been filled.
+    pub fn is_fully_signed(&self) -> bool {
+        self.signatures.iter().all(|s| s != &[0u8; 64])
+    }
+
+    /// Check every signature slot against the matching signer key.
+    ///
+    /// Empty slots are reported as `None`, filled slots as whether the
+    /// signature is valid for this message.
+    pub fn verify_signatures(&self) -> Vec<Option<bool>> {
+        let message = self.message.serialize();
+        self.signatures
+            .iter()
+            .zip(self.message.signer_keys())
+            .map(|(signature, key)| {
+                (signature != &[0u8; 64]).then(|| verify_bytes(key, &message, signature))
+            })
+            .collect()
+    }
+
+    /// The transaction ID: the base58-encoded fee payer signature.
+    pub fn signature(&self) -> Option<String> {
+        self.signatures
+            .first()
+            .map(|s| bs58::encode(s).into_string())
+    }
+
+    /// Serialize into the wire format accepted by `sendTransaction`.
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_with_signatures(&self.signatures, &self.message.serialize())
+    }
+
+    /// Parse a legacy or versioned transaction from its wire format.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+        let num_signatures = reader.read_compact_u16()?;
+        let mut signatures = Vec::with_capacity(num_signatures);
+        for _ in 0..num_signatures {
+            let sig: [u8; 64] = reader.read_bytes(64)?.try_into().expect("read 64 bytes");
+            signatures.push(sig);
+        }
+
+        let message = VersionedMessage::read(&mut reader)?;
+        if !reader.is_empty() {
+            bail!(
+                "Unexpected {} trailing bytes after transaction",
+                reader.remaining()
+            );
+        }
+        if signatures.len() != message.header().num_required_signatures as usize {
+            bail!(
+                "Transaction has {} signatures but its message requires {}",
+                signatures.len(),
+                message.header().num_required_signatures
+            );
+        }
+
+        Ok(Self {
+            signatures,
+            message,
+        })
+    }
+
+    /// Encode the wire format as base64.
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.serialize())
+    }
+
+    /// Encode the wire format as base58.
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.serialize()).into_string()
+    }
+
+    /// Decode a base64-encoded wire transaction.
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        Self::deserialize(&decode_base64(encoded)?)
+    }
+
+    /// Decode a base58-encoded wire transaction.
+    pub fn from_base58(encoded: &str) -> Result<Self> {
+        Self::deserialize(&decode_base58(encoded)?)
+    }
+}
+
+impl From<Transaction> for VersionedTransaction {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            signatures: tx.signatures,
+            message: VersionedMessage::Legacy(tx.message),
+        }
+    }
+}
+
 /// Builder for assembling Solana transactions.
 ///
-/// This is a simplified transaction builder for FrogPump's needs.
-/// For full Solana transaction support, use the solana-sdk crate directly.
+/// Instructions are compiled into a message on `build`, so signatures
+/// collected with `sign` are always placed in account-key order.
 pub struct TransactionBuilder {
     instructions: Vec<Instruction>,
     fee_payer: Option<[u8; 32]>,
     recent_blockhash: Option<String>,
-    signatures: Vec<Vec<u8>>,
+    lookup_tables: Vec<AddressLookupTable>,
+    signers: Vec<SecretKeypair>,
 }
 
 impl TransactionBuilder {
@@ -34,7 +817,8 @@ impl TransactionBuilder {
             instructions: Vec::new(),
             fee_payer: None,
             recent_blockhash: None,
-            signatures: Vec::new(),
+            lookup_tables: Vec::new(),
+            signers: Vec::new(),
         }
     }
 
@@ -50,100 +834,1660 @@ impl TransactionBuilder {
         self
     }
 
-    /// Set the recent blockhash for the transaction.
+    /// Set the recent blockhash (base58) for the transaction.
     pub fn set_recent_blockhash(&mut self, hash: String) -> &mut Self {
         self.recent_blockhash = Some(hash);
         self
     }
 
-    /// Sign the transaction message with the provided keypair bytes.
+    /// Make an address lookup table available to v0 messages built by this builder.
+    pub fn add_lookup_table(&mut self, table: AddressLookupTable) -> &mut Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    /// Sign the transaction with the provided keypair.
     ///
-    /// The keypair should be 64 bytes: 32-byte secret key followed by 32-byte public key.
-    pub fn sign(&mut self, keypair: &[u8; 64]) -> Result<&mut Self> {
-        use ed25519_dalek::{Signer, SigningKey};
-
-        let secret_bytes: [u8; 32] = keypair[..32]
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("Invalid keypair: could not extract secret key"))?;
-
-        let signing_key = SigningKey::from_bytes(&secret_bytes);
-        let message = self.build_message()?;
-        let signature = signing_key.sign(&message);
-        self.signatures.push(signature.to_bytes().to_vec());
+    /// Signing happens when the transaction is built, after all instructions are known.
+    pub fn sign(&mut self, keypair: &SecretKeypair) -> Result<&mut Self> {
+        self.signers.push(keypair.clone());
         Ok(self)
     }
 
-    /// Build the serialized transaction bytes.
-    ///
-    /// Returns a simplified byte representation. In production, this would
-    /// produce a fully Solana-compatible binary transaction.
-    pub fn build(&self) -> Result<Vec<u8>> {
+    fn payer_and_blockhash(&self) -> Result<([u8; 32], [u8; 32])> {
         if self.instructions.is_empty() {
             bail!("Transaction must have at least one instruction");
         }
-        if self.fee_payer.is_none() {
-            bail!("Transaction must have a fee payer");
+        let fee_payer = match self.fee_payer {
+            Some(payer) => payer,
+            None => bail!("Transaction must have a fee payer"),
+        };
+        let blockhash = match self.recent_blockhash {
+            Some(ref hash) => decode_hash(hash)?,
+            None => bail!("Transaction must have a recent blockhash"),
+        };
+        Ok((fee_payer, blockhash))
+    }
+
+    /// Compile the instructions into a legacy message.
+    pub fn compile_message(&self) -> Result<Message> {
+        let (fee_payer, blockhash) = self.payer_and_blockhash()?;
+        Message::compile(&self.instructions, &fee_payer, blockhash)
+    }
+
+    /// Compile the instructions into a v0 message using the registered lookup tables.
+    pub fn compile_message_v0(&self) -> Result<MessageV0> {
+        let (fee_payer, blockhash) = self.payer_and_blockhash()?;
+        MessageV0::compile(
+            &self.instructions,
+            &fee_payer,
+            &self.lookup_tables,
+            blockhash,
+        )
+    }
+
+    /// Compile the message and apply every collected signature.
+    ///
+    /// A transaction over the packet size limit is rejected before anything is signed.
+    pub fn build_transaction(&self) -> Result<Transaction> {
+        let mut tx = Transaction::new_unsigned(self.compile_message()?);
+        check_packet_size(&tx.serialize())?;
+        for keypair in &self.signers {
+            tx.sign(keypair)?;
+        }
+        Ok(tx)
+    }
+
+    /// Compile a v0 message and apply every collected signature.
+    ///
+    /// A transaction over the packet size limit is rejected before anything is signed.
+    pub fn build_versioned_transaction(&self) -> Result<VersionedTransaction> {
+        let message = VersionedMessage::V0(self.compile_message_v0()?);
+        let mut tx = VersionedTransaction::new_unsigned(message);
+        check_packet_size(&tx.serialize())?;
+        for keypair in &self.signers {
+            tx.sign(keypair)?;
+        }
+        Ok(tx)
+    }
+
+    /// Build the serialized wire-format transaction bytes.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        Ok(self.build_transaction()?.serialize())
+    }
+
+    /// Build the serialized wire-format bytes of a v0 transaction.
+    pub fn build_v0(&self) -> Result<Vec<u8>> {
+        Ok(self.build_versioned_transaction()?.serialize())
+    }
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Leading byte of the ComputeBudget `SetComputeUnitLimit` instruction.
+pub const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// Leading byte of the ComputeBudget `SetComputeUnitPrice` instruction.
+pub const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// ComputeBudget `SetComputeUnitLimit` instruction.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: programs::program_id(programs::COMPUTE_BUDGET_PROGRAM_ID),
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// ComputeBudget `SetComputeUnitPrice` instruction, priced in micro-lamports per compute unit.
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: programs::program_id(programs::COMPUTE_BUDGET_PROGRAM_ID),
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// Associated Token Account `CreateIdempotent` instruction: creates `owner`'s
+/// account for `mint` unless it already exists.
+pub fn create_associated_token_account_idempotent(
+    payer: &[u8; 32],
+    owner: &[u8; 32],
+    mint: &[u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id: programs::program_id(programs::ASSOCIATED_TOKEN_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pda::associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(programs::program_id(programs::SYSTEM_PROGRAM_ID), false),
+            AccountMeta::new_readonly(programs::program_id(programs::TOKEN_PROGRAM_ID), false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// Index of the System program's `AdvanceNonceAccount` instruction.
+const ADVANCE_NONCE_ACCOUNT: u32 = 4;
+
+/// System `AdvanceNonceAccount` instruction. A transaction that starts with it
+/// uses the nonce stored in `nonce_account` in place of a recent blockhash.
+pub fn advance_nonce_account(nonce_account: &[u8; 32], authority: &[u8; 32]) -> Instruction {
+    Instruction {
+        program_id: programs::program_id(programs::SYSTEM_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::new(*nonce_account, false),
+            AccountMeta::new_readonly(
+                programs::program_id(programs::RECENT_BLOCKHASHES_SYSVAR_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: ADVANCE_NONCE_ACCOUNT.to_le_bytes().to_vec(),
+    }
+}
+
+/// Per-key flags merged across every instruction of a message.
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyMeta {
+    is_signer: bool,
+    is_writable: bool,
+    is_invoked: bool,
+}
+
+/// Account keys collected from a set of instructions, prior to ordering.
+struct CompiledKeys {
+    payer: [u8; 32],
+    metas: BTreeMap<[u8; 32], KeyMeta>,
+}
+
+impl CompiledKeys {
+    fn new(instructions: &[Instruction], payer: &[u8; 32]) -> Self {
+        let mut metas: BTreeMap<[u8; 32], KeyMeta> = BTreeMap::new();
+        for ix in instructions {
+            metas.entry(ix.program_id).or_default().is_invoked = true;
+            for acct in &ix.accounts {
+                let meta = metas.entry(acct.pubkey).or_default();
+                meta.is_signer |= acct.is_signer;
+                meta.is_writable |= acct.is_writable;
+            }
+        }
+        metas.remove(payer);
+
+        Self {
+            payer: *payer,
+            metas,
+        }
+    }
+
+    /// Move every eligible key found in `table` out of the static set.
+    fn extract_table_lookup(
+        &mut self,
+        table: &AddressLookupTable,
+    ) -> Option<(MessageAddressTableLookup, LoadedAddresses)> {
+        let mut lookup = MessageAddressTableLookup {
+            account_key: table.key,
+            writable_indexes: Vec::new(),
+            readonly_indexes: Vec::new(),
+        };
+        let mut loaded = LoadedAddresses::default();
+
+        let mut drained = Vec::new();
+        for (key, meta) in &self.metas {
+            if meta.is_signer || meta.is_invoked {
+                continue;
+            }
+            let Some(index) = table.addresses.iter().position(|a| a == key) else {
+                continue;
+            };
+            let Ok(index) = u8::try_from(index) else {
+                continue;
+            };
+            if meta.is_writable {
+                lookup.writable_indexes.push(index);
+                loaded.writable.push(*key);
+            } else {
+                lookup.readonly_indexes.push(index);
+                loaded.readonly.push(*key);
+            }
+            drained.push(*key);
+        }
+
+        if drained.is_empty() {
+            return None;
         }
-        if self.recent_blockhash.is_none() {
-            bail!("Transaction must have a recent blockhash");
+        for key in drained {
+            self.metas.remove(&key);
         }
+        Some((lookup, loaded))
+    }
+
+    /// Order the remaining keys and derive the message header.
+    fn into_static_keys(self) -> Result<(MessageHeader, Vec<[u8; 32]>)> {
+        let select = |signer: bool, writable: bool| -> Vec<[u8; 32]> {
+            self.metas
+                .iter()
+                .filter(|(_, m)| m.is_signer == signer && m.is_writable == writable)
+                .map(|(k, _)| *k)
+                .collect()
+        };
+
+        let mut writable_signers = vec![self.payer];
+        writable_signers.extend(select(true, true));
+        let readonly_signers = select(true, false);
+        let writable_unsigned = select(false, true);
+        let readonly_unsigned = select(false, false);
+
+        let header = MessageHeader {
+            num_required_signatures: u8::try_from(writable_signers.len() + readonly_signers.len())
+                .context("Too many signers in transaction")?,
+            num_readonly_signed_accounts: readonly_signers.len() as u8,
+            num_readonly_unsigned_accounts: u8::try_from(readonly_unsigned.len())
+                .context("Too many read-only accounts in transaction")?,
+        };
+
+        let account_keys = writable_signers
+            .into_iter()
+            .chain(readonly_signers)
+            .chain(writable_unsigned)
+            .chain(readonly_unsigned)
+            .collect();
+
+        Ok((header, account_keys))
+    }
+}
+
+/// Replace instruction account keys with their indexes into `account_keys`.
+fn compile_instructions(
+    instructions: &[Instruction],
+    account_keys: &[[u8; 32]],
+) -> Result<Vec<CompiledInstruction>> {
+    if account_keys.len() > 256 {
+        bail!(
+            "Transaction references {} accounts, at most 256 are allowed",
+            account_keys.len()
+        );
+    }
+    // Every length below is written as a compact-u16.
+    let max = u16::MAX as usize;
+    if instructions.len() > max {
+        bail!(
+            "Transaction has {} instructions, at most {} can be encoded",
+            instructions.len(),
+            max
+        );
+    }
+    if instructions
+        .iter()
+        .any(|ix| ix.accounts.len() > max || ix.data.len() > max)
+    {
+        bail!(
+            "Instruction has more than {} accounts or bytes of data",
+            max
+        );
+    }
+
+    let index_of = |key: &[u8; 32]| -> u8 {
+        account_keys
+            .iter()
+            .position(|k| k == key)
+            .expect("every instruction key was collected") as u8
+    };
+
+    Ok(instructions
+        .iter()
+        .map(|ix| CompiledInstruction {
+            program_id_index: index_of(&ix.program_id),
+            accounts: ix.accounts.iter().map(|a| index_of(&a.pubkey)).collect(),
+            data: ix.data.clone(),
+        })
+        .collect())
+}
+
+fn is_static_key_writable(header: &MessageHeader, num_keys: usize, index: usize) -> bool {
+    let num_signed = header.num_required_signatures as usize;
+    if index < num_signed {
+        index < num_signed - header.num_readonly_signed_accounts as usize
+    } else {
+        let num_unsigned = num_keys - num_signed;
+        let writable_unsigned =
+            num_unsigned.saturating_sub(header.num_readonly_unsigned_accounts as usize);
+        index - num_signed < writable_unsigned
+    }
+}
+
+fn signer_keys<'a>(header: &MessageHeader, account_keys: &'a [[u8; 32]]) -> &'a [[u8; 32]] {
+    let count = (header.num_required_signatures as usize).min(account_keys.len());
+    &account_keys[..count]
+}
+
+/// Write the header, keys, blockhash and instructions shared by every message version.
+fn write_message_body(
+    header: &MessageHeader,
+    account_keys: &[[u8; 32]],
+    recent_blockhash: &[u8; 32],
+    instructions: &[CompiledInstruction],
+    out: &mut Vec<u8>,
+) {
+    out.push(header.num_required_signatures);
+    out.push(header.num_readonly_signed_accounts);
+    out.push(header.num_readonly_unsigned_accounts);
+
+    encode_compact_u16(account_keys.len(), out);
+    for key in account_keys {
+        out.extend_from_slice(key);
+    }
+    out.extend_from_slice(recent_blockhash);
+
+    encode_compact_u16(instructions.len(), out);
+    for ix in instructions {
+        out.push(ix.program_id_index);
+        encode_compact_u16(ix.accounts.len(), out);
+        out.extend_from_slice(&ix.accounts);
+        encode_compact_u16(ix.data.len(), out);
+        out.extend_from_slice(&ix.data);
+    }
+}
 
-        let message = self.build_message()?;
-        let mut tx_bytes = Vec::new();
+type MessageBody = (
+    MessageHeader,
+    Vec<[u8; 32]>,
+    [u8; 32],
+    Vec<CompiledInstruction>,
+);
 
-        // Compact array length for signatures
-        tx_bytes.push(self.signatures.len() as u8);
-        for sig in &self.signatures {
-            tx_bytes.extend_from_slice(sig);
+fn read_message_body(reader: &mut ByteReader) -> Result<MessageBody> {
+    let header = MessageHeader {
+        num_required_signatures: reader.read_u8()?,
+        num_readonly_signed_accounts: reader.read_u8()?,
+        num_readonly_unsigned_accounts: reader.read_u8()?,
+    };
+
+    let num_keys = reader.read_compact_u16()?;
+    let mut account_keys = Vec::with_capacity(num_keys);
+    for _ in 0..num_keys {
+        account_keys.push(reader.read_pubkey()?);
+    }
+    let recent_blockhash = reader.read_pubkey()?;
+
+    let num_instructions = reader.read_compact_u16()?;
+    let mut instructions = Vec::with_capacity(num_instructions);
+    for _ in 0..num_instructions {
+        let program_id_index = reader.read_u8()?;
+        let num_accounts = reader.read_compact_u16()?;
+        let accounts = reader.read_bytes(num_accounts)?.to_vec();
+        let data_len = reader.read_compact_u16()?;
+        let data = reader.read_bytes(data_len)?.to_vec();
+        instructions.push(CompiledInstruction {
+            program_id_index,
+            accounts,
+            data,
+        });
+    }
+
+    Ok((header, account_keys, recent_blockhash, instructions))
+}
+
+/// Reject messages whose header or instruction indexes do not fit the key list.
+fn sanitize(
+    header: &MessageHeader,
+    num_static_keys: usize,
+    num_total_keys: usize,
+    instructions: &[CompiledInstruction],
+) -> Result<()> {
+    let num_signed = header.num_required_signatures as usize;
+    if num_signed > num_static_keys
+        || header.num_readonly_signed_accounts as usize >= num_signed.max(1)
+        || header.num_readonly_unsigned_accounts as usize > num_static_keys - num_signed
+    {
+        bail!(
+            "Message header is inconsistent with its {} account keys",
+            num_static_keys
+        );
+    }
+    for ix in instructions {
+        if ix.program_id_index as usize >= num_static_keys
+            || ix.accounts.iter().any(|&i| i as usize >= num_total_keys)
+        {
+            bail!("Instruction references an account index out of range");
         }
-        tx_bytes.extend_from_slice(&message);
+    }
+    Ok(())
+}
+
+fn serialize_with_signatures(signatures: &[[u8; 64]], message: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_compact_u16(signatures.len(), &mut out);
+    for sig in signatures {
+        out.extend_from_slice(sig);
+    }
+    out.extend_from_slice(message);
+    out
+}
 
-        Ok(tx_bytes)
+fn check_packet_size(bytes: &[u8]) -> Result<()> {
+    if bytes.len() > PACKET_DATA_SIZE {
+        bail!(
+            "Transaction is {} bytes, exceeding the {} byte packet limit",
+            bytes.len(),
+            PACKET_DATA_SIZE
+        );
     }
+    Ok(())
+}
+
+fn decode_base64(encoded: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .context("Invalid base64 transaction")
+}
+
+fn decode_base58(encoded: &str) -> Result<Vec<u8>> {
+    bs58::decode(encoded.trim())
+        .into_vec()
+        .context("Invalid base58 transaction")
+}
+
+/// Decode a base58 blockhash into its 32 raw bytes.
+pub fn decode_hash(hash: &str) -> Result<[u8; 32]> {
+    let bytes = bs58::decode(hash)
+        .into_vec()
+        .context(format!("Invalid base58 blockhash: {}", hash))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow::anyhow!("Blockhash is {} bytes, expected 32", b.len()))
+}
+
+/// Append `value` in Solana's compact-u16 (shortvec) encoding.
+///
+/// Compiling a message rejects anything longer, so an out-of-range value is a
+/// bug and panics rather than writing a corrupt length.
+pub fn encode_compact_u16(value: usize, out: &mut Vec<u8>) {
+    assert!(
+        value <= u16::MAX as usize,
+        "compact-u16 overflow: {}",
+        value
+    );
+    let mut rem = value as u16;
+    loop {
+        let mut byte = (rem & 0x7f) as u8;
+        rem >>= 7;
+        if rem == 0 {
+            out.push(byte);
+            break;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+fn signer_position(signer_keys: &[[u8; 32]], keypair: &SecretKeypair) -> Result<usize> {
+    let pubkey = keypair.pubkey();
+    signer_keys
+        .iter()
+        .position(|k| k == &pubkey)
+        .context(format!(
+            "{} is not a required signer of this transaction",
+            keypair.address()
+        ))
+}
+
+fn verify_bytes(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    use ed25519_dalek::Verifier;
+
+    ed25519_dalek::VerifyingKey::from_bytes(pubkey)
+        .map(|key| {
+            key.verify(message, &ed25519_dalek::Signature::from_bytes(signature))
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Human-readable view of a transaction, for inspecting what is about to be signed.
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+    /// `None` for legacy transactions, otherwise the message version.
+    pub version: Option<u8>,
+    pub header: MessageHeader,
+    pub recent_blockhash: String,
+    pub accounts: Vec<DecodedAccount>,
+    pub signatures: Vec<DecodedSignature>,
+    pub instructions: Vec<DecodedInstruction>,
+}
+
+/// An account key with its role in the message.
+#[derive(Debug, Clone)]
+pub struct DecodedAccount {
+    /// Base58 address, or a `table[index]` reference for keys loaded from a lookup table.
+    pub address: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub from_lookup_table: bool,
+}
+
+/// A signature slot and whether it verifies against the message.
+#[derive(Debug, Clone)]
+pub struct DecodedSignature {
+    pub signer: String,
+    pub signature: String,
+    /// `None` when the slot is still empty.
+    pub verified: Option<bool>,
+}
 
-    /// Build the transaction message (the signable payload).
-    fn build_message(&self) -> Result<Vec<u8>> {
-        let mut message = Vec::new();
+/// An instruction, decoded where the program and instruction are known.
+#[derive(Debug, Clone)]
+pub struct DecodedInstruction {
+    pub program_id: String,
+    pub program: Option<&'static str>,
+    pub name: Option<&'static str>,
+    /// Labelled arguments and accounts of a recognised instruction.
+    pub fields: Vec<(&'static str, String)>,
+    pub accounts: Vec<String>,
+    pub data: Vec<u8>,
+}
 
-        // Simplified message header
-        let num_required_signatures: u8 = 1;
-        let num_readonly_signed: u8 = 0;
-        let num_readonly_unsigned: u8 = 0;
-        message.push(num_required_signatures);
-        message.push(num_readonly_signed);
-        message.push(num_readonly_unsigned);
+type DecodedFields = (&'static str, Vec<(&'static str, String)>);
 
-        // Fee payer
-        if let Some(ref payer) = self.fee_payer {
-            message.extend_from_slice(payer);
+impl VersionedTransaction {
+    /// Decode the transaction into a human-readable form, verifying any signatures present.
+    pub fn decode(&self) -> DecodedTransaction {
+        let message = &self.message;
+        let mut addresses: Vec<String> = message
+            .static_account_keys()
+            .iter()
+            .map(|key| bs58::encode(key).into_string())
+            .collect();
+        let num_static = addresses.len();
+        if let VersionedMessage::V0(m) = message {
+            let loaded = |indexes: fn(&MessageAddressTableLookup) -> &Vec<u8>| {
+                m.address_table_lookups
+                    .iter()
+                    .flat_map(move |lookup| {
+                        let table = bs58::encode(lookup.account_key).into_string();
+                        indexes(lookup)
+                            .iter()
+                            .map(move |index| format!("{}[{}]", table, index))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            addresses.extend(loaded(|l| &l.writable_indexes));
+            addresses.extend(loaded(|l| &l.readonly_indexes));
         }
 
-        // Blockhash
-        if let Some(ref hash) = self.recent_blockhash {
-            message.extend_from_slice(hash.as_bytes());
+        let accounts = addresses
+            .iter()
+            .enumerate()
+            .map(|(index, address)| DecodedAccount {
+                address: address.clone(),
+                is_signer: message.is_signer(index),
+                is_writable: message.is_writable(index),
+                from_lookup_table: index >= num_static,
+            })
+            .collect();
+
+        let signatures = self
+            .signatures
+            .iter()
+            .zip(&addresses)
+            .zip(self.verify_signatures())
+            .map(|((signature, signer), verified)| DecodedSignature {
+                signer: signer.clone(),
+                signature: bs58::encode(signature).into_string(),
+                verified,
+            })
+            .collect();
+
+        let unknown = || "<unknown>".to_string();
+        let instructions = message
+            .instructions()
+            .iter()
+            .map(|ix| {
+                let program_key = message
+                    .static_account_keys()
+                    .get(ix.program_id_index as usize);
+                let ix_accounts: Vec<String> = ix
+                    .accounts
+                    .iter()
+                    .map(|&i| addresses.get(i as usize).cloned().unwrap_or_else(unknown))
+                    .collect();
+                let decoded =
+                    program_key.and_then(|key| decode_instruction(key, &ix_accounts, &ix.data));
+                let (name, fields) = match decoded {
+                    Some((name, fields)) => (Some(name), fields),
+                    None => (None, Vec::new()),
+                };
+                DecodedInstruction {
+                    program_id: program_key
+                        .map(|key| bs58::encode(key).into_string())
+                        .unwrap_or_else(unknown),
+                    program: program_key.and_then(programs::program_name),
+                    name,
+                    fields,
+                    accounts: ix_accounts,
+                    data: ix.data.clone(),
+                }
+            })
+            .collect();
+
+        DecodedTransaction {
+            version: match message {
+                VersionedMessage::Legacy(_) => None,
+                VersionedMessage::V0(_) => Some(0),
+            },
+            header: *message.header(),
+            recent_blockhash: bs58::encode(message.recent_blockhash()).into_string(),
+            accounts,
+            signatures,
+            instructions,
         }
+    }
+}
 
-        // Instructions (simplified encoding)
-        message.push(self.instructions.len() as u8);
-        for ix in &self.instructions {
-            message.extend_from_slice(&ix.program_id);
-            message.push(ix.accounts.len() as u8);
-            for acct in &ix.accounts {
-                message.extend_from_slice(&acct.pubkey);
-                message.push(u8::from(acct.is_signer));
-                message.push(u8::from(acct.is_writable));
+/// Decode the instructions of the programs FrogPump interacts with.
+///
+/// Returns the instruction name and its labelled fields, or `None` when the
+/// program or instruction is not recognised or the data is malformed.
+pub fn decode_instruction(
+    program_id: &[u8; 32],
+    accounts: &[String],
+    data: &[u8],
+) -> Option<DecodedFields> {
+    let decoders: [(&str, InstructionDecoder); 6] = [
+        (programs::SYSTEM_PROGRAM_ID, decode_system_instruction),
+        (programs::TOKEN_PROGRAM_ID, decode_token_instruction),
+        (
+            programs::ASSOCIATED_TOKEN_PROGRAM_ID,
+            decode_associated_token_instruction,
+        ),
+        (
+            programs::COMPUTE_BUDGET_PROGRAM_ID,
+            decode_compute_budget_instruction,
+        ),
+        (programs::METADATA_PROGRAM_ID, decode_metadata_instruction),
+        (programs::PUMP_FUN_PROGRAM_ID, decode_pump_fun_instruction),
+    ];
+    let (_, decoder) = decoders
+        .iter()
+        .find(|(id, _)| programs::program_id(id) == *program_id)?;
+    let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+    decoder(&account, &mut ByteReader::new(data)).ok()
+}
+
+type AccountLookup<'a> = dyn Fn(usize) -> String + 'a;
+
+type InstructionDecoder = fn(&AccountLookup, &mut ByteReader) -> Result<DecodedFields>;
+
+fn decode_system_instruction(
+    account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    Ok(match reader.read_u32()? {
+        0 => (
+            "CreateAccount",
+            vec![
+                ("funder", account(0)),
+                ("new account", account(1)),
+                ("lamports", format_lamports(reader.read_u64()?)),
+                ("space", reader.read_u64()?.to_string()),
+                ("owner", bs58::encode(reader.read_pubkey()?).into_string()),
+            ],
+        ),
+        1 => (
+            "Assign",
+            vec![
+                ("account", account(0)),
+                ("owner", bs58::encode(reader.read_pubkey()?).into_string()),
+            ],
+        ),
+        2 => (
+            "Transfer",
+            vec![
+                ("from", account(0)),
+                ("to", account(1)),
+                ("lamports", format_lamports(reader.read_u64()?)),
+            ],
+        ),
+        3 => (
+            "CreateAccountWithSeed",
+            vec![("funder", account(0)), ("new account", account(1))],
+        ),
+        4 => (
+            "AdvanceNonceAccount",
+            vec![("nonce account", account(0)), ("authority", account(2))],
+        ),
+        5 => (
+            "WithdrawNonceAccount",
+            vec![
+                ("nonce account", account(0)),
+                ("to", account(1)),
+                ("authority", account(4)),
+                ("lamports", format_lamports(reader.read_u64()?)),
+            ],
+        ),
+        6 => (
+            "InitializeNonceAccount",
+            vec![("nonce account", account(0))],
+        ),
+        7 => ("AuthorizeNonceAccount", vec![("nonce account", account(0))]),
+        8 => (
+            "Allocate",
+            vec![
+                ("account", account(0)),
+                ("space", reader.read_u64()?.to_string()),
+            ],
+        ),
+        9 => ("AllocateWithSeed", vec![("account", account(0))]),
+        10 => ("AssignWithSeed", vec![("account", account(0))]),
+        11 => (
+            "TransferWithSeed",
+            vec![
+                ("from", account(0)),
+                ("to", account(2)),
+                ("lamports", format_lamports(reader.read_u64()?)),
+            ],
+        ),
+        12 => ("UpgradeNonceAccount", vec![("nonce account", account(0))]),
+        tag => bail!("Unknown System instruction {}", tag),
+    })
+}
+
+fn decode_token_instruction(
+    account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    Ok(match reader.read_u8()? {
+        0 | 20 => {
+            let decimals = reader.read_u8()?;
+            let authority = bs58::encode(reader.read_pubkey()?).into_string();
+            (
+                "InitializeMint",
+                vec![
+                    ("mint", account(0)),
+                    ("decimals", decimals.to_string()),
+                    ("mint authority", authority),
+                ],
+            )
+        }
+        1 => (
+            "InitializeAccount",
+            vec![
+                ("account", account(0)),
+                ("mint", account(1)),
+                ("owner", account(2)),
+            ],
+        ),
+        2 | 19 => ("InitializeMultisig", vec![("multisig", account(0))]),
+        3 => (
+            "Transfer",
+            vec![
+                ("source", account(0)),
+                ("destination", account(1)),
+                ("authority", account(2)),
+                ("amount", reader.read_u64()?.to_string()),
+            ],
+        ),
+        4 => (
+            "Approve",
+            vec![
+                ("source", account(0)),
+                ("delegate", account(1)),
+                ("owner", account(2)),
+                ("amount", reader.read_u64()?.to_string()),
+            ],
+        ),
+        5 => (
+            "Revoke",
+            vec![("source", account(0)), ("owner", account(1))],
+        ),
+        6 => {
+            let authority_type = match reader.read_u8()? {
+                0 => "MintTokens",
+                1 => "FreezeAccount",
+                2 => "AccountOwner",
+                3 => "CloseAccount",
+                _ => "Unknown",
+            };
+            let new_authority = match reader.read_u8()? {
+                0 => "(none)".to_string(),
+                _ => bs58::encode(reader.read_pubkey()?).into_string(),
+            };
+            (
+                "SetAuthority",
+                vec![
+                    ("account", account(0)),
+                    ("current authority", account(1)),
+                    ("authority type", authority_type.to_string()),
+                    ("new authority", new_authority),
+                ],
+            )
+        }
+        7 => (
+            "MintTo",
+            vec![
+                ("mint", account(0)),
+                ("destination", account(1)),
+                ("authority", account(2)),
+                ("amount", reader.read_u64()?.to_string()),
+            ],
+        ),
+        8 => (
+            "Burn",
+            vec![
+                ("account", account(0)),
+                ("mint", account(1)),
+                ("authority", account(2)),
+                ("amount", reader.read_u64()?.to_string()),
+            ],
+        ),
+        9 => (
+            "CloseAccount",
+            vec![
+                ("account", account(0)),
+                ("destination", account(1)),
+                ("authority", account(2)),
+            ],
+        ),
+        10 => (
+            "FreezeAccount",
+            vec![("account", account(0)), ("mint", account(1))],
+        ),
+        11 => (
+            "ThawAccount",
+            vec![("account", account(0)), ("mint", account(1))],
+        ),
+        12 => {
+            let amount = reader.read_u64()?;
+            let decimals = reader.read_u8()?;
+            (
+                "TransferChecked",
+                vec![
+                    ("source", account(0)),
+                    ("mint", account(1)),
+                    ("destination", account(2)),
+                    ("authority", account(3)),
+                    ("amount", format_token_amount(amount, decimals)),
+                ],
+            )
+        }
+        13 => {
+            let amount = reader.read_u64()?;
+            let decimals = reader.read_u8()?;
+            (
+                "ApproveChecked",
+                vec![
+                    ("source", account(0)),
+                    ("mint", account(1)),
+                    ("delegate", account(2)),
+                    ("owner", account(3)),
+                    ("amount", format_token_amount(amount, decimals)),
+                ],
+            )
+        }
+        14 => {
+            let amount = reader.read_u64()?;
+            let decimals = reader.read_u8()?;
+            (
+                "MintToChecked",
+                vec![
+                    ("mint", account(0)),
+                    ("destination", account(1)),
+                    ("authority", account(2)),
+                    ("amount", format_token_amount(amount, decimals)),
+                ],
+            )
+        }
+        15 => {
+            let amount = reader.read_u64()?;
+            let decimals = reader.read_u8()?;
+            (
+                "BurnChecked",
+                vec![
+                    ("account", account(0)),
+                    ("mint", account(1)),
+                    ("authority", account(2)),
+                    ("amount", format_token_amount(amount, decimals)),
+                ],
+            )
+        }
+        16 | 18 => (
+            "InitializeAccount",
+            vec![
+                ("account", account(0)),
+                ("mint", account(1)),
+                ("owner", bs58::encode(reader.read_pubkey()?).into_string()),
+            ],
+        ),
+        17 => ("SyncNative", vec![("account", account(0))]),
+        tag => bail!("Unknown SPL Token instruction {}", tag),
+    })
+}
+
+fn decode_associated_token_instruction(
+    account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    // `Create` predates the instruction enum and is sent with empty data.
+    let name = match if reader.is_empty() {
+        0
+    } else {
+        reader.read_u8()?
+    } {
+        0 => "Create",
+        1 => "CreateIdempotent",
+        2 => return Ok(("RecoverNested", vec![("wallet", account(5))])),
+        tag => bail!("Unknown Associated Token instruction {}", tag),
+    };
+    Ok((
+        name,
+        vec![
+            ("payer", account(0)),
+            ("associated account", account(1)),
+            ("wallet", account(2)),
+            ("mint", account(3)),
+        ],
+    ))
+}
+
+fn decode_compute_budget_instruction(
+    _account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    Ok(match reader.read_u8()? {
+        1 => (
+            "RequestHeapFrame",
+            vec![("bytes", reader.read_u32()?.to_string())],
+        ),
+        2 => (
+            "SetComputeUnitLimit",
+            vec![("units", reader.read_u32()?.to_string())],
+        ),
+        3 => (
+            "SetComputeUnitPrice",
+            vec![("micro-lamports per unit", reader.read_u64()?.to_string())],
+        ),
+        4 => (
+            "SetLoadedAccountsDataSizeLimit",
+            vec![("bytes", reader.read_u32()?.to_string())],
+        ),
+        tag => bail!("Unknown ComputeBudget instruction {}", tag),
+    })
+}
+
+fn decode_metadata_instruction(
+    account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    let name = match reader.read_u8()? {
+        16 => "CreateMetadataAccountV2",
+        33 => "CreateMetadataAccountV3",
+        42 => {
+            // `Create` wraps its asset data in a versioned args enum.
+            if reader.read_u8()? != 0 {
+                bail!("Unknown Create args version");
+            }
+            "Create"
+        }
+        tag => bail!("Unsupported Token Metadata instruction {}", tag),
+    };
+    Ok((
+        name,
+        vec![
+            ("metadata", account(0)),
+            ("mint", account(1)),
+            ("mint authority", account(2)),
+            ("payer", account(3)),
+            ("update authority", account(4)),
+            ("name", reader.read_string()?),
+            ("symbol", reader.read_string()?),
+            ("uri", reader.read_string()?),
+            ("seller fee", format!("{} bps", reader.read_u16()?)),
+        ],
+    ))
+}
+
+fn decode_pump_fun_instruction(
+    account: &AccountLookup,
+    reader: &mut ByteReader,
+) -> Result<DecodedFields> {
+    let discriminator: [u8; 8] = reader.read_bytes(8)?.try_into().expect("read 8 bytes");
+    Ok(match discriminator {
+        pumpfun::CREATE_DISCRIMINATOR => {
+            let mut fields = vec![
+                ("mint", account(0)),
+                ("bonding curve", account(2)),
+                ("user", account(7)),
+                ("name", reader.read_string()?),
+                ("symbol", reader.read_string()?),
+                ("uri", reader.read_string()?),
+            ];
+            // Newer versions of the program take the creator as an argument.
+            if reader.remaining() >= 32 {
+                fields.push(("creator", bs58::encode(reader.read_pubkey()?).into_string()));
             }
-            message.push(ix.data.len() as u8);
-            message.extend_from_slice(&ix.data);
+            ("Create", fields)
         }
+        pumpfun::BUY_DISCRIMINATOR => (
+            "Buy",
+            vec![
+                ("mint", account(2)),
+                ("user", account(6)),
+                (
+                    "tokens",
+                    format_token_amount(reader.read_u64()?, pumpfun::TOKEN_DECIMALS),
+                ),
+                ("max SOL cost", format_lamports(reader.read_u64()?)),
+            ],
+        ),
+        pumpfun::SELL_DISCRIMINATOR => (
+            "Sell",
+            vec![
+                ("mint", account(2)),
+                ("user", account(6)),
+                (
+                    "tokens",
+                    format_token_amount(reader.read_u64()?, pumpfun::TOKEN_DECIMALS),
+                ),
+                ("min SOL output", format_lamports(reader.read_u64()?)),
+            ],
+        ),
+        _ => bail!("Unknown pump.fun instruction"),
+    })
+}
 
-        Ok(message)
+/// Cursor over a byte slice used when decoding wire-format data.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.remaining() < len {
+            bail!(
+                "Unexpected end of data: needed {} bytes at offset {}, {} left",
+                len,
+                self.pos,
+                self.remaining()
+            );
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    pub(crate) fn peek_u8(&self) -> Result<u8> {
+        match self.bytes.get(self.pos) {
+            Some(byte) => Ok(*byte),
+            None => bail!("Unexpected end of data at offset {}", self.pos),
+        }
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(
+            self.read_bytes(2)?.try_into().expect("read 2 bytes"),
+        ))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(
+            self.read_bytes(4)?.try_into().expect("read 4 bytes"),
+        ))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(
+            self.read_bytes(8)?.try_into().expect("read 8 bytes"),
+        ))
+    }
+
+    /// Read a Borsh string: a u32 length followed by UTF-8 bytes.
+    pub(crate) fn read_string(&mut self) -> Result<String> {
+        let len = self.read_u32()? as usize;
+        String::from_utf8(self.read_bytes(len)?.to_vec()).context("String is not valid UTF-8")
+    }
+
+    pub(crate) fn read_pubkey(&mut self) -> Result<[u8; 32]> {
+        Ok(self.read_bytes(32)?.try_into().expect("read 32 bytes"))
+    }
+
+    pub(crate) fn read_compact_u16(&mut self) -> Result<usize> {
+        let mut value: usize = 0;
+        for i in 0..3 {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as usize) << (i * 7);
+            if byte & 0x80 == 0 {
+                if value > u16::MAX as usize || (i > 0 && byte == 0) {
+                    bail!("Invalid compact-u16 encoding");
+                }
+                return Ok(value);
+            }
+        }
+        bail!("Invalid compact-u16 encoding: too many bytes")
     }
 }
 
-impl Default for TransactionBuilder {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixtures produced with solana-sdk 2.2 (`Transaction::sign` + bincode).
+    const BLOCKHASH: &str = "LbUiWL3xVV8hTFYBVdbTNrpDo41NKS6o3LHHuDzjfcY";
+    const TRANSFER_TX: &str = "AY8VJizw/Coa1Z0ViAKT9NvCiQ10WObfDhWWPrjzVzPNXjdZsKJdFYDUIIfL/I+7OBEwK71X5hKTRNR4ZmFfWQ4BAAED6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iwJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUBAgIAAQwCAAAAQEIPAAAAAAA=";
+    const MULTI_TX: &str = "AqssMFSFUP6WlDxpnyVm204qHKvzE6JlDBlE2pD3+raAzVxNQVKE6zfo71+fzB3c9acWgUk0Mpbya0FXH1kb9QMdAyfp/kADK1uP5OB1HnSk6kmAKBzJWouijyzvam1APN+hAVp61RNdlhl2y7fXFMRYP0isjRdrc/1OimWvDtEPAgEDBupKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIs7UkoxijRwsbq6QM4kFmVYSlZJzpcY/k2NsFGFKyHN9EFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBCqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqgUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFAgUDBAIBAwECAwMCAAIMAgAAACoAAAAAAAAA";
+
+    const V0_TX: &str = "AqgMQMeIbbh/Rr4wNM1/jc4LcGk2/s1kK3evFRQK/h9Dia3LGKtyxuKRC70y5xi3q6QNEt6BrvheHPm8tdzIEw+XHnEiUptUetsgtCx9twg0XbyEwfV9n831qSpMn0UoPgJDwzBcFBDl6ZNvAlxvVjC8Ktz3GIMmHXwM0jPSd28PgAIBAwXqSmxj4pxSCr71UHsTLsX5lUd2rr6+e5JCHuppFEbSLO1JKMYo0cLG6ukDOJBZlWEpWSc6XGP5NjbBRhSshzfRAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMKqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUCBAQGBQEDAwECAwICAAUMAgAAACoAAAAAAAAAAczMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMAQABAQ==";
+    const NONCE_TX: &str = "AdjSMRamoXDX6Zz1VA4zbM+IOhlKAMkntHxm775WJ2wsAVE1GIsLgX1uGoHAKlGrxjlMOCreMGl43e+kTdHMOQSAAQACBepKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIsCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkREREREREREREREREREREREREREREREREREREREREREQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABqfVFxksVo7gioRfc9KXiM8DXDFFshqzRNgGLqlAAACD8QYRDv8no1y5BiuNCDGegS7yGM7/K3IjM/NGutJRnQIDAwIEAAQEAAAAAwIAAQwCAAAAKgAAAAAAAAAA";
+    const LOOKUP_TABLE_DATA: &str = "AQAAAP//////////ewAAAAAAAAAAAepKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIsAAAFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBRAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICA=";
+
+    fn keypair_from_seed(seed: u8) -> SecretKeypair {
+        SecretKeypair::from_secret(&[seed; 32])
+    }
+
+    fn pubkey(keypair: &SecretKeypair) -> [u8; 32] {
+        keypair.pubkey()
+    }
+
+    fn transfer(from: [u8; 32], to: [u8; 32], lamports: u64) -> Instruction {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&lamports.to_le_bytes());
+        Instruction {
+            program_id: [0u8; 32],
+            accounts: vec![AccountMeta::new(from, true), AccountMeta::new(to, false)],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let limit = set_compute_unit_limit(200_000);
+        assert_eq!(limit.data, vec![2, 0x40, 0x0d, 0x03, 0x00]);
+        assert!(limit.accounts.is_empty());
+        assert_eq!(
+            bs58::encode(limit.program_id).into_string(),
+            programs::COMPUTE_BUDGET_PROGRAM_ID
+        );
+
+        let price = set_compute_unit_price(1_000);
+        assert_eq!(price.data, vec![3, 0xe8, 0x03, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_compact_u16_encoding() {
+        for (value, expected) in [
+            (0usize, vec![0x00]),
+            (0x7f, vec![0x7f]),
+            (0x80, vec![0x80, 0x01]),
+            (0x3fff, vec![0xff, 0x7f]),
+            (0x4000, vec![0x80, 0x80, 0x01]),
+            (0xffff, vec![0xff, 0xff, 0x03]),
+        ] {
+            let mut out = Vec::new();
+            encode_compact_u16(value, &mut out);
+            assert_eq!(out, expected);
+            assert_eq!(ByteReader::new(&out).read_compact_u16().unwrap(), value);
+        }
+        assert!(ByteReader::new(&[0x80, 0x00]).read_compact_u16().is_err());
+    }
+
+    #[test]
+    fn test_transfer_matches_solana_sdk() {
+        let payer = keypair_from_seed(7);
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(transfer(pubkey(&payer), [9u8; 32], 1_000_000))
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .sign(&payer)
+            .unwrap();
+
+        let tx = builder.build_transaction().unwrap();
+        assert!(tx.is_fully_signed());
+        assert_eq!(tx.to_base64(), TRANSFER_TX);
+        assert_eq!(
+            builder.build().unwrap(),
+            Transaction::from_base64(TRANSFER_TX).unwrap().serialize()
+        );
+    }
+
+    #[test]
+    fn test_account_ordering_matches_solana_sdk() {
+        let payer = keypair_from_seed(7);
+        let cosigner = keypair_from_seed(3);
+        let custom = Instruction {
+            program_id: [0xAA; 32],
+            accounts: vec![
+                AccountMeta::new_readonly([0x10; 32], false),
+                AccountMeta::new([0x05; 32], false),
+                AccountMeta::new_readonly(pubkey(&cosigner), true),
+            ],
+            data: vec![1, 2, 3],
+        };
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(custom)
+            .add_instruction(transfer(pubkey(&payer), [0x05; 32], 42))
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string());
+        // Signing order must not matter; signatures follow account-key order.
+        builder.sign(&cosigner).unwrap();
+        builder.sign(&payer).unwrap();
+
+        let tx = builder.build_transaction().unwrap();
+        assert_eq!(
+            tx.message.header,
+            MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 3,
+            }
+        );
+        assert_eq!(tx.to_base64(), MULTI_TX);
+        assert!(tx.message.is_writable(0));
+        assert!(!tx.message.is_writable(1));
+        assert!(tx.message.is_writable(2));
+        assert!(!tx.message.is_writable(3));
+    }
+
+    #[test]
+    fn test_deserialize_round_trip() {
+        for fixture in [TRANSFER_TX, MULTI_TX] {
+            let tx = Transaction::from_base64(fixture).unwrap();
+            assert_eq!(tx.to_base64(), fixture);
+            assert_eq!(Transaction::from_base58(&tx.to_base58()).unwrap(), tx);
+            let message = Message::deserialize(&tx.message.serialize()).unwrap();
+            assert_eq!(message, tx.message);
+        }
+    }
+
+    // Matches solana-sdk for an advance-nonce instruction followed by a transfer.
+    #[test]
+    fn test_durable_nonce_transaction() {
+        let authority = keypair_from_seed(7);
+        let nonce_account = [0x11; 32];
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(advance_nonce_account(&nonce_account, &pubkey(&authority)))
+            .add_instruction(transfer(pubkey(&authority), [9u8; 32], 42))
+            .set_fee_payer(pubkey(&authority))
+            .set_recent_blockhash("9t3a7oPtPTwKyY3d62K8fiUXesw8PsyG6nAJDSNrKpk4".to_string())
+            .sign(&authority)
+            .unwrap();
+        let tx = builder.build_versioned_transaction().unwrap();
+        assert_eq!(tx.to_base64(), NONCE_TX);
+        assert_eq!(tx.message.durable_nonce_account(), Some(nonce_account));
+
+        let plain = VersionedTransaction::from_base64(V0_TX).unwrap();
+        assert_eq!(plain.message.durable_nonce_account(), None);
+    }
+
+    #[test]
+    fn test_sign_rejects_non_signer() {
+        let payer = keypair_from_seed(7);
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(transfer(pubkey(&payer), [9u8; 32], 1))
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .sign(&keypair_from_seed(1))
+            .unwrap();
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_verify_signatures() {
+        let mut tx = VersionedTransaction::from_base64(MULTI_TX).unwrap();
+        assert_eq!(tx.verify_signatures(), vec![Some(true), Some(true)]);
+
+        tx.signatures[1] = [0u8; 64];
+        assert_eq!(tx.verify_signatures(), vec![Some(true), None]);
+
+        tx.signatures[0][0] ^= 1;
+        assert_eq!(tx.verify_signatures(), vec![Some(false), None]);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_truncated() {
+        let bytes = Transaction::from_base64(TRANSFER_TX).unwrap().serialize();
+        assert!(Transaction::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    fn lookup_table() -> AddressLookupTable {
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(LOOKUP_TABLE_DATA)
+            .unwrap();
+        AddressLookupTable::deserialize([0xCC; 32], &data).unwrap()
+    }
+
+    #[test]
+    fn test_lookup_table_deserialize() {
+        let table = lookup_table();
+        assert_eq!(table.addresses, vec![[0x05; 32], [0x10; 32], [0x20; 32]]);
+        assert_eq!(table.authority, Some(pubkey(&keypair_from_seed(7))));
+        assert!(!table.is_deactivated());
+        assert!(AddressLookupTable::deserialize([0xCC; 32], &[0u8; 56]).is_err());
+    }
+
+    #[test]
+    fn test_v0_matches_solana_sdk() {
+        let payer = keypair_from_seed(7);
+        let cosigner = keypair_from_seed(3);
+        let custom = Instruction {
+            program_id: [0xAA; 32],
+            accounts: vec![
+                AccountMeta::new_readonly([0x10; 32], false),
+                AccountMeta::new([0x05; 32], false),
+                AccountMeta::new_readonly(pubkey(&cosigner), true),
+                AccountMeta::new_readonly([0x30; 32], false),
+            ],
+            data: vec![1, 2, 3],
+        };
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(custom)
+            .add_instruction(transfer(pubkey(&payer), [0x05; 32], 42))
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .add_lookup_table(lookup_table());
+        builder.sign(&payer).unwrap();
+        builder.sign(&cosigner).unwrap();
+
+        let tx = builder.build_versioned_transaction().unwrap();
+        assert_eq!(tx.to_base64(), V0_TX);
+
+        let VersionedMessage::V0(ref message) = tx.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.account_keys.len(), 5);
+        assert_eq!(message.num_loaded_accounts(), 2);
+        let loaded = message.resolve_addresses(&[lookup_table()]).unwrap();
+        assert_eq!(loaded.writable, vec![[0x05; 32]]);
+        assert_eq!(loaded.readonly, vec![[0x10; 32]]);
+        assert!(message.is_writable(5));
+        assert!(!message.is_writable(6));
+    }
+
+    #[test]
+    fn test_compile_skips_deactivated_lookup_table() {
+        let payer = pubkey(&keypair_from_seed(7));
+        let instructions = [transfer(payer, [0x05; 32], 42)];
+        let blockhash = [0x11; 32];
+
+        let mut table = lookup_table();
+        let active =
+            MessageV0::compile(&instructions, &payer, &[table.clone()], blockhash).unwrap();
+        assert_eq!(active.num_loaded_accounts(), 1);
+
+        table.deactivation_slot = 250_000_000;
+        assert!(table.is_deactivated());
+        let message = MessageV0::compile(&instructions, &payer, &[table], blockhash).unwrap();
+        assert!(message.address_table_lookups.is_empty());
+        assert!(message.account_keys.contains(&[0x05; 32]));
+    }
+
+    #[test]
+    fn test_versioned_round_trip() {
+        let tx = VersionedTransaction::from_base64(V0_TX).unwrap();
+        assert!(matches!(tx.message, VersionedMessage::V0(_)));
+        assert_eq!(tx.to_base64(), V0_TX);
+        assert_eq!(tx.message.serialize()[0], MESSAGE_VERSION_PREFIX);
+
+        let legacy = VersionedTransaction::from_base64(TRANSFER_TX).unwrap();
+        assert!(matches!(legacy.message, VersionedMessage::Legacy(_)));
+        assert!(Transaction::from_base64(V0_TX).is_err());
+    }
+
+    #[test]
+    fn test_decompile_round_trip() {
+        let tx = VersionedTransaction::from_base64(V0_TX).unwrap();
+        let VersionedMessage::V0(ref message) = tx.message else {
+            panic!("expected a v0 message");
+        };
+        assert!(tx.message.decompile(&LoadedAddresses::default()).is_err());
+
+        let loaded = message.resolve_addresses(&[lookup_table()]).unwrap();
+        let instructions = tx.message.decompile(&loaded).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].program_id, [0xAA; 32]);
+        assert_eq!(instructions[0].data, vec![1, 2, 3]);
+        let flags: Vec<_> = instructions[0]
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ([0x10; 32], false, false),
+                ([0x05; 32], false, true),
+                (pubkey(&keypair_from_seed(3)), true, false),
+                ([0x30; 32], false, false),
+            ]
+        );
+
+        // Recompiling the expanded instructions reproduces the original message.
+        let mut builder = TransactionBuilder::new();
+        for ix in instructions {
+            builder.add_instruction(ix);
+        }
+        builder
+            .set_fee_payer(tx.message.static_account_keys()[0])
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .add_lookup_table(lookup_table());
+        let rebuilt = builder.compile_message_v0().unwrap();
+        assert_eq!(&rebuilt, message);
+    }
+
+    #[test]
+    fn test_oversized_transaction_rejected_before_signing() {
+        let payer = keypair_from_seed(7);
+        let accounts: Vec<[u8; 32]> = (100..140).map(|i| [i; 32]).collect();
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(Instruction {
+                program_id: [0xAA; 32],
+                accounts: accounts
+                    .iter()
+                    .map(|key| AccountMeta::new(*key, false))
+                    .collect(),
+                data: vec![],
+            })
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string())
+            .sign(&payer)
+            .unwrap();
+        assert!(builder.build_versioned_transaction().is_err());
+        assert!(builder.build_v0().is_err());
+
+        // Loading the same accounts from a lookup table brings it under the limit.
+        builder.add_lookup_table(AddressLookupTable::new([0xBB; 32], accounts));
+        let tx = builder.build_versioned_transaction().unwrap();
+        assert!(tx.serialize().len() <= PACKET_DATA_SIZE);
+        assert!(tx.is_fully_signed());
+
+        // A length a compact-u16 cannot hold is refused before it is encoded.
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(Instruction {
+                program_id: [0xAA; 32],
+                accounts: vec![],
+                data: vec![0; u16::MAX as usize + 1],
+            })
+            .set_fee_payer(pubkey(&payer))
+            .set_recent_blockhash(BLOCKHASH.to_string());
+        assert!(builder.build_transaction().is_err());
+        assert!(builder.build_versioned_transaction().is_err());
+    }
+
+    #[test]
+    fn test_resolve_missing_lookup_table() {
+        let tx = VersionedTransaction::from_base64(V0_TX).unwrap();
+        let VersionedMessage::V0(message) = tx.message else {
+            panic!("expected a v0 message");
+        };
+        assert!(message.resolve_addresses(&[]).is_err());
+    }
+
+    fn borsh_string(value: &str) -> Vec<u8> {
+        let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(value.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        let decoded = VersionedTransaction::from_base64(TRANSFER_TX)
+            .unwrap()
+            .decode();
+        assert_eq!(decoded.version, None);
+        assert_eq!(decoded.accounts.len(), 3);
+        assert!(decoded.accounts[0].is_signer && decoded.accounts[0].is_writable);
+        assert_eq!(decoded.signatures[0].verified, Some(true));
+
+        let ix = &decoded.instructions[0];
+        assert_eq!(ix.program, Some("System"));
+        assert_eq!(ix.name, Some("Transfer"));
+        assert_eq!(ix.fields[0].1, decoded.accounts[0].address);
+        assert_eq!(
+            ix.fields[2],
+            ("lamports", "0.001 SOL (1000000 lamports)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_v0_marks_lookup_accounts() {
+        let decoded = VersionedTransaction::from_base64(V0_TX).unwrap().decode();
+        assert_eq!(decoded.version, Some(0));
+        let loaded: Vec<_> = decoded
+            .accounts
+            .iter()
+            .filter(|a| a.from_lookup_table)
+            .collect();
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded[0]
+            .address
+            .starts_with(&bs58::encode([0xCC; 32]).into_string()));
+        assert!(loaded[0].is_writable && !loaded[1].is_writable);
+    }
+
+    #[test]
+    fn test_decode_known_instructions() {
+        let user = keypair_from_seed(7);
+        let mut buy = pumpfun::BUY_DISCRIMINATOR.to_vec();
+        buy.extend_from_slice(&1_500_000u64.to_le_bytes());
+        buy.extend_from_slice(&250_000_000u64.to_le_bytes());
+
+        let mut metadata = vec![33];
+        for field in ["Frog Coin", "FROG", "https://arweave.net/frog"] {
+            metadata.extend(borsh_string(field));
+        }
+        metadata.extend_from_slice(&0u16.to_le_bytes());
+
+        let mut transfer_checked = vec![12];
+        transfer_checked.extend_from_slice(&2_500_000u64.to_le_bytes());
+        transfer_checked.push(6);
+
+        let program = |id| programs::program_id(id);
+        let accounts = |n: u8| -> Vec<AccountMeta> {
+            (0..n)
+                .map(|i| AccountMeta::new([0x40 + i; 32], false))
+                .chain([AccountMeta::new(pubkey(&user), true)])
+                .collect()
+        };
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_instruction(set_compute_unit_price(10_000))
+            .add_instruction(Instruction {
+                program_id: program(programs::PUMP_FUN_PROGRAM_ID),
+                accounts: accounts(6),
+                data: buy,
+            })
+            .add_instruction(Instruction {
+                program_id: program(programs::METADATA_PROGRAM_ID),
+                accounts: accounts(4),
+                data: metadata,
+            })
+            .add_instruction(Instruction {
+                program_id: program(programs::TOKEN_PROGRAM_ID),
+                accounts: accounts(3),
+                data: transfer_checked,
+            })
+            .add_instruction(Instruction {
+                program_id: program(programs::ASSOCIATED_TOKEN_PROGRAM_ID),
+                accounts: accounts(3),
+                data: vec![1],
+            })
+            .set_fee_payer(pubkey(&user))
+            .set_recent_blockhash(BLOCKHASH.to_string());
+        let tx: VersionedTransaction = builder.build_transaction().unwrap().into();
+        let decoded = tx.decode();
+        assert_eq!(decoded.signatures[0].verified, None);
+
+        let names: Vec<_> = decoded.instructions.iter().map(|ix| ix.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("SetComputeUnitPrice"),
+                Some("Buy"),
+                Some("CreateMetadataAccountV3"),
+                Some("TransferChecked"),
+                Some("CreateIdempotent"),
+            ]
+        );
+
+        let field = |ix: usize, label: &str| {
+            decoded.instructions[ix]
+                .fields
+                .iter()
+                .find(|(l, _)| *l == label)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+        assert_eq!(field(1, "tokens"), "1.500000");
+        assert_eq!(field(1, "max SOL cost"), "0.25 SOL (250000000 lamports)");
+        assert_eq!(field(1, "user"), bs58::encode(pubkey(&user)).into_string());
+        assert_eq!(field(2, "symbol"), "FROG");
+        assert_eq!(field(2, "uri"), "https://arweave.net/frog");
+        assert_eq!(field(3, "amount"), "2.500000");
+    }
+
+    #[test]
+    fn test_decode_unknown_instruction() {
+        let decoded = VersionedTransaction::from_base64(MULTI_TX)
+            .unwrap()
+            .decode();
+        let custom = &decoded.instructions[0];
+        assert_eq!(custom.program, None);
+        assert_eq!(custom.name, None);
+        assert_eq!(custom.data, vec![1, 2, 3]);
+        assert_eq!(custom.accounts.len(), 3);
     }
 }
 
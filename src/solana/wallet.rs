@@ -1,74 +1,273 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
-use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use ed25519_dalek::{Verifier, VerifyingKey};
 use log::debug;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
 
-use crate::solana::keypair;
+use crate::config::settings::Settings;
+use crate::solana::keypair::{self, SecretKeypair};
+use crate::solana::keystore::{self, Keystore, ScryptParams};
+use crate::solana::offchain::OffchainMessage;
+
+/// The statement a wallet signs to prove it belongs to an agent.
+///
+/// Binding the agent, wallet, network and a server-issued nonce into the text
+/// means a signature is only good for one verification on one cluster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipMessage {
+    pub agent_id: String,
+    pub wallet_address: String,
+    pub network: String,
+    pub issued_at: String,
+    pub nonce: String,
+}
+
+impl OwnershipMessage {
+    /// The human-readable text shown to and signed by the wallet owner.
+    pub fn text(&self) -> String {
+        format!(
+            "FrogPump wallet verification\n\n\
+             Agent: {}\n\
+             Wallet: {}\n\
+             Network: {}\n\
+             Issued At: {}\n\
+             Nonce: {}",
+            self.agent_id, self.wallet_address, self.network, self.issued_at, self.nonce
+        )
+    }
+
+    /// The exact bytes that are signed: the text in the Solana off-chain message format.
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(OffchainMessage::new(&self.text())?.serialize())
+    }
+}
+
+/// Name of the wallet kept in the original single-wallet location, `wallet.json`.
+pub const DEFAULT_WALLET: &str = "default";
+
+const REGISTRY_FILE: &str = "wallets.toml";
+const WALLETS_DIR: &str = "wallets";
+
+/// Installation-wide wallet choices, kept in `wallets.toml` next to the wallets.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletRegistry {
+    /// Wallet used when neither `--wallet` nor the agent's settings pick one.
+    pub active: Option<String>,
+    /// Free-form descriptions, by wallet name.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl WalletRegistry {
+    /// Load the registry from `config_dir`, or an empty one if there is none yet.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(REGISTRY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .context(format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).context(format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(config_dir).context("Failed to create config directory")?;
+        let path = config_dir.join(REGISTRY_FILE);
+        let content = toml::to_string_pretty(self).context("Failed to serialize wallet registry")?;
+        std::fs::write(&path, content).context(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// A local wallet as shown by `wallet list`.
+#[derive(Debug, Clone)]
+pub struct WalletSummary {
+    pub name: String,
+    pub address: String,
+    pub encrypted: bool,
+    pub label: Option<String>,
+    /// Agents linked to this wallet in the settings.
+    pub agents: Vec<String>,
+    /// Whether commands run now would sign with this wallet.
+    pub selected: bool,
+}
 
 /// Manages local wallet keypair storage and signing operations.
+///
+/// An installation can hold several named wallets. The `default` wallet lives in
+/// `wallet.json` in the config directory and the others in `wallets/<name>.json`.
 pub struct WalletManager {
     config_dir: PathBuf,
+    name: String,
 }
 
 impl WalletManager {
-    /// Create a new WalletManager using the application config directory.
+    /// Create a WalletManager for the default wallet in the application config directory.
     pub fn new() -> Self {
+        Self::with_config_dir(crate::config_dir())
+    }
+
+    /// Create a WalletManager for the default wallet in `config_dir`.
+    pub fn with_config_dir(config_dir: PathBuf) -> Self {
         Self {
-            config_dir: crate::config_dir(),
+            config_dir,
+            name: DEFAULT_WALLET.to_string(),
         }
     }
 
-    /// Load the keypair bytes from the local wallet file.
-    pub fn load_keypair(&self) -> Result<[u8; 64]> {
-        let path = self.config_dir.join("wallet.json");
-        debug!("Loading keypair from {}", path.display());
+    /// Pick the wallet to use: the `--wallet` flag, then the wallet linked to
+    /// the agent in the settings, then the registry's active wallet.
+    pub fn select(config: &Settings, agent_id: Option<&str>) -> Result<Self> {
+        Self::new().resolve(config, agent_id)
+    }
+
+    fn resolve(&self, config: &Settings, agent_id: Option<&str>) -> Result<Self> {
+        let name = match config.wallet_for(agent_id) {
+            Some(name) => name,
+            None => self
+                .registry()?
+                .active
+                .unwrap_or_else(|| DEFAULT_WALLET.to_string()),
+        };
+        self.named(&name)
+    }
+
+    /// The wallet called `name` in the same config directory.
+    pub fn named(&self, name: &str) -> Result<Self> {
+        validate_wallet_name(name)?;
+        Ok(Self {
+            config_dir: self.config_dir.clone(),
+            name: name.to_string(),
+        })
+    }
+
+    /// Name of the wallet this manager reads and writes.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Path of the local wallet file.
+    pub fn wallet_path(&self) -> PathBuf {
+        if self.name == DEFAULT_WALLET {
+            self.config_dir.join("wallet.json")
+        } else {
+            self.config_dir
+                .join(WALLETS_DIR)
+                .join(format!("{}.json", self.name))
+        }
+    }
+
+    /// Whether a local wallet file exists.
+    pub fn exists(&self) -> bool {
+        self.wallet_path().exists()
+    }
+
+    /// Names of all wallets in the config directory, sorted.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        if self.named(DEFAULT_WALLET)?.exists() {
+            names.push(DEFAULT_WALLET.to_string());
+        }
+        let dir = self.config_dir.join(WALLETS_DIR);
+        if dir.exists() {
+            for entry in std::fs::read_dir(&dir)
+                .context(format!("Failed to list {}", dir.display()))?
+            {
+                let path = entry?.path();
+                let name = path.file_stem().and_then(|stem| stem.to_str());
+                if let (Some("json"), Some(name)) =
+                    (path.extension().and_then(|ext| ext.to_str()), name)
+                {
+                    if validate_wallet_name(name).is_ok() && name != DEFAULT_WALLET {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// The registry of the config directory this wallet lives in.
+    pub fn registry(&self) -> Result<WalletRegistry> {
+        WalletRegistry::load(&self.config_dir)
+    }
 
-        let data = std::fs::read_to_string(&path)
-            .context(format!("No wallet found at {}", path.display()))?;
+    pub fn save_registry(&self, registry: &WalletRegistry) -> Result<()> {
+        registry.save(&self.config_dir)
+    }
 
-        let bytes: Vec<u8> =
-            serde_json::from_str(&data).context("Invalid wallet file format")?;
+    /// Load the keypair from the local wallet file, asking for the
+    /// passphrase if the wallet is encrypted.
+    pub fn load_keypair(&self) -> Result<SecretKeypair> {
+        let path = self.wallet_path();
+        debug!("Loading keypair from {}", path.display());
 
-        if bytes.len() != 64 {
-            bail!(
-                "Invalid keypair length: expected 64 bytes, got {}",
-                bytes.len()
-            );
+        let data = self.read_wallet_file()?;
+        if Keystore::is_keystore(&data) {
+            let keystore = parse_keystore(&data)?;
+            let passphrase = keystore::read_passphrase(&format!(
+                "Passphrase for wallet {}: ",
+                keystore.pubkey
+            ))?;
+            return keystore.decrypt(&passphrase);
         }
+        SecretKeypair::from_json(&data).context("Invalid wallet file format")
+    }
+
+    /// Save the keypair to the local wallet file in plaintext, readable only by the current user.
+    pub fn save_keypair(&self, keypair: &SecretKeypair) -> Result<()> {
+        self.write_wallet_file(&keypair.to_json())
+    }
 
-        let mut keypair = [0u8; 64];
-        keypair.copy_from_slice(&bytes);
-        Ok(keypair)
+    /// Save the keypair to the local wallet file encrypted under `passphrase`.
+    pub fn save_encrypted(&self, keypair: &SecretKeypair, passphrase: &str) -> Result<()> {
+        let keystore = Keystore::encrypt(keypair, passphrase, ScryptParams::default())?;
+        let json = serde_json::to_string_pretty(&keystore)
+            .context("Failed to serialize keystore")?;
+        self.write_wallet_file(&json)
     }
 
-    /// Save keypair bytes to the local wallet file.
-    pub fn save_keypair(&self, bytes: &[u8; 64]) -> Result<()> {
-        std::fs::create_dir_all(&self.config_dir)
-            .context("Failed to create config directory")?;
+    /// Whether the local wallet file is an encrypted keystore.
+    pub fn is_encrypted(&self) -> Result<bool> {
+        Ok(Keystore::is_keystore(&self.read_wallet_file()?))
+    }
 
-        let path = self.config_dir.join("wallet.json");
-        let json = serde_json::to_string(&bytes.to_vec())
-            .context("Failed to serialize keypair")?;
+    fn read_wallet_file(&self) -> Result<Zeroizing<String>> {
+        let path = self.wallet_path();
+        std::fs::read_to_string(&path)
+            .map(Zeroizing::new)
+            .context(format!("No wallet found at {}", path.display()))
+    }
+
+    fn write_wallet_file(&self, contents: &str) -> Result<()> {
+        let path = self.wallet_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
 
-        std::fs::write(&path, json).context(format!("Failed to write wallet to {}", path.display()))?;
+        keypair::write_secret_file(&path, contents)?;
         debug!("Keypair saved to {}", path.display());
         Ok(())
     }
 
     /// Derive the base58-encoded public key from the stored keypair.
+    ///
+    /// An encrypted wallet records its public key in the clear, so no passphrase is needed.
     pub fn get_public_key(&self) -> Result<String> {
-        let kp = self.load_keypair()?;
-        keypair::pubkey_from_bytes(&kp[32..])
+        let data = self.read_wallet_file()?;
+        if Keystore::is_keystore(&data) {
+            return Ok(parse_keystore(&data)?.pubkey);
+        }
+        let kp = SecretKeypair::from_json(&data).context("Invalid wallet file format")?;
+        Ok(kp.address())
     }
 
     /// Sign an arbitrary message with the stored keypair.
     pub fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let kp = self.load_keypair()?;
-        let secret: [u8; 32] = kp[..32].try_into().context("Invalid secret key bytes")?;
-        let signing_key = SigningKey::from_bytes(&secret);
-        let signature = signing_key.sign(message);
-        Ok(signature.to_bytes().to_vec())
+        Ok(self.load_keypair()?.sign(message).to_vec())
     }
 
     /// Verify a signature against a public key and message.
@@ -92,4 +291,123 @@ impl WalletManager {
     }
 }
 
+/// Wallet names become file names, so keep them to letters, digits, `-` and `_`.
+fn validate_wallet_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 32 {
+        bail!("Wallet name must be 1-32 characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!(
+            "Wallet name '{}' may only contain letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+fn parse_keystore(data: &str) -> Result<Keystore> {
+    serde_json::from_str(data).context("Invalid wallet keystore format")
+}
+
+impl Default for WalletManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ownership_message() {
+        let message = OwnershipMessage {
+            agent_id: "agent-7".to_string(),
+            wallet_address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+            network: "devnet".to_string(),
+            issued_at: "2026-10-18T10:00:00Z".to_string(),
+            nonce: "n0nce".to_string(),
+        };
+        assert_eq!(
+            message.text(),
+            "FrogPump wallet verification\n\nAgent: agent-7\n\
+             Wallet: 9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM\nNetwork: devnet\n\
+             Issued At: 2026-10-18T10:00:00Z\nNonce: n0nce"
+        );
+        let bytes = message.signing_bytes().unwrap();
+        assert!(bytes.starts_with(crate::solana::offchain::SIGNING_DOMAIN));
+        assert!(bytes.ends_with(message.text().as_bytes()));
+    }
+
+    #[test]
+    fn test_named_wallets() {
+        let dir = std::env::temp_dir().join(format!("frogpump-wallets-{}", std::process::id()));
+        let manager = WalletManager::with_config_dir(dir.clone());
+        let kp = SecretKeypair::generate();
+
+        let trader = manager.named("trader").unwrap();
+        trader.save_keypair(&kp).unwrap();
+        manager.save_keypair(&kp).unwrap();
+        assert_eq!(trader.wallet_path(), dir.join("wallets").join("trader.json"));
+        assert_eq!(manager.list().unwrap(), ["default", "trader"]);
+        assert!(manager.named("../escape").is_err());
+
+        let mut config = Settings::default();
+        assert_eq!(manager.resolve(&config, None).unwrap().name(), DEFAULT_WALLET);
+
+        manager
+            .save_registry(&WalletRegistry {
+                active: Some("trader".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(manager.resolve(&config, None).unwrap().name(), "trader");
+
+        config
+            .agent_wallets
+            .insert("agent-7".to_string(), "sniper".to_string());
+        assert_eq!(manager.resolve(&config, Some("agent-7")).unwrap().name(), "sniper");
+        assert_eq!(manager.resolve(&config, Some("agent-8")).unwrap().name(), "trader");
+
+        config.wallet_override = Some("default".to_string());
+        assert_eq!(manager.resolve(&config, Some("agent-7")).unwrap().name(), DEFAULT_WALLET);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_and_load_keypair() {
+        let dir = std::env::temp_dir().join(format!("frogpump-wallet-{}", std::process::id()));
+        let manager = WalletManager::with_config_dir(dir.clone());
+        let kp = SecretKeypair::generate();
+
+        assert!(!manager.exists());
+        manager.save_keypair(&kp).unwrap();
+        assert_eq!(manager.load_keypair().unwrap().expose_secret(), kp.expose_secret());
+        assert_eq!(manager.get_public_key().unwrap(), kp.address());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(manager.wallet_path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let message = b"frogpump:verify:agent";
+        let signature = manager.sign_message(message).unwrap();
+        assert!(WalletManager::verify_signature(&kp.pubkey(), message, &signature).unwrap());
+        assert!(!WalletManager::verify_signature(&kp.pubkey(), b"other", &signature).unwrap());
+
+        // A tampered wallet file is rejected rather than signing with a mismatched key.
+        let mut bytes = kp.expose_secret().to_vec();
+        bytes[63] ^= 1;
+        std::fs::write(manager.wallet_path(), serde_json::to_string(&bytes).unwrap()).unwrap();
+        assert!(manager.load_keypair().is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
+
 // iteration 91
@@ -1,9 +1,36 @@
 use colored::Colorize;
+use zeroize::Zeroizing;
 
 pub fn format_sol(amount: f64) -> String {
     format!("{:.4} SOL", amount)
 }
 
+/// Format a lamport amount as SOL followed by the exact lamports.
+pub fn format_lamports(lamports: u64) -> String {
+    let sol = format_token_amount(lamports, 9);
+    let sol = sol.trim_end_matches('0').trim_end_matches('.');
+    format!("{} SOL ({} lamports)", sol, lamports)
+}
+
+/// Format an integer token amount with its decimal point placed exactly.
+pub fn format_token_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let scale = 10u64.pow(decimals as u32);
+    format!(
+        "{}.{:0width$}",
+        amount / scale,
+        amount % scale,
+        width = decimals as usize
+    )
+}
+
+/// Format basis points as a percentage with two decimals.
+pub fn format_bps(bps: u64) -> String {
+    format!("{}.{:02}%", bps / 100, bps % 100)
+}
+
 pub fn format_usd(amount: f64) -> String {
     let formatted = if amount >= 1_000.0 {
         let whole = amount as u64;
@@ -63,6 +90,69 @@ pub fn format_rank(rank: u32) -> String {
     }
 }
 
+/// Ask a yes/no question on stderr and read the answer from stdin. Anything but "y" or "yes" is a no.
+pub fn confirm(question: &str) -> std::io::Result<bool> {
+    use std::io::Write;
+
+    eprint!("{} [y/N] ", question);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Prompt on stderr and read a line from stdin without echoing it to the terminal.
+pub fn prompt_secret(prompt: &str) -> std::io::Result<Zeroizing<String>> {
+    use std::io::Write;
+
+    eprint!("{}", prompt);
+    std::io::stderr().flush()?;
+    let mut line = Zeroizing::new(String::new());
+    {
+        #[cfg(unix)]
+        let _echo = EchoGuard::disable();
+        std::io::stdin().read_line(&mut line)?;
+    }
+    eprintln!();
+    Ok(Zeroizing::new(
+        line.trim_end_matches(['\r', '\n']).to_string(),
+    ))
+}
+
+/// Turns off terminal echo on stdin until dropped; does nothing when stdin is not a terminal.
+#[cfg(unix)]
+struct EchoGuard(Option<libc::termios>);
+
+#[cfg(unix)]
+impl EchoGuard {
+    fn disable() -> Self {
+        // SAFETY: termios is plain data, and tcgetattr/tcsetattr only read and
+        // write the struct we pass for the stdin descriptor.
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return Self(None);
+            }
+            let original = termios;
+            termios.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            Self(Some(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        if let Some(original) = self.0 {
+            // SAFETY: restores the settings read in `disable`.
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original);
+            }
+        }
+    }
+}
+
 pub fn print_key_value(key: &str, value: &str) {
     println!("  {:<20} {}", key.cyan(), value);
 }
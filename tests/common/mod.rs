@@ -1,4 +1,4 @@
-use frogpump::config::{Network, Settings};
+use frogpump::config::{Commitment, Network, Settings};
 use frogpump::models::{Agent, Earning, LaunchType, Token};
 
 pub fn mock_token() -> Token {
@@ -45,6 +45,8 @@ pub fn mock_settings() -> Settings {
         network: Network::Devnet,
         rpc_url: "https://api.devnet.solana.com".to_string(),
         verbose: false,
+        commitment: Commitment::Confirmed,
+        ..Settings::default()
     }
 }
 